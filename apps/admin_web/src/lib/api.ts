@@ -1,4 +1,4 @@
-import { clearSession, getToken } from "./auth";
+import { clearSession, getRefreshToken, getToken, setTokens } from "./auth";
 
 const API_BASE = "/api/v1";
 
@@ -8,7 +8,50 @@ interface ApiResponse<T> {
   data?: T | null;
 }
 
-async function request<T>(path: string, options: RequestInit = {}): Promise<T> {
+interface RefreshResp {
+  token: string;
+  refresh_token: string;
+}
+
+let refreshing: Promise<boolean> | null = null;
+
+async function refreshTokens(): Promise<boolean> {
+  const refreshToken = getRefreshToken();
+  if (!refreshToken) {
+    return false;
+  }
+  try {
+    const response = await fetch(`${API_BASE}/auth/refresh`, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ refresh_token: refreshToken })
+    });
+    if (!response.ok) {
+      return false;
+    }
+    const payload = (await response.json()) as ApiResponse<RefreshResp>;
+    if (payload.code !== 0 || !payload.data) {
+      return false;
+    }
+    setTokens(payload.data.token, payload.data.refresh_token);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+// Refresh tokens rotate and reusing an old one revokes the session, so
+// concurrent 401s must share a single refresh call.
+function refreshOnce(): Promise<boolean> {
+  if (!refreshing) {
+    refreshing = refreshTokens().finally(() => {
+      refreshing = null;
+    });
+  }
+  return refreshing;
+}
+
+async function request<T>(path: string, options: RequestInit = {}, retried = false): Promise<T> {
   const token = getToken();
   const headers: HeadersInit = {
     "Content-Type": "application/json",
@@ -24,6 +67,13 @@ async function request<T>(path: string, options: RequestInit = {}): Promise<T> {
   });
 
   if (response.status === 401) {
+    if (!retried && token) {
+      // Another request may already have refreshed while this one was in flight.
+      const refreshed = getToken() !== token || (await refreshOnce());
+      if (refreshed) {
+        return request<T>(path, options, true);
+      }
+    }
     clearSession();
     throw new Error("unauthorized");
   }
@@ -1,8 +1,10 @@
 const TOKEN_KEY = "admin_token";
+const REFRESH_TOKEN_KEY = "admin_refresh_token";
 const USER_KEY = "admin_user";
 
 export interface LoginSession {
   token: string;
+  refreshToken?: string;
   user?: { id: number; phone: string; status: string };
   roles?: string[];
 }
@@ -11,9 +13,22 @@ export function getToken(): string | null {
   return localStorage.getItem(TOKEN_KEY);
 }
 
+export function getRefreshToken(): string | null {
+  return localStorage.getItem(REFRESH_TOKEN_KEY);
+}
+
 export function setSession(session: LoginSession) {
+  const { refreshToken, ...profile } = session;
   localStorage.setItem(TOKEN_KEY, session.token);
-  localStorage.setItem(USER_KEY, JSON.stringify(session));
+  if (refreshToken) {
+    localStorage.setItem(REFRESH_TOKEN_KEY, refreshToken);
+  }
+  localStorage.setItem(USER_KEY, JSON.stringify(profile));
+}
+
+export function setTokens(token: string, refreshToken: string) {
+  localStorage.setItem(TOKEN_KEY, token);
+  localStorage.setItem(REFRESH_TOKEN_KEY, refreshToken);
 }
 
 export function getSession(): LoginSession | null {
@@ -30,6 +45,7 @@ export function getSession(): LoginSession | null {
 
 export function clearSession() {
   localStorage.removeItem(TOKEN_KEY);
+  localStorage.removeItem(REFRESH_TOKEN_KEY);
   localStorage.removeItem(USER_KEY);
 }
 
@@ -0,0 +1,13 @@
+/** 后端金额以两位小数字符串返回（如 "800.00"），仅在展示时转换。 */
+export type Money = string;
+
+export function formatMoney(value: Money | null | undefined): string {
+  const amount = Number(value ?? 0);
+  if (!Number.isFinite(amount)) {
+    return "-";
+  }
+  return `¥ ${amount.toLocaleString("zh-CN", {
+    minimumFractionDigits: 2,
+    maximumFractionDigits: 2
+  })}`;
+}
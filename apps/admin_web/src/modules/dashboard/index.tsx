@@ -5,6 +5,7 @@ import { useEffect, useMemo, useState } from "react";
 import SectionHeader from "../../components/SectionHeader";
 import StatCard from "../../components/StatCard";
 import { apiGet } from "../../lib/api";
+import { formatMoney, type Money } from "../../lib/money";
 
 interface FocusOrder {
   key: string;
@@ -25,15 +26,15 @@ interface MetricsResp {
   disputes_period: number;
   pending_photographers: number;
   pending_merchant_approvals: number;
-  revenue_today: number;
-  revenue_period: number;
+  revenue_today: Money;
+  revenue_period: Money;
 }
 
 interface TrendPoint {
   date: string;
   orders: number;
   disputes: number;
-  revenue: number;
+  revenue: Money;
 }
 
 interface TrendResp {
@@ -53,7 +54,7 @@ interface AdminOrderListItem {
   status: string;
   user_phone?: string | null;
   photographer_phone?: string | null;
-  total_amount: number;
+  total_amount: Money;
 }
 
 const columns: ColumnsType<FocusOrder> = [
@@ -80,9 +81,9 @@ export default function DashboardPage() {
     pendingPhotographers: 0,
     disputes: 0,
     merchantApprovals: 0,
-    totalAmount: 0,
+    totalAmount: "0.00",
     ordersToday: 0,
-    revenuePeriod: 0
+    revenuePeriod: "0.00"
   });
 
   useEffect(() => {
@@ -127,7 +128,7 @@ export default function DashboardPage() {
               orderNo: `OP-${item.id}`,
               user: item.user_phone ?? "未知用户",
               photographer: item.photographer_phone ?? "未指派",
-              amount: `¥ ${item.total_amount.toFixed(2)}`,
+              amount: formatMoney(item.total_amount),
               status: item.status
             }))
           );
@@ -151,7 +152,7 @@ export default function DashboardPage() {
   }, []);
 
   const formattedTotal = useMemo(
-    () => `¥ ${stats.totalAmount.toLocaleString("zh-CN")}`,
+    () => formatMoney(stats.totalAmount),
     [stats.totalAmount]
   );
 
@@ -161,7 +162,7 @@ export default function DashboardPage() {
     {
       title: "成交额",
       dataIndex: "revenue",
-      render: (value: number) => `¥ ${value.toLocaleString("zh-CN")}`
+      render: (value: Money) => formatMoney(value)
     },
     { title: "纠纷数", dataIndex: "disputes" }
   ];
@@ -198,7 +199,7 @@ export default function DashboardPage() {
           title="今日成交额"
           value={formattedTotal}
           metaLeft={`今日订单 ${stats.ordersToday}`}
-          metaRight={`近${stats.periodDays}日 ¥ ${stats.revenuePeriod.toLocaleString("zh-CN")}`}
+          metaRight={`近${stats.periodDays}日 ${formatMoney(stats.revenuePeriod)}`}
           icon={<RiseOutlined />}
         />
       </div>
@@ -3,6 +3,7 @@ import type { ColumnsType } from "antd/es/table";
 import { useCallback, useEffect, useMemo, useState } from "react";
 import SectionHeader from "../../components/SectionHeader";
 import { apiGet, apiPost, apiPut } from "../../lib/api";
+import { formatMoney, type Money } from "../../lib/money";
 
 interface MerchantApproval {
   key: string;
@@ -27,7 +28,7 @@ interface MerchantApprovalItem {
 interface MerchantTemplateItem {
   name: string;
   quantity: number;
-  price: number;
+  price: Money;
 }
 
 interface MerchantTemplateItemResp {
@@ -390,7 +391,7 @@ export default function OpsPage() {
                   <ul style={{ margin: 0, paddingLeft: 16 }}>
                     {record.items.map((item, index) => (
                       <li key={`${record.id}-${index}`}>
-                        {item.name} × {item.quantity}（¥ {item.price.toFixed(2)}）
+                        {item.name} × {item.quantity}（{formatMoney(item.price)}）
                       </li>
                     ))}
                   </ul>
@@ -4,16 +4,17 @@ import { useEffect, useMemo, useState } from "react";
 import { useNavigate, useParams } from "react-router-dom";
 import SectionHeader from "../../components/SectionHeader";
 import { apiGet } from "../../lib/api";
+import { formatMoney, type Money } from "../../lib/money";
 
 interface OrderItem {
   name: string;
-  price: number;
+  price: Money;
   quantity: number;
 }
 
 interface PaymentItem {
   id: number;
-  amount: number;
+  amount: Money;
   status: string;
   pay_channel: string;
   paid_at?: string | null;
@@ -22,7 +23,7 @@ interface PaymentItem {
 
 interface RefundItem {
   id: number;
-  amount: number;
+  amount: Money;
   status: string;
   reason?: string | null;
   proof_url?: string | null;
@@ -55,9 +56,9 @@ interface OrderDetailResp {
   id: number;
   status: string;
   pay_type: string;
-  total_amount: number;
-  deposit_amount: number;
-  service_fee: number;
+  total_amount: Money;
+  deposit_amount: Money;
+  service_fee: Money;
   schedule_start?: string | null;
   schedule_end?: string | null;
   created_at: string;
@@ -104,7 +105,7 @@ export default function OrderDetailPage() {
   const itemColumns: ColumnsType<OrderItem> = useMemo(
     () => [
       { title: "项目", dataIndex: "name" },
-      { title: "单价", dataIndex: "price", render: (v: number) => `¥ ${v.toFixed(2)}` },
+      { title: "单价", dataIndex: "price", render: (v: Money) => formatMoney(v) },
       { title: "数量", dataIndex: "quantity" }
     ],
     []
@@ -114,7 +115,7 @@ export default function OrderDetailPage() {
     () => [
       { title: "支付编号", dataIndex: "id" },
       { title: "渠道", dataIndex: "pay_channel" },
-      { title: "金额", dataIndex: "amount", render: (v: number) => `¥ ${v.toFixed(2)}` },
+      { title: "金额", dataIndex: "amount", render: (v: Money) => formatMoney(v) },
       { title: "状态", dataIndex: "status" },
       { title: "完成时间", dataIndex: "paid_at" },
       {
@@ -136,7 +137,7 @@ export default function OrderDetailPage() {
   const refundColumns: ColumnsType<RefundItem> = useMemo(
     () => [
       { title: "退款编号", dataIndex: "id" },
-      { title: "金额", dataIndex: "amount", render: (v: number) => `¥ ${v.toFixed(2)}` },
+      { title: "金额", dataIndex: "amount", render: (v: Money) => formatMoney(v) },
       { title: "状态", dataIndex: "status" },
       { title: "原因", dataIndex: "reason" },
       {
@@ -195,13 +196,13 @@ export default function OrderDetailPage() {
             {detail?.photographer_phone ?? detail?.photographer_id ?? "-"}
           </Descriptions.Item>
           <Descriptions.Item label="总金额">
-            {detail ? `¥ ${detail.total_amount.toFixed(2)}` : "-"}
+            {detail ? formatMoney(detail.total_amount) : "-"}
           </Descriptions.Item>
           <Descriptions.Item label="定金">
-            {detail ? `¥ ${detail.deposit_amount.toFixed(2)}` : "-"}
+            {detail ? formatMoney(detail.deposit_amount) : "-"}
           </Descriptions.Item>
           <Descriptions.Item label="平台服务费">
-            {detail ? `¥ ${detail.service_fee.toFixed(2)}` : "-"}
+            {detail ? formatMoney(detail.service_fee) : "-"}
           </Descriptions.Item>
           <Descriptions.Item label="服务时间">
             {detail?.schedule_start ?? "-"} ~ {detail?.schedule_end ?? "-"}
@@ -5,6 +5,7 @@ import { useEffect, useMemo, useState } from "react";
 import { Link } from "react-router-dom";
 import SectionHeader from "../../components/SectionHeader";
 import { apiGet, apiPost } from "../../lib/api";
+import { formatMoney, type Money } from "../../lib/money";
 
 interface OrderRow {
   key: string;
@@ -26,7 +27,7 @@ interface AdminOrderListItem {
   photographer_phone?: string | null;
   status: string;
   pay_type: string;
-  total_amount: number;
+  total_amount: Money;
   created_at: string;
 }
 
@@ -99,7 +100,7 @@ export default function OrdersPage() {
               orderNo: `OP-${item.id}`,
               user: item.user_phone ?? String(item.user_id),
               photographer: item.photographer_phone ?? (item.photographer_id ? String(item.photographer_id) : "-"),
-              amount: `¥ ${item.total_amount.toFixed(2)}`,
+              amount: formatMoney(item.total_amount),
               payType: item.pay_type,
               status: item.status,
               createdAt: item.created_at
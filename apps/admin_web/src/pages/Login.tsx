@@ -21,6 +21,7 @@ interface LoginReq {
 
 interface LoginResp {
   token: string;
+  refresh_token: string;
   user: { id: number; phone: string; status: string };
   roles: string[];
 }
@@ -52,7 +53,12 @@ export default function LoginPage() {
     setLoginLoading(true);
     try {
       const data = await apiPost<LoginReq, LoginResp>("/auth/login", values);
-      setSession({ token: data.token, user: data.user, roles: data.roles });
+      setSession({
+        token: data.token,
+        refreshToken: data.refresh_token,
+        user: data.user,
+        roles: data.roles
+      });
       message.success("登录成功");
       const redirect = (location.state as { from?: { pathname?: string } } | null)?.from?.pathname ?? "/";
       navigate(redirect, { replace: true });
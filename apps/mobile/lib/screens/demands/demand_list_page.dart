@@ -436,6 +436,7 @@ class _DemandListTabState extends State<DemandListTab> {
                           DropdownMenuItem(value: "all", child: Text("全部")),
                           DropdownMenuItem(value: "draft", child: Text("草稿")),
                           DropdownMenuItem(value: "open", child: Text("开放")),
+                          DropdownMenuItem(value: "matched", child: Text("已成交")),
                           DropdownMenuItem(value: "closed", child: Text("已关闭")),
                         ],
                         onChanged: (value) => setState(() => _status = value ?? "all"),
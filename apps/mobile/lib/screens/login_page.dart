@@ -55,7 +55,7 @@ class _LoginPageState extends State<LoginPage> {
       if (token == null || token.isEmpty) {
         throw ApiException("token_missing");
       }
-      await SessionStore.setToken(token);
+      await SessionStore.setToken(token, refresh: data["refresh_token"] as String?);
     } catch (error) {
       _showMessage("登录失败：$error");
     } finally {
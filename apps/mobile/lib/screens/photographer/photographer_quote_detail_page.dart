@@ -71,7 +71,7 @@ class _PhotographerQuoteDetailPageState extends State<PhotographerQuoteDetailPag
   Future<void> _editQuote() async {
     final detail = _detail ?? {};
     final items = detail["items"] is List ? detail["items"] as List : [];
-    final total = (detail["total_price"] as num?)?.toDouble() ?? 0;
+    final total = double.tryParse(detail["total_price"]?.toString() ?? "") ?? 0;
     final note = detail["note"]?.toString();
     final updated = await Navigator.of(context).push<bool>(
       MaterialPageRoute(
@@ -106,6 +106,10 @@ class _PhotographerQuoteDetailPageState extends State<PhotographerQuoteDetailPag
         return "待确认";
       case "accepted":
         return "已接受";
+      case "stale":
+        return "需求已变更，待重新确认";
+      case "rejected":
+        return "未被选中";
       case "expired":
         return "已过期/撤回";
       default:
@@ -128,6 +128,10 @@ class _PhotographerQuoteListPageState extends State<PhotographerQuoteListPage> {
         return "待客户确认";
       case "accepted":
         return "已转订单";
+      case "stale":
+        return "需求已变更，待重新报价";
+      case "rejected":
+        return "客户已选定其他报价";
       case "expired":
         return "已过期/撤回";
       default:
@@ -177,6 +181,8 @@ class _PhotographerQuoteListPageState extends State<PhotographerQuoteListPage> {
                 DropdownMenuItem(value: "all", child: Text("全部")),
                 DropdownMenuItem(value: "pending", child: Text("待处理")),
                 DropdownMenuItem(value: "accepted", child: Text("已接受")),
+                DropdownMenuItem(value: "stale", child: Text("需重新确认")),
+                DropdownMenuItem(value: "rejected", child: Text("未被选中")),
                 DropdownMenuItem(value: "expired", child: Text("已过期")),
               ],
               onChanged: (value) {
@@ -43,7 +43,12 @@ class ApiClient {
     return _request("DELETE", path);
   }
 
-  static Future<dynamic> _request(String method, String path, {Map<String, dynamic>? body}) async {
+  static Future<dynamic> _request(
+    String method,
+    String path, {
+    Map<String, dynamic>? body,
+    bool retried = false,
+  }) async {
     final uri = Uri.parse("$baseUrl$path");
     final request = await _client.openUrl(method, uri);
     request.headers.contentType = ContentType.json;
@@ -56,6 +61,19 @@ class ApiClient {
     }
 
     final response = await request.close();
+    if (response.statusCode == HttpStatus.unauthorized &&
+        !retried &&
+        token != null &&
+        token.isNotEmpty) {
+      await response.drain<void>();
+      // Another request may already have refreshed while this one was in flight.
+      final refreshed = SessionStore.token != token || await _refreshOnce();
+      if (refreshed) {
+        return _request(method, path, body: body, retried: true);
+      }
+      await SessionStore.clear();
+      throw ApiException("unauthorized");
+    }
     if (response.statusCode == HttpStatus.unauthorized ||
         response.statusCode == HttpStatus.forbidden) {
       await SessionStore.clear();
@@ -72,4 +90,39 @@ class ApiClient {
     }
     return decoded["data"];
   }
+
+  static Future<bool>? _refreshing;
+
+  // Refresh tokens rotate and reusing an old one revokes the session, so
+  // concurrent 401s must share a single refresh call.
+  static Future<bool> _refreshOnce() {
+    return _refreshing ??= _refreshTokens().whenComplete(() => _refreshing = null);
+  }
+
+  static Future<bool> _refreshTokens() async {
+    final refreshToken = SessionStore.refreshToken;
+    if (refreshToken == null || refreshToken.isEmpty) {
+      return false;
+    }
+    try {
+      final request = await _client.postUrl(Uri.parse("$baseUrl/auth/refresh"));
+      request.headers.contentType = ContentType.json;
+      request.write(jsonEncode({"refresh_token": refreshToken}));
+      final response = await request.close();
+      final payload = await response.transform(utf8.decoder).join();
+      if (response.statusCode != HttpStatus.ok || payload.isEmpty) {
+        return false;
+      }
+      final decoded = jsonDecode(payload) as Map<String, dynamic>;
+      final data = decoded["data"] as Map<String, dynamic>?;
+      final token = data?["token"] as String?;
+      if ((decoded["code"] as int? ?? 0) != 0 || token == null || token.isEmpty) {
+        return false;
+      }
+      await SessionStore.setToken(token, refresh: data?["refresh_token"] as String?);
+      return true;
+    } catch (_) {
+      return false;
+    }
+  }
 }
@@ -4,16 +4,19 @@ import "package:shared_preferences/shared_preferences.dart";
 class SessionStore {
   static final ValueNotifier<bool> authed = ValueNotifier(false);
   static String? token;
+  static String? refreshToken;
   static SharedPreferences? _prefs;
 
   static Future<void> init() async {
     _prefs = await SharedPreferences.getInstance();
     token = _prefs?.getString("token");
+    refreshToken = _prefs?.getString("refresh_token");
     authed.value = token != null && token!.isNotEmpty;
   }
 
-  static Future<void> setToken(String? value) async {
+  static Future<void> setToken(String? value, {String? refresh}) async {
     token = value;
+    refreshToken = refresh;
     authed.value = value != null && value.isNotEmpty;
     final prefs = _prefs ?? await SharedPreferences.getInstance();
     _prefs = prefs;
@@ -22,6 +25,11 @@ class SessionStore {
     } else {
       await prefs.remove("token");
     }
+    if (authed.value && refresh != null && refresh.isNotEmpty) {
+      await prefs.setString("refresh_token", refresh);
+    } else {
+      await prefs.remove("refresh_token");
+    }
   }
 
   static Future<void> clear() async {
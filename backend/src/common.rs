@@ -32,3 +32,26 @@ impl<T> ApiResponse<T> {
         Self { code, message: message.to_string(), data: None }
     }
 }
+
+/// 渲染 CSV 文本：首行为表头，每行按 RFC 4180 规则转义。
+pub fn render_csv<I>(header: &[&str], rows: I) -> String
+where
+    I: IntoIterator<Item = Vec<String>>,
+{
+    let mut out = header.join(",");
+    out.push('\n');
+    for row in rows {
+        out.push_str(&row.iter().map(|v| escape_csv(v)).collect::<Vec<_>>().join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn escape_csv(input: &str) -> String {
+    if input.contains(',') || input.contains('"') || input.contains('\n') {
+        let escaped = input.replace('"', "\"\"");
+        format!("\"{}\"", escaped)
+    } else {
+        input.to_string()
+    }
+}
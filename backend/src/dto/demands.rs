@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
 #[derive(Deserialize)]
 pub struct CreateDemandReq {
     pub r#type: String,
@@ -7,8 +9,8 @@ pub struct CreateDemandReq {
     pub location: Option<String>,
     pub schedule_start: String,
     pub schedule_end: String,
-    pub budget_min: Option<f64>,
-    pub budget_max: Option<f64>,
+    pub budget_min: Option<Money>,
+    pub budget_max: Option<Money>,
     pub people_count: Option<i32>,
     pub style_tags: Option<Vec<String>>,
     pub attachments: Option<Vec<AttachmentReq>>,
@@ -16,6 +18,19 @@ pub struct CreateDemandReq {
     pub merchant_id: Option<i64>,
 }
 
+/// 仅 open 需求可修改；未传字段保持不变，传 `attachments` 时整体替换附件。
+#[derive(Deserialize)]
+pub struct UpdateDemandReq {
+    pub location: Option<String>,
+    pub schedule_start: Option<String>,
+    pub schedule_end: Option<String>,
+    pub budget_min: Option<Money>,
+    pub budget_max: Option<Money>,
+    pub people_count: Option<i32>,
+    pub style_tags: Option<Vec<String>>,
+    pub attachments: Option<Vec<AttachmentReq>>,
+}
+
 #[derive(Deserialize)]
 pub struct AttachmentReq {
     pub file_url: String,
@@ -37,8 +52,8 @@ pub struct DemandListQuery {
     pub status: Option<String>,
     pub schedule_start: Option<String>,
     pub schedule_end: Option<String>,
-    pub min_budget: Option<f64>,
-    pub max_budget: Option<f64>,
+    pub min_budget: Option<Money>,
+    pub max_budget: Option<Money>,
     pub style_tag: Option<String>,
     pub is_merchant: Option<bool>,
     pub sort: Option<String>,
@@ -66,8 +81,8 @@ pub struct DemandDetail {
     pub location: Option<String>,
     pub schedule_start: Option<String>,
     pub schedule_end: Option<String>,
-    pub budget_min: Option<f64>,
-    pub budget_max: Option<f64>,
+    pub budget_min: Option<Money>,
+    pub budget_max: Option<Money>,
     pub people_count: Option<i32>,
     pub style_tags: Option<Vec<String>>,
     pub is_merchant: bool,
@@ -82,6 +97,18 @@ pub struct AttachmentResp {
     pub file_type: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct DemandRevisionItem {
+    pub id: i64,
+    pub revision: i32,
+    /// 变更字段：`{ "<field>": { "from": .., "to": .. } }`。
+    pub changes: serde_json::Value,
+    /// 修改后的需求快照。
+    pub snapshot: serde_json::Value,
+    pub created_by: i64,
+    pub created_at: String,
+}
+
 #[derive(Deserialize)]
 pub struct DemandMerchantAssetQuery {
     pub asset_type: Option<String>,
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dto::pagination::Paged;
+use crate::money::Money;
+
+#[derive(Deserialize)]
+pub struct LedgerAccountQuery {
+    pub owner_type: Option<String>,
+    pub owner_id: Option<i64>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct LedgerAccountResp {
+    pub id: i64,
+    pub owner_type: String,
+    pub owner_id: i64,
+    pub balance: Money,
+    pub created_at: String,
+}
+
+#[derive(Deserialize)]
+pub struct LedgerStatementQuery {
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct LedgerStatementItem {
+    pub line_id: i64,
+    pub entry_id: i64,
+    pub entry_type: String,
+    pub order_id: Option<i64>,
+    pub reference_type: String,
+    pub reference_id: i64,
+    pub memo: Option<String>,
+    pub amount: Money,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct LedgerStatementResp {
+    pub account: LedgerAccountResp,
+    pub opening_balance: Money,
+    pub closing_balance: Money,
+    pub lines: Paged<LedgerStatementItem>,
+}
+
+#[derive(Deserialize)]
+pub struct LedgerReconciliationQuery {
+    pub start_date: String,
+    pub end_date: String,
+}
+
+#[derive(Serialize)]
+pub struct LedgerReconciliationItem {
+    pub pay_channel: String,
+    pub payment_count: u64,
+    pub payment_total: Money,
+    pub ledger_total: Money,
+    pub missing_payment_ids: Vec<i64>,
+}
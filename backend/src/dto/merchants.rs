@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
 #[derive(Deserialize)]
 pub struct CreateMerchantReq {
     pub name: String,
@@ -65,7 +67,7 @@ pub struct MerchantMemberResp {
 pub struct TemplateItemReq {
     pub name: String,
     pub quantity: i32,
-    pub price: f64,
+    pub price: Money,
 }
 
 #[derive(Deserialize)]
@@ -88,7 +90,7 @@ pub struct TemplateResp {
 pub struct TemplateItemResp {
     pub name: String,
     pub quantity: i32,
-    pub price: f64,
+    pub price: Money,
 }
 
 #[derive(Serialize)]
@@ -178,7 +180,7 @@ pub struct CreateInvoiceReq {
     pub order_id: Option<i64>,
     pub title: String,
     pub tax_no: Option<String>,
-    pub amount: f64,
+    pub amount: Money,
 }
 
 #[derive(Serialize)]
@@ -195,7 +197,7 @@ pub struct InvoiceListItem {
     pub order_id: Option<i64>,
     pub title: String,
     pub tax_no: Option<String>,
-    pub amount: f64,
+    pub amount: Money,
     pub status: String,
     pub created_at: String,
 }
@@ -212,7 +214,7 @@ pub struct InvoiceListQuery {
 pub struct MerchantOrderListItem {
     pub id: i64,
     pub status: String,
-    pub total_amount: f64,
+    pub total_amount: Money,
     pub demand_id: Option<i64>,
     pub created_at: String,
 }
@@ -232,9 +234,9 @@ pub struct MerchantOrderReportItem {
     pub id: i64,
     pub demand_id: Option<i64>,
     pub status: String,
-    pub total_amount: f64,
-    pub paid_amount: f64,
-    pub refund_amount: f64,
+    pub total_amount: Money,
+    pub paid_amount: Money,
+    pub refund_amount: Money,
     pub created_at: String,
 }
 
@@ -250,7 +252,7 @@ pub struct MerchantOrderReportResp {
 #[derive(Serialize)]
 pub struct MerchantOrderItem {
     pub name: String,
-    pub price: f64,
+    pub price: Money,
     pub quantity: i32,
 }
 
@@ -259,8 +261,8 @@ pub struct MerchantOrderDetail {
     pub id: i64,
     pub status: String,
     pub pay_type: String,
-    pub total_amount: f64,
-    pub service_fee: f64,
+    pub total_amount: Money,
+    pub service_fee: Money,
     pub schedule_start: Option<String>,
     pub schedule_end: Option<String>,
     pub user_id: i64,
@@ -2,6 +2,7 @@ pub mod conversations;
 pub mod configs;
 pub mod merchants;
 pub mod demands;
+pub mod ledger;
 pub mod messages;
 pub mod notifications;
 pub mod orders;
@@ -10,4 +11,7 @@ pub mod payments;
 pub mod quotes;
 pub mod teams;
 pub mod refunds;
+pub mod roles;
+pub mod sessions;
+pub mod users;
 pub mod photographers;
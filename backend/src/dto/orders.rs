@@ -1,5 +1,8 @@
+use sea_orm::prelude::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
 #[derive(Deserialize)]
 pub struct OrderListQuery {
     pub status: Option<String>,
@@ -7,8 +10,8 @@ pub struct OrderListQuery {
     pub page_size: Option<u64>,
     pub keyword: Option<String>,
     pub sort: Option<String>,
-    pub min_amount: Option<f64>,
-    pub max_amount: Option<f64>,
+    pub min_amount: Option<Money>,
+    pub max_amount: Option<Money>,
     pub start_time: Option<String>,
     pub end_time: Option<String>,
 }
@@ -16,7 +19,7 @@ pub struct OrderListQuery {
 #[derive(Serialize)]
 pub struct OrderItemResp {
     pub name: String,
-    pub price: f64,
+    pub price: Money,
     pub quantity: i32,
 }
 
@@ -26,19 +29,45 @@ pub struct OrderResp {
     pub user_id: i64,
     pub status: String,
     pub pay_type: String,
-    pub total_amount: f64,
-    pub service_fee: f64,
+    pub total_amount: Money,
+    pub service_fee: Money,
+    pub quote_id: Option<i64>,
+    pub quote_version: Option<i32>,
+    pub schedule_start: Option<String>,
+    pub schedule_end: Option<String>,
+    pub location: Option<String>,
     pub items: Vec<OrderItemResp>,
 }
 
+#[derive(Serialize)]
+pub struct OrderContractResp {
+    pub id: i64,
+    pub order_id: i64,
+    pub version: i32,
+    pub terms: serde_json::Value,
+    pub created_at: String,
+}
+
 #[derive(Serialize)]
 pub struct RefundPreviewResp {
     pub order_id: i64,
-    pub paid_amount: f64,
-    pub refund_ratio: f64,
-    pub refund_amount: f64,
+    pub paid_amount: Money,
+    pub refund_ratio: Decimal,
+    pub refund_amount: Money,
     pub responsible_party: String,
     pub rule: String,
+    pub policy_name: String,
+    pub policy_source: String,
+    pub days_before_start: i64,
+    pub applied_tier: Option<RefundPolicyTierResp>,
+}
+
+#[derive(Serialize)]
+pub struct RefundPolicyTierResp {
+    pub index: usize,
+    pub pay_type: Option<String>,
+    pub min_days_before: Option<i64>,
+    pub ratio: Decimal,
 }
 
 #[derive(Deserialize)]
@@ -51,12 +80,43 @@ pub struct CancelOrderResp {
     pub order_id: i64,
     pub status: String,
     pub refund_id: Option<i64>,
-    pub refund_amount: f64,
+    pub refund_amount: Money,
 }
 
 #[derive(Serialize)]
 pub struct OrderListItem {
     pub id: i64,
     pub status: String,
-    pub total_amount: f64,
+    pub total_amount: Money,
+}
+
+#[derive(Serialize)]
+pub struct OrderTimelineItem {
+    pub id: i64,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: Option<i64>,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct PaymentPlanStageItem {
+    pub stage: String,
+    pub amount: Money,
+    pub due_at: Option<String>,
+    pub paid_amount: Money,
+    pub pending_amount: Money,
+    pub outstanding_amount: Money,
+    pub status: String,
+}
+
+#[derive(Serialize)]
+pub struct PaymentPlanResp {
+    pub order_id: i64,
+    pub pay_type: String,
+    pub total_amount: Money,
+    pub paid_amount: Money,
+    pub outstanding_amount: Money,
+    pub stages: Vec<PaymentPlanStageItem>,
 }
@@ -1,19 +1,35 @@
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
 #[derive(Deserialize)]
 pub struct CreatePaymentReq {
     pub order_id: i64,
-    pub amount: f64,
+    pub amount: Money,
     pub pay_channel: String,
-    pub proof_url: String,
+    pub proof_url: Option<String>,
     pub stage: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct ConfirmPaymentReq {
+    pub received: bool,
+}
+
 #[derive(Serialize)]
 pub struct PaymentResp {
     pub id: i64,
     pub order_id: i64,
-    pub amount: f64,
+    pub amount: Money,
     pub status: String,
     pub stage: Option<String>,
+    pub pay_channel: String,
+    pub provider_ref: Option<String>,
+    pub pay_params: Option<serde_json::Value>,
+}
+
+#[derive(Serialize)]
+pub struct PaymentCallbackResp {
+    pub payment_id: i64,
+    pub status: String,
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
 #[derive(Deserialize)]
 pub struct PhotographerListQuery {
     pub keyword: Option<String>,
@@ -23,3 +25,27 @@ pub struct PhotographerListItem {
     pub rating_avg: f64,
     pub completed_orders: i32,
 }
+
+#[derive(Deserialize)]
+pub struct PhotographerEarningsQuery {
+    pub month: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PhotographerEarningsItem {
+    pub kind: String,
+    pub order_id: Option<i64>,
+    pub reference_id: i64,
+    pub amount: Money,
+    pub occurred_at: String,
+}
+
+#[derive(Serialize)]
+pub struct PhotographerEarningsResp {
+    pub month: String,
+    pub received_amount: Money,
+    pub refunded_amount: Money,
+    pub service_fee_amount: Money,
+    pub net_amount: Money,
+    pub items: Vec<PhotographerEarningsItem>,
+}
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
 #[derive(Deserialize, Clone)]
 pub struct QuoteItemReq {
     pub name: String,
-    pub price: f64,
+    pub price: Money,
     pub quantity: i32,
 }
 
@@ -12,7 +14,7 @@ pub struct CreateQuoteReq {
     pub demand_id: i64,
     pub photographer_id: Option<i64>,
     pub team_id: Option<i64>,
-    pub total_price: f64,
+    pub total_price: Money,
     pub items: Vec<QuoteItemReq>,
     pub note: Option<String>,
 }
@@ -39,7 +41,7 @@ pub struct QuoteListQuery {
 #[derive(Serialize)]
 pub struct QuoteItemResp {
     pub name: String,
-    pub price: f64,
+    pub price: Money,
     pub quantity: i32,
 }
 
@@ -48,7 +50,7 @@ pub struct QuoteListItem {
     pub id: i64,
     pub demand_id: i64,
     pub status: String,
-    pub total_price: f64,
+    pub total_price: Money,
     pub photographer_id: Option<i64>,
     pub team_id: Option<i64>,
     pub version: i32,
@@ -68,8 +70,9 @@ pub struct MyQuoteListQuery {
 pub struct MyQuoteListItem {
     pub id: i64,
     pub demand_id: i64,
+    pub team_id: Option<i64>,
     pub status: String,
-    pub total_price: f64,
+    pub total_price: Money,
     pub created_at: String,
     pub version: i32,
     pub expires_at: Option<String>,
@@ -83,7 +86,7 @@ pub struct QuoteDetailResp {
     pub id: i64,
     pub demand_id: i64,
     pub status: String,
-    pub total_price: f64,
+    pub total_price: Money,
     pub photographer_id: Option<i64>,
     pub team_id: Option<i64>,
     pub version: i32,
@@ -95,7 +98,7 @@ pub struct QuoteDetailResp {
 
 #[derive(Deserialize)]
 pub struct UpdateQuoteReq {
-    pub total_price: f64,
+    pub total_price: Money,
     pub items: Vec<QuoteItemReq>,
     pub note: Option<String>,
 }
@@ -104,9 +107,46 @@ pub struct UpdateQuoteReq {
 pub struct QuoteVersionItem {
     pub id: i64,
     pub version: i32,
-    pub total_price: f64,
+    pub total_price: Money,
     pub items: serde_json::Value,
     pub note: Option<String>,
     pub created_by: i64,
+    /// 提出方：photographer / customer。
+    pub proposer_role: String,
+    /// proposed（待摄影师回应的还价）/ adopted / declined / superseded。
+    pub status: String,
+    /// 是否为当前生效版本（与报价 version 一致）。
+    pub is_binding: bool,
     pub created_at: String,
 }
+
+#[derive(Deserialize)]
+pub struct QuoteVersionDiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+/// 两个版本中同名条目的变化，差额均为 `to - from`。
+#[derive(Serialize)]
+pub struct QuoteItemChange {
+    pub name: String,
+    pub from_price: Money,
+    pub to_price: Money,
+    pub price_delta: Money,
+    pub from_quantity: i32,
+    pub to_quantity: i32,
+    pub subtotal_delta: Money,
+}
+
+#[derive(Serialize)]
+pub struct QuoteVersionDiffResp {
+    pub quote_id: i64,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub from_total: Money,
+    pub to_total: Money,
+    pub total_change: Money,
+    pub added: Vec<QuoteItemResp>,
+    pub removed: Vec<QuoteItemResp>,
+    pub modified: Vec<QuoteItemChange>,
+}
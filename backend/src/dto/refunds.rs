@@ -1,19 +1,59 @@
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
 #[derive(Deserialize)]
 pub struct CreateRefundReq {
     pub order_id: i64,
-    pub amount: f64,
+    pub amount: Money,
     pub reason: Option<String>,
     pub proof_url: Option<String>,
     pub responsible_party: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct RefundListQuery {
+    pub status: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct ApproveRefundReq {
+    pub amount: Option<Money>,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RejectRefundReq {
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ReviewRefundReq {
+    pub status: String,
+    pub amount: Option<Money>,
+    pub note: String,
+}
+
+#[derive(Deserialize)]
+pub struct CompleteRefundReq {
+    pub proof_url: String,
+}
+
 #[derive(Serialize)]
 pub struct RefundResp {
     pub id: i64,
     pub order_id: i64,
-    pub amount: f64,
+    pub applicant_id: i64,
+    pub amount: Money,
+    pub approved_amount: Option<Money>,
     pub status: String,
     pub responsible_party: Option<String>,
+    pub reason: Option<String>,
+    pub proof_url: Option<String>,
+    pub review_note: Option<String>,
+    pub completion_proof_url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
 }
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct RoleResp {
+    pub id: i64,
+    pub name: String,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateRolePermissionsReq {
+    pub permissions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GrantRoleReq {
+    pub role: String,
+    pub scope: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct UserRoleItem {
+    pub role: String,
+    pub scope: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct UserRolesResp {
+    pub user_id: i64,
+    pub roles: Vec<UserRoleItem>,
+    pub permissions: Vec<String>,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct TokenResp {
+    pub token: String,
+    pub refresh_token: String,
+    pub expired_at: i64,
+    pub refresh_expired_at: i64,
+}
+
+#[derive(Deserialize)]
+pub struct RefreshTokenReq {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize)]
+pub struct SessionItem {
+    pub id: i64,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: String,
+    pub last_seen_at: Option<String>,
+    pub expired_at: String,
+    pub current: bool,
+}
+
+#[derive(Serialize)]
+pub struct RevokeSessionResp {
+    pub id: i64,
+    pub revoked: bool,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct BanUserReq {
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct UserStatusResp {
+    pub id: i64,
+    pub status: String,
+    pub revoked_sessions: u64,
+}
+
+#[derive(Deserialize)]
+pub struct ChangePasswordReq {
+    pub old_password: Option<String>,
+    pub new_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct ForcePasswordResetReq {
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PasswordResp {
+    pub user_id: i64,
+    pub revoked_sessions: u64,
+}
+
+#[derive(Serialize)]
+pub struct DeleteAccountResp {
+    pub user_id: i64,
+    pub status: String,
+    pub deleted_at: String,
+}
+
+#[derive(Serialize)]
+pub struct UserExport {
+    pub exported_at: String,
+    pub user: serde_json::Value,
+    pub profile: Option<serde_json::Value>,
+    pub demands: Vec<serde_json::Value>,
+    pub quotes: Vec<serde_json::Value>,
+    pub orders: Vec<serde_json::Value>,
+    pub payments: Vec<serde_json::Value>,
+    pub reviews: Vec<serde_json::Value>,
+    pub messages: Vec<serde_json::Value>,
+    pub notifications: Vec<serde_json::Value>,
+}
@@ -1,4 +1,5 @@
 pub mod demand_attachments;
+pub mod demand_revisions;
 pub mod demands;
 pub mod deliveries;
 pub mod delivery_items;
@@ -7,6 +8,10 @@ pub mod disputes;
 pub mod audit_logs;
 pub mod configs;
 pub mod conversations;
+pub mod idempotency_keys;
+pub mod ledger_accounts;
+pub mod ledger_entries;
+pub mod ledger_lines;
 pub mod messages;
 pub mod merchant_approvals;
 pub mod merchant_assets;
@@ -19,7 +24,10 @@ pub mod merchant_templates;
 pub mod merchant_users;
 pub mod merchants;
 pub mod notifications;
+pub mod order_contracts;
 pub mod order_items;
+pub mod order_payment_plans;
+pub mod order_status_history;
 pub mod orders;
 pub mod payments;
 pub mod photographers;
@@ -30,10 +38,14 @@ pub mod quotes;
 pub mod quote_versions;
 pub mod reviews;
 pub mod refunds;
+pub mod role_permissions;
+pub mod roles;
+pub mod session_rotated_tokens;
 pub mod sessions;
 pub mod team_members;
 pub mod teams;
 pub mod user_profiles;
+pub mod user_roles;
 pub mod users;
 pub mod verification_codes;
 
@@ -46,6 +58,10 @@ pub use disputes::Entity as Disputes;
 pub use audit_logs::Entity as AuditLogs;
 pub use configs::Entity as Configs;
 pub use conversations::Entity as Conversations;
+pub use idempotency_keys::Entity as IdempotencyKeys;
+pub use ledger_accounts::Entity as LedgerAccounts;
+pub use ledger_entries::Entity as LedgerEntries;
+pub use ledger_lines::Entity as LedgerLines;
 pub use messages::Entity as Messages;
 pub use merchant_approvals::Entity as MerchantApprovals;
 pub use merchant_assets::Entity as MerchantAssets;
@@ -58,7 +74,10 @@ pub use merchant_templates::Entity as MerchantTemplates;
 pub use merchant_users::Entity as MerchantUsers;
 pub use merchants::Entity as Merchants;
 pub use notifications::Entity as Notifications;
+pub use order_contracts::Entity as OrderContracts;
 pub use order_items::Entity as OrderItems;
+pub use order_payment_plans::Entity as OrderPaymentPlans;
+pub use order_status_history::Entity as OrderStatusHistory;
 pub use orders::Entity as Orders;
 pub use payments::Entity as Payments;
 pub use photographers::Entity as Photographers;
@@ -69,9 +88,13 @@ pub use quotes::Entity as Quotes;
 pub use quote_versions::Entity as QuoteVersions;
 pub use reviews::Entity as Reviews;
 pub use refunds::Entity as Refunds;
+pub use role_permissions::Entity as RolePermissions;
+pub use roles::Entity as Roles;
+pub use session_rotated_tokens::Entity as SessionRotatedTokens;
 pub use sessions::Entity as Sessions;
 pub use team_members::Entity as TeamMembers;
 pub use teams::Entity as Teams;
 pub use user_profiles::Entity as UserProfiles;
+pub use user_roles::Entity as UserRoles;
 pub use users::Entity as Users;
 pub use verification_codes::Entity as VerificationCodes;
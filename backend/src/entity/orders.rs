@@ -20,6 +20,9 @@ pub struct Model {
     pub cancelled_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub refund_policy: Option<Json>,
+    pub quote_version: Option<i32>,
+    pub location: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
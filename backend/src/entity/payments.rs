@@ -15,6 +15,9 @@ pub struct Model {
     pub paid_at: Option<DateTimeWithTimeZone>,
     pub proof_url: Option<String>,
     pub created_at: DateTimeWithTimeZone,
+    pub provider_ref: Option<String>,
+    pub pay_params: Option<Json>,
+    pub callback_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -11,6 +11,8 @@ pub struct Model {
     pub items: Json,
     pub note: Option<String>,
     pub created_by: i64,
+    pub proposer_role: String,
+    pub status: String,
     pub created_at: DateTimeWithTimeZone,
 }
 
@@ -14,6 +14,12 @@ pub struct Model {
     pub proof_url: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub approved_amount: Option<Decimal>,
+    pub reviewer_id: Option<i64>,
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<DateTimeWithTimeZone>,
+    pub completion_proof_url: Option<String>,
+    pub completed_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -9,6 +9,13 @@ pub struct Model {
     pub token: String,
     pub expired_at: DateTimeWithTimeZone,
     pub created_at: DateTimeWithTimeZone,
+    pub refresh_token: Option<String>,
+    pub refresh_expired_at: Option<DateTimeWithTimeZone>,
+    pub user_agent: Option<String>,
+    pub device_name: Option<String>,
+    pub ip: Option<String>,
+    pub last_seen_at: Option<DateTimeWithTimeZone>,
+    pub revoked_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -8,6 +8,8 @@ pub struct Model {
     pub phone: String,
     pub code: String,
     pub expired_at: DateTimeWithTimeZone,
+    pub attempts: i32,
+    pub consumed_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
 }
 
@@ -25,6 +25,10 @@ impl ApiError {
         Self { code: 1003, message: "forbidden".to_string(), status: StatusCode::FORBIDDEN }
     }
 
+    pub fn forbidden_with(msg: &str) -> Self {
+        Self { code: 1003, message: msg.to_string(), status: StatusCode::FORBIDDEN }
+    }
+
     pub fn not_found() -> Self {
         Self { code: 1004, message: "not_found".to_string(), status: StatusCode::NOT_FOUND }
     }
@@ -33,6 +37,10 @@ impl ApiError {
         Self { code: 1005, message: msg.to_string(), status: StatusCode::CONFLICT }
     }
 
+    pub fn too_many_requests(msg: &str) -> Self {
+        Self { code: 1006, message: msg.to_string(), status: StatusCode::TOO_MANY_REQUESTS }
+    }
+
     pub fn internal() -> Self {
         Self { code: 1500, message: "internal".to_string(), status: StatusCode::INTERNAL_SERVER_ERROR }
     }
@@ -63,10 +71,13 @@ impl From<DomainError> for ApiError {
             DomainError::InvalidAddress => ApiError::bad_request("invalid_address"),
             DomainError::InvalidAmount => ApiError::bad_request("invalid_amount"),
             DomainError::ItemsRequired => ApiError::bad_request("items_required"),
+            DomainError::Unauthorized => ApiError::unauthorized(),
             DomainError::Forbidden => ApiError::forbidden(),
             DomainError::NotFound => ApiError::not_found(),
             DomainError::MemberExists => ApiError::conflict("member_exists"),
             DomainError::ContactUser => ApiError::conflict("contact_user"),
+            DomainError::OrderFrozen => ApiError::conflict("order_frozen"),
+            DomainError::AccountDisabled => ApiError::forbidden_with("account_disabled"),
         }
     }
 }
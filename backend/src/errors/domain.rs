@@ -18,6 +18,8 @@ pub enum DomainError {
     InvalidAmount,
     #[error("items_required")]
     ItemsRequired,
+    #[error("unauthorized")]
+    Unauthorized,
     #[error("forbidden")]
     Forbidden,
     #[error("not_found")]
@@ -26,4 +28,8 @@ pub enum DomainError {
     MemberExists,
     #[error("contact_user")]
     ContactUser,
+    #[error("order_frozen")]
+    OrderFrozen,
+    #[error("account_disabled")]
+    AccountDisabled,
 }
@@ -2,5 +2,5 @@ pub use crate::services::admin_service::{
     create_audit, export_orders_report, freeze_order, get_admin_dispute_detail, get_admin_order_detail,
     get_metrics, get_metrics_trends, list_admin_disputes, list_admin_orders, list_admin_users,
     list_admin_portfolios, list_audits, list_merchant_approvals, list_merchant_templates,
-    resolve_dispute, review_merchant_approval, review_photographer, review_portfolio,
+    resolve_dispute, review_merchant_approval, review_photographer, review_portfolio, unfreeze_order,
 };
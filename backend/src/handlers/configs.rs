@@ -17,11 +17,11 @@ pub async fn get_config(
 }
 
 pub async fn upsert_config(
-    AuthUser { .. }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(key): Path<String>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<UpdateConfigReq>,
 ) -> ApiResult<ConfigResp> {
-    let data = configs_service::upsert_config(&state, key, req).await?;
+    let data = configs_service::upsert_config(&state, user_id, key, req).await?;
     Ok(Json(ApiResponse::ok(data)))
 }
@@ -9,7 +9,7 @@ use crate::services::conversations_service;
 use crate::state::AppState;
 
 pub async fn create_conversation(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateConversationReq>,
 ) -> ApiResult<ConversationResp> {
@@ -18,7 +18,7 @@ pub async fn create_conversation(
 }
 
 pub async fn list_conversations(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<ListConversationsQuery>,
 ) -> ApiResult<Paged<ConversationResp>> {
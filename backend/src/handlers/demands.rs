@@ -4,7 +4,7 @@ use crate::middleware::auth::AuthUser;
 use crate::common::ApiResponse;
 use crate::dto::demands::{
     CreateDemandReq, DemandDetail, DemandListItem, DemandListQuery, DemandMerchantAssetItem,
-    DemandMerchantAssetQuery, DemandResp,
+    DemandMerchantAssetQuery, DemandResp, DemandRevisionItem, UpdateDemandReq,
 };
 use crate::dto::pagination::Paged;
 use crate::error::ApiResult;
@@ -12,7 +12,7 @@ use crate::services::demands_service;
 use crate::state::AppState;
 
 pub async fn create_demand(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateDemandReq>,
 ) -> ApiResult<DemandResp> {
@@ -21,7 +21,7 @@ pub async fn create_demand(
 }
 
 pub async fn list_demands(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<DemandListQuery>,
 ) -> ApiResult<Paged<DemandListItem>> {
@@ -37,8 +37,26 @@ pub async fn get_demand(
     Ok(Json(ApiResponse::ok(data)))
 }
 
+pub async fn update_demand(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(demand_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<UpdateDemandReq>,
+) -> ApiResult<DemandDetail> {
+    let data = demands_service::update_demand(&state, user_id, demand_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn list_demand_revisions(
+    Path(demand_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<Vec<DemandRevisionItem>> {
+    let data = demands_service::list_demand_revisions(&state, demand_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
 pub async fn list_demand_merchant_assets(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(demand_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<DemandMerchantAssetQuery>,
@@ -49,7 +67,7 @@ pub async fn list_demand_merchant_assets(
 }
 
 pub async fn close_demand(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(demand_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<DemandResp> {
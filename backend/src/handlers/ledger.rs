@@ -0,0 +1,40 @@
+use axum::{extract::Path, Json};
+
+use crate::common::ApiResponse;
+use crate::dto::ledger::{
+    LedgerAccountQuery, LedgerAccountResp, LedgerReconciliationItem, LedgerReconciliationQuery,
+    LedgerStatementQuery, LedgerStatementResp,
+};
+use crate::dto::pagination::Paged;
+use crate::error::ApiResult;
+use crate::middleware::auth::AuthUser;
+use crate::services::ledger_service;
+use crate::state::AppState;
+
+pub async fn list_accounts(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<LedgerAccountQuery>,
+) -> ApiResult<Paged<LedgerAccountResp>> {
+    let data = ledger_service::list_accounts(&state, user_id, query).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn get_statement(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(account_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<LedgerStatementQuery>,
+) -> ApiResult<LedgerStatementResp> {
+    let data = ledger_service::get_statement(&state, user_id, account_id, query).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn reconcile_payments(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<LedgerReconciliationQuery>,
+) -> ApiResult<Vec<LedgerReconciliationItem>> {
+    let data = ledger_service::reconcile_payments(&state, user_id, query).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
@@ -27,7 +27,7 @@ pub async fn create_merchant(
 }
 
 pub async fn list_my_merchants(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<Vec<MerchantListItem>> {
     let data = merchants_service::list_my_merchants(&state, user_id).await?;
@@ -35,7 +35,7 @@ pub async fn list_my_merchants(
 }
 
 pub async fn create_location(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(merchant_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateLocationReq>,
@@ -45,7 +45,7 @@ pub async fn create_location(
 }
 
 pub async fn list_locations(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(merchant_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<Vec<MerchantLocationResp>> {
@@ -54,7 +54,7 @@ pub async fn list_locations(
 }
 
 pub async fn update_location(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path((merchant_id, location_id)): Path<(i64, i64)>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<UpdateLocationReq>,
@@ -65,7 +65,7 @@ pub async fn update_location(
 }
 
 pub async fn delete_location(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path((merchant_id, location_id)): Path<(i64, i64)>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<MerchantLocationResp> {
@@ -75,7 +75,7 @@ pub async fn delete_location(
 }
 
 pub async fn add_member(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(merchant_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<AddMerchantMemberReq>,
@@ -85,7 +85,7 @@ pub async fn add_member(
 }
 
 pub async fn list_members(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(merchant_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<Vec<MerchantMemberResp>> {
@@ -94,7 +94,7 @@ pub async fn list_members(
 }
 
 pub async fn remove_member(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path((merchant_id, user_id_param)): Path<(i64, i64)>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<MerchantMemberResp> {
@@ -112,7 +112,7 @@ pub async fn create_template(
 }
 
 pub async fn list_templates(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<TemplateListQuery>,
 ) -> ApiResult<Paged<TemplateDetailResp>> {
@@ -130,7 +130,7 @@ pub async fn create_approval(
 }
 
 pub async fn list_approvals(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<ApprovalListQuery>,
 ) -> ApiResult<Paged<ApprovalListItem>> {
@@ -148,7 +148,7 @@ pub async fn create_contract(
 }
 
 pub async fn list_contracts(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<ContractListQuery>,
 ) -> ApiResult<Paged<ContractListItem>> {
@@ -166,7 +166,7 @@ pub async fn create_invoice(
 }
 
 pub async fn list_invoices(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<InvoiceListQuery>,
 ) -> ApiResult<Paged<InvoiceListItem>> {
@@ -175,7 +175,7 @@ pub async fn list_invoices(
 }
 
 pub async fn create_merchant_asset(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(merchant_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateMerchantAssetReq>,
@@ -185,7 +185,7 @@ pub async fn create_merchant_asset(
 }
 
 pub async fn list_merchant_assets(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(merchant_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<MerchantAssetListQuery>,
@@ -195,7 +195,7 @@ pub async fn list_merchant_assets(
 }
 
 pub async fn list_merchant_asset_versions(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(asset_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<MerchantAssetVersionListQuery>,
@@ -206,7 +206,7 @@ pub async fn list_merchant_asset_versions(
 }
 
 pub async fn create_merchant_asset_version(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(asset_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateMerchantAssetVersionReq>,
@@ -217,7 +217,7 @@ pub async fn create_merchant_asset_version(
 }
 
 pub async fn list_merchant_orders(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<MerchantOrderQuery>,
 ) -> ApiResult<Paged<MerchantOrderListItem>> {
@@ -226,7 +226,7 @@ pub async fn list_merchant_orders(
 }
 
 pub async fn get_merchant_order(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(order_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<MerchantOrderDetail> {
@@ -235,7 +235,7 @@ pub async fn get_merchant_order(
 }
 
 pub async fn export_merchant_orders_report(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<MerchantOrderReportQuery>,
 ) -> ApiResult<MerchantOrderReportResp> {
@@ -9,7 +9,7 @@ use crate::services::messages_service;
 use crate::state::AppState;
 
 pub async fn send_message(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateMessageReq>,
 ) -> ApiResult<MessageResp> {
@@ -18,7 +18,7 @@ pub async fn send_message(
 }
 
 pub async fn list_messages(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<ListMessagesQuery>,
 ) -> ApiResult<Paged<MessageItem>> {
@@ -2,6 +2,7 @@ pub mod conversations;
 pub mod configs;
 pub mod admin;
 pub mod demands;
+pub mod ledger;
 pub mod photographers;
 pub mod merchants;
 pub mod messages;
@@ -10,4 +11,7 @@ pub mod orders;
 pub mod payments;
 pub mod quotes;
 pub mod refunds;
+pub mod roles;
+pub mod sessions;
 pub mod teams;
+pub mod users;
@@ -11,7 +11,7 @@ use crate::services::notifications_service;
 use crate::state::AppState;
 
 pub async fn list_notifications(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<ListNotificationsQuery>,
 ) -> ApiResult<Paged<NotificationItem>> {
@@ -20,7 +20,7 @@ pub async fn list_notifications(
 }
 
 pub async fn get_summary(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<NotificationSummary> {
     let data = notifications_service::get_summary(&state, user_id).await?;
@@ -28,7 +28,7 @@ pub async fn get_summary(
 }
 
 pub async fn mark_all_read(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<MarkAllReadResp> {
     let data = notifications_service::mark_all_read(&state, user_id).await?;
@@ -36,7 +36,7 @@ pub async fn mark_all_read(
 }
 
 pub async fn get_notification(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(notification_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<NotificationItem> {
@@ -45,7 +45,7 @@ pub async fn get_notification(
 }
 
 pub async fn mark_notification_read(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(notification_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<NotificationItem> {
@@ -3,7 +3,8 @@ use axum::{extract::Path, Json};
 use crate::middleware::auth::AuthUser;
 use crate::common::ApiResponse;
 use crate::dto::orders::{
-    CancelOrderReq, CancelOrderResp, OrderListItem, OrderListQuery, OrderResp, RefundPreviewResp,
+    CancelOrderReq, CancelOrderResp, OrderContractResp, OrderListItem, OrderListQuery, OrderResp,
+    OrderTimelineItem, PaymentPlanResp, RefundPreviewResp,
 };
 use crate::dto::pagination::Paged;
 use crate::error::ApiResult;
@@ -11,7 +12,7 @@ use crate::services::orders_service;
 use crate::state::AppState;
 
 pub async fn list_orders(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<OrderListQuery>,
 ) -> ApiResult<Paged<OrderListItem>> {
@@ -20,7 +21,7 @@ pub async fn list_orders(
 }
 
 pub async fn get_order(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(order_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<OrderResp> {
@@ -28,8 +29,17 @@ pub async fn get_order(
     Ok(Json(ApiResponse::ok(data)))
 }
 
+pub async fn get_order_contract(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(order_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<OrderContractResp> {
+    let data = orders_service::get_order_contract(&state, user_id, order_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
 pub async fn refund_preview(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(order_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<RefundPreviewResp> {
@@ -38,7 +48,7 @@ pub async fn refund_preview(
 }
 
 pub async fn cancel_order(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(order_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CancelOrderReq>,
@@ -46,3 +56,21 @@ pub async fn cancel_order(
     let data = orders_service::cancel_order(&state, user_id, order_id, req).await?;
     Ok(Json(ApiResponse::ok(data)))
 }
+
+pub async fn get_order_timeline(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(order_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<Vec<OrderTimelineItem>> {
+    let data = orders_service::get_order_timeline(&state, user_id, order_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn get_payment_plan(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(order_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<PaymentPlanResp> {
+    let data = orders_service::get_payment_plan(&state, user_id, order_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
@@ -1,17 +1,39 @@
-use axum::Json;
+use axum::{body::Bytes, extract::Path, http::HeaderMap, Json};
 
 use crate::middleware::auth::AuthUser;
 use crate::common::ApiResponse;
-use crate::dto::payments::{CreatePaymentReq, PaymentResp};
+use crate::dto::payments::{
+    ConfirmPaymentReq, CreatePaymentReq, PaymentCallbackResp, PaymentResp,
+};
 use crate::error::ApiResult;
 use crate::services::payments_service;
 use crate::state::AppState;
 
 pub async fn create_payment(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreatePaymentReq>,
 ) -> ApiResult<PaymentResp> {
     let data = payments_service::create_payment(&state, user_id, req).await?;
     Ok(Json(ApiResponse::ok(data)))
 }
+
+pub async fn confirm_payment(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<ConfirmPaymentReq>,
+) -> ApiResult<PaymentResp> {
+    let data = payments_service::confirm_payment(&state, user_id, id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn payment_callback(
+    Path(channel): Path<String>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<PaymentCallbackResp> {
+    let data = payments_service::handle_callback(&state, &channel, &headers, &body).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
@@ -1,9 +1,16 @@
-use axum::Json;
+use axum::{
+    http::{header, StatusCode},
+    response::Response,
+    Json,
+};
 
 use crate::common::ApiResponse;
 use crate::dto::pagination::Paged;
-use crate::dto::photographers::{PhotographerListItem, PhotographerListQuery};
-use crate::error::ApiResult;
+use crate::dto::photographers::{
+    PhotographerEarningsQuery, PhotographerEarningsResp, PhotographerListItem,
+    PhotographerListQuery,
+};
+use crate::error::{ApiError, ApiResult};
 use crate::middleware::auth::AuthUser;
 use crate::services::photographers_service;
 use crate::state::AppState;
@@ -16,3 +23,27 @@ pub async fn list_photographers(
     let data = photographers_service::list_photographers(&state, query).await?;
     Ok(Json(ApiResponse::ok(data)))
 }
+
+pub async fn get_my_earnings(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<PhotographerEarningsQuery>,
+) -> ApiResult<PhotographerEarningsResp> {
+    let data = photographers_service::get_my_earnings(&state, user_id, query).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn export_my_earnings(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<PhotographerEarningsQuery>,
+) -> Result<Response, ApiError> {
+    let (month, csv) = photographers_service::export_my_earnings_csv(&state, user_id, query).await?;
+    let disposition = format!("attachment; filename=\"earnings-{month}.csv\"");
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .body(axum::body::Body::from(csv))
+        .map_err(|_| ApiError::internal())
+}
@@ -4,7 +4,8 @@ use crate::middleware::auth::AuthUser;
 use crate::common::ApiResponse;
 use crate::dto::quotes::{
     AcceptQuoteResp, CreateQuoteReq, MyQuoteListItem, MyQuoteListQuery, QuoteDetailResp,
-    QuoteListItem, QuoteListQuery, QuoteResp, QuoteVersionItem, UpdateQuoteReq,
+    QuoteListItem, QuoteListQuery, QuoteResp, QuoteVersionDiffQuery, QuoteVersionDiffResp,
+    QuoteVersionItem, UpdateQuoteReq,
 };
 use crate::dto::pagination::Paged;
 use crate::error::ApiResult;
@@ -12,7 +13,7 @@ use crate::services::quotes_service;
 use crate::state::AppState;
 
 pub async fn list_quotes(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<QuoteListQuery>,
 ) -> ApiResult<Paged<QuoteListItem>> {
@@ -21,7 +22,7 @@ pub async fn list_quotes(
 }
 
 pub async fn list_my_quotes(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(query): axum::extract::Query<MyQuoteListQuery>,
 ) -> ApiResult<Paged<MyQuoteListItem>> {
@@ -30,7 +31,7 @@ pub async fn list_my_quotes(
 }
 
 pub async fn get_quote(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(quote_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<QuoteDetailResp> {
@@ -39,7 +40,7 @@ pub async fn get_quote(
 }
 
 pub async fn update_quote(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(quote_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<UpdateQuoteReq>,
@@ -49,7 +50,7 @@ pub async fn update_quote(
 }
 
 pub async fn list_quote_versions(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(quote_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<Vec<QuoteVersionItem>> {
@@ -57,8 +58,46 @@ pub async fn list_quote_versions(
     Ok(Json(ApiResponse::ok(data)))
 }
 
+pub async fn diff_quote_versions(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(quote_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<QuoteVersionDiffQuery>,
+) -> ApiResult<QuoteVersionDiffResp> {
+    let data = quotes_service::diff_quote_versions(&state, user_id, quote_id, query).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn create_counter_offer(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(quote_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<UpdateQuoteReq>,
+) -> ApiResult<QuoteVersionItem> {
+    let data = quotes_service::create_counter_offer(&state, user_id, quote_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn accept_counter_offer(
+    AuthUser { user_id, .. }: AuthUser,
+    Path((quote_id, version)): Path<(i64, i32)>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<QuoteVersionItem> {
+    let data = quotes_service::accept_counter_offer(&state, user_id, quote_id, version).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn decline_counter_offer(
+    AuthUser { user_id, .. }: AuthUser,
+    Path((quote_id, version)): Path<(i64, i32)>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<QuoteVersionItem> {
+    let data = quotes_service::decline_counter_offer(&state, user_id, quote_id, version).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
 pub async fn withdraw_quote(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(quote_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<QuoteResp> {
@@ -67,7 +106,7 @@ pub async fn withdraw_quote(
 }
 
 pub async fn create_quote(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateQuoteReq>,
 ) -> ApiResult<QuoteResp> {
@@ -76,7 +115,7 @@ pub async fn create_quote(
 }
 
 pub async fn accept_quote(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(quote_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<AcceptQuoteResp> {
@@ -1,17 +1,70 @@
-use axum::Json;
+use axum::{extract::Path, Json};
 
 use crate::middleware::auth::AuthUser;
 use crate::common::ApiResponse;
-use crate::dto::refunds::{CreateRefundReq, RefundResp};
+use crate::dto::pagination::Paged;
+use crate::dto::refunds::{
+    ApproveRefundReq, CompleteRefundReq, CreateRefundReq, RefundListQuery, RefundResp,
+    RejectRefundReq, ReviewRefundReq,
+};
 use crate::error::ApiResult;
 use crate::services::refunds_service;
 use crate::state::AppState;
 
 pub async fn create_refund(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateRefundReq>,
 ) -> ApiResult<RefundResp> {
     let data = refunds_service::create_refund(&state, user_id, req).await?;
     Ok(Json(ApiResponse::ok(data)))
 }
+
+pub async fn list_refunds(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<RefundListQuery>,
+) -> ApiResult<Paged<RefundResp>> {
+    let data = refunds_service::list_refunds(&state, user_id, query).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn approve_refund(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(refund_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<ApproveRefundReq>,
+) -> ApiResult<RefundResp> {
+    let data = refunds_service::approve_refund(&state, user_id, refund_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn reject_refund(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(refund_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<RejectRefundReq>,
+) -> ApiResult<RefundResp> {
+    let data = refunds_service::reject_refund(&state, user_id, refund_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn complete_refund(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(refund_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<CompleteRefundReq>,
+) -> ApiResult<RefundResp> {
+    let data = refunds_service::complete_refund(&state, user_id, refund_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn review_refund(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(refund_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<ReviewRefundReq>,
+) -> ApiResult<RefundResp> {
+    let data = refunds_service::review_refund(&state, user_id, refund_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
@@ -0,0 +1,54 @@
+use axum::{extract::Path, Json};
+
+use crate::middleware::auth::AuthUser;
+use crate::common::ApiResponse;
+use crate::dto::roles::{GrantRoleReq, RoleResp, UpdateRolePermissionsReq, UserRolesResp};
+use crate::error::ApiResult;
+use crate::services::roles_service;
+use crate::state::AppState;
+
+pub async fn list_roles(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<Vec<RoleResp>> {
+    let data = roles_service::list_roles(&state, user_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn update_role_permissions(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(role): Path<String>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<UpdateRolePermissionsReq>,
+) -> ApiResult<RoleResp> {
+    let data = roles_service::update_role_permissions(&state, user_id, role, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn list_user_roles(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(target_user_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<UserRolesResp> {
+    let data = roles_service::list_user_roles(&state, user_id, target_user_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn grant_role(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(target_user_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<GrantRoleReq>,
+) -> ApiResult<UserRolesResp> {
+    let data = roles_service::grant_role(&state, user_id, target_user_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn revoke_role(
+    AuthUser { user_id, .. }: AuthUser,
+    Path((target_user_id, role)): Path<(i64, String)>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<UserRolesResp> {
+    let data = roles_service::revoke_role(&state, user_id, target_user_id, role).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
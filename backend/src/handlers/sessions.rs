@@ -0,0 +1,43 @@
+use axum::{extract::Path, http::HeaderMap, Json};
+
+use crate::middleware::auth::AuthUser;
+use crate::common::ApiResponse;
+use crate::dto::sessions::{RefreshTokenReq, RevokeSessionResp, SessionItem, TokenResp};
+use crate::error::ApiResult;
+use crate::services::sessions_service::{self, SessionMeta};
+use crate::state::AppState;
+
+pub async fn refresh(
+    headers: HeaderMap,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<RefreshTokenReq>,
+) -> ApiResult<TokenResp> {
+    let meta = SessionMeta::from_headers(&headers);
+    let data = sessions_service::refresh_session(&state, req, meta).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn logout(
+    AuthUser { session_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<RevokeSessionResp> {
+    let data = sessions_service::logout(&state, session_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn list_sessions(
+    AuthUser { user_id, session_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<Vec<SessionItem>> {
+    let data = sessions_service::list_sessions(&state, user_id, session_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn revoke_session(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(target_session_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<RevokeSessionResp> {
+    let data = sessions_service::revoke_session(&state, user_id, target_session_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
@@ -8,7 +8,7 @@ use crate::services::teams_service;
 use crate::state::AppState;
 
 pub async fn create_team(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateTeamReq>,
 ) -> ApiResult<TeamResp> {
@@ -17,7 +17,7 @@ pub async fn create_team(
 }
 
 pub async fn list_teams(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<Vec<TeamResp>> {
     let data = teams_service::list_teams(&state, user_id).await?;
@@ -25,7 +25,7 @@ pub async fn list_teams(
 }
 
 pub async fn update_team(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(team_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<UpdateTeamReq>,
@@ -35,7 +35,7 @@ pub async fn update_team(
 }
 
 pub async fn add_member(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(team_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<AddTeamMemberReq>,
@@ -45,7 +45,7 @@ pub async fn add_member(
 }
 
 pub async fn list_members(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path(team_id): Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<Vec<TeamMemberResp>> {
@@ -54,7 +54,7 @@ pub async fn list_members(
 }
 
 pub async fn remove_member(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     Path((team_id, member_id)): Path<(i64, i64)>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<TeamMemberResp> {
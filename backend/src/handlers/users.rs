@@ -0,0 +1,66 @@
+use axum::{extract::Path, Json};
+
+use crate::middleware::auth::AuthUser;
+use crate::common::ApiResponse;
+use crate::dto::users::{
+    BanUserReq, ChangePasswordReq, DeleteAccountResp, ForcePasswordResetReq, PasswordResp,
+    UserExport, UserStatusResp,
+};
+use crate::error::ApiResult;
+use crate::services::users_service;
+use crate::state::AppState;
+
+pub async fn ban_user(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(target_user_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<BanUserReq>,
+) -> ApiResult<UserStatusResp> {
+    let data = users_service::ban_user(&state, user_id, target_user_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn unban_user(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(target_user_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<BanUserReq>,
+) -> ApiResult<UserStatusResp> {
+    let data = users_service::unban_user(&state, user_id, target_user_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn change_password(
+    AuthUser { user_id, session_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<ChangePasswordReq>,
+) -> ApiResult<PasswordResp> {
+    let data = users_service::change_password(&state, user_id, session_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn force_password_reset(
+    AuthUser { user_id, .. }: AuthUser,
+    Path(target_user_id): Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<ForcePasswordResetReq>,
+) -> ApiResult<PasswordResp> {
+    let data = users_service::force_password_reset(&state, user_id, target_user_id, req).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn delete_account(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<DeleteAccountResp> {
+    let data = users_service::delete_account(&state, user_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
+
+pub async fn export_account(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> ApiResult<UserExport> {
+    let data = users_service::export_account(&state, user_id).await?;
+    Ok(Json(ApiResponse::ok(data)))
+}
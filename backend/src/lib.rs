@@ -6,7 +6,11 @@ pub mod errors;
 pub mod handlers;
 pub mod middleware;
 pub mod migration;
+pub mod money;
+pub mod payment_provider;
 pub mod repositories;
 pub mod routes;
+pub mod scheduler;
 pub mod services;
+pub mod sms;
 pub mod state;
@@ -1,5 +1,9 @@
 use axum::{routing::get, Router};
+use api_gateway::middleware::auth::AuthCache;
+use api_gateway::payment_provider::payment_gateway_from_env;
 use api_gateway::routes::create_router;
+use api_gateway::scheduler::{scheduler_interval_from_env, spawn_scheduler};
+use api_gateway::sms::sms_sender_from_env;
 use api_gateway::state::AppState;
 use sea_orm::Database;
 use std::env;
@@ -17,7 +21,21 @@ async fn main() {
         .await
         .expect("failed to connect orm");
 
-    let state = AppState { orm };
+    let sms = sms_sender_from_env().expect("failed to configure sms sender");
+    let payments = payment_gateway_from_env().expect("failed to configure payment gateway");
+    let scheduler_interval =
+        scheduler_interval_from_env().expect("failed to configure scheduler");
+
+    let state = AppState {
+        orm,
+        sms,
+        auth_cache: AuthCache::default(),
+        payments,
+    };
+
+    if let Some(interval) = scheduler_interval {
+        spawn_scheduler(state.clone(), interval);
+    }
 
     let admin_dir = env::var("ADMIN_WEB_DIR").unwrap_or_else(|_| "admin_web".to_string());
     let static_service =
@@ -25,7 +43,7 @@ async fn main() {
 
     let app = Router::new()
         .route("/health", get(health))
-        .nest("/api/v1", create_router())
+        .nest("/api/v1", create_router(state.clone()))
         .fallback_service(static_service)
         .with_state(state);
 
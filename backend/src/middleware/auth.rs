@@ -1,14 +1,73 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
 use axum::extract::{FromRef, FromRequestParts};
 use axum::http::request::Parts;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
 use crate::error::ApiError;
+use crate::errors::DomainError;
+use crate::repositories::{sessions_repo, users_repo};
+use crate::services::roles_service;
 use crate::state::AppState;
-use crate::entity::sessions;
+
+const AUTH_CACHE_TTL: Duration = Duration::from_secs(30);
+const AUTH_CACHE_MAX_ENTRIES: usize = 10_000;
 
 #[derive(Debug, Clone)]
 pub struct AuthUser {
     pub user_id: i64,
+    pub session_id: i64,
+    pub roles: Vec<String>,
+}
+
+impl AuthUser {
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
+#[derive(Clone)]
+struct CachedUser {
+    active: bool,
+    roles: Vec<String>,
+    loaded_at: Instant,
+}
+
+/// Short-lived per-process cache of user status and roles so the extractor
+/// does not hit `users` / `user_roles` on every request. Entries are dropped
+/// explicitly when status or roles change.
+#[derive(Clone, Default)]
+pub struct AuthCache {
+    inner: Arc<RwLock<HashMap<i64, CachedUser>>>,
+}
+
+impl AuthCache {
+    fn get(&self, user_id: i64) -> Option<CachedUser> {
+        let map = self.inner.read().ok()?;
+        map.get(&user_id)
+            .filter(|c| c.loaded_at.elapsed() < AUTH_CACHE_TTL)
+            .cloned()
+    }
+
+    fn insert(&self, user_id: i64, entry: CachedUser) {
+        if let Ok(mut map) = self.inner.write() {
+            if map.len() >= AUTH_CACHE_MAX_ENTRIES {
+                map.retain(|_, c| c.loaded_at.elapsed() < AUTH_CACHE_TTL);
+            }
+            map.insert(user_id, entry);
+        }
+    }
+
+    pub fn invalidate(&self, user_id: i64) {
+        if let Ok(mut map) = self.inner.write() {
+            map.remove(&user_id);
+        }
+    }
+}
+
+pub fn is_user_active(user: &crate::entity::users::Model) -> bool {
+    user.status == "active" && user.deleted_at.is_none()
 }
 
 impl<S> FromRequestParts<S> for AuthUser
@@ -29,15 +88,73 @@ where
             .strip_prefix("Bearer ")
             .ok_or_else(ApiError::unauthorized)?;
 
-        let AppState { orm, .. } = AppState::from_ref(state);
-        let session = sessions::Entity::find()
-            .filter(sessions::Column::Token.eq(token))
-            .filter(sessions::Column::ExpiredAt.gt(chrono::Utc::now()))
-            .one(&orm)
+        let AppState { orm, auth_cache, .. } = AppState::from_ref(state);
+        let session = sessions_repo::find_active_by_token(&orm, token, chrono::Utc::now())
             .await
-            .map_err(|_| ApiError::unauthorized())?;
+            .map_err(|_| ApiError::unauthorized())?
+            .ok_or_else(ApiError::unauthorized)?;
+
+        let cached = match auth_cache.get(session.user_id) {
+            Some(cached) => cached,
+            None => {
+                let user = users_repo::find_user_by_id(&orm, session.user_id)
+                    .await
+                    .map_err(|_| ApiError::unauthorized())?
+                    .ok_or_else(ApiError::unauthorized)?;
+                let roles = roles_service::resolve_roles(&orm, user.id).await?;
+                let entry = CachedUser {
+                    active: is_user_active(&user),
+                    roles,
+                    loaded_at: Instant::now(),
+                };
+                auth_cache.insert(user.id, entry.clone());
+                entry
+            }
+        };
+
+        if !cached.active {
+            return Err(DomainError::AccountDisabled.into());
+        }
+
+        Ok(AuthUser {
+            user_id: session.user_id,
+            session_id: session.id,
+            roles: cached.roles,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_cache_invalidate() {
+        let cache = AuthCache::default();
+        cache.insert(
+            1,
+            CachedUser {
+                active: true,
+                roles: vec!["user".to_string()],
+                loaded_at: Instant::now(),
+            },
+        );
+        assert!(cache.get(1).is_some());
+        cache.invalidate(1);
+        assert!(cache.get(1).is_none());
+    }
 
-        let user_id = session.ok_or_else(ApiError::unauthorized)?.user_id;
-        Ok(AuthUser { user_id })
+    #[test]
+    fn test_auth_cache_expires() {
+        let cache = AuthCache::default();
+        cache.insert(
+            1,
+            CachedUser {
+                active: true,
+                roles: Vec::new(),
+                loaded_at: Instant::now() - AUTH_CACHE_TTL,
+            },
+        );
+        assert!(cache.get(1).is_none());
     }
 }
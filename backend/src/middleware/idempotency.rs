@@ -0,0 +1,171 @@
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{FromRequestParts, OriginalUri, Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+
+use crate::entity::idempotency_keys;
+use crate::error::ApiError;
+use crate::middleware::auth::AuthUser;
+use crate::repositories::idempotency_repo;
+use crate::state::AppState;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const KEY_MAX_LEN: usize = 128;
+const KEY_TTL_HOURS: i64 = 24;
+/// 携带幂等键的请求最长处理时长，超时即中止处理（未提交的事务随之回滚）。
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// 处理中的键超过该时长仍无响应，视为进程中断遗留，允许重新执行。
+/// 取请求超时的两倍，保证仍在执行的请求不会被重新放行。
+const KEY_LOCK_SECS: i64 = 2 * REQUEST_TIMEOUT_SECS as i64;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// 写接口幂等：同一用户、同一 `Idempotency-Key` 在有效期内重放首次响应；
+/// 请求内容不同返回 409 `idempotency_key_reused`，首次请求未完成返回 409
+/// `idempotency_request_in_progress`。未携带该头的请求不受影响。
+pub async fn idempotency(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    match handle(state, req, next).await {
+        Ok(response) => response,
+        Err(err) => err.into_response(),
+    }
+}
+
+async fn handle(state: AppState, req: Request, next: Next) -> Result<Response, ApiError> {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(next.run(req).await);
+    }
+    let Some(raw_key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(req).await);
+    };
+    let key = parse_key(raw_key)?;
+
+    let (mut parts, body) = req.into_parts();
+    let auth = AuthUser::from_request_parts(&mut parts, &state).await?;
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| ApiError::bad_request("invalid_body"))?;
+    // 嵌套路由内 uri 已去掉前缀，取原始路径，避免不同接口同一请求体得到相同哈希。
+    let uri = parts
+        .extensions
+        .get::<OriginalUri>()
+        .map(|u| u.0.clone())
+        .unwrap_or_else(|| parts.uri.clone());
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("");
+    let hash = request_hash(parts.method.as_str(), path, &body);
+
+    let now = chrono::Utc::now();
+    let lock_before = now - chrono::Duration::seconds(KEY_LOCK_SECS);
+    idempotency_repo::delete_stale_key(&state.orm, auth.user_id, &key, now, lock_before).await?;
+    let expires_at = now + chrono::Duration::hours(KEY_TTL_HOURS);
+    let claimed =
+        idempotency_repo::try_claim_key(&state.orm, auth.user_id, &key, &hash, expires_at).await?;
+    let Some(record_id) = claimed else {
+        let existing = idempotency_repo::find_key(&state.orm, auth.user_id, &key)
+            .await?
+            .ok_or_else(|| ApiError::conflict("idempotency_request_in_progress"))?;
+        return replay(existing, &hash);
+    };
+
+    let timeout = std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS);
+    let run = next.run(Request::from_parts(parts, Body::from(body)));
+    // 超时后无法确定业务是否已提交，保留处理中的键，锁超时前的重试返回 409。
+    let Ok(response) = tokio::time::timeout(timeout, run).await else {
+        tracing::error!(record_id, "idempotency_request_timed_out");
+        return Err(ApiError::internal());
+    };
+    // 服务端错误不缓存，释放键以便客户端重试。
+    if response.status().is_server_error() {
+        idempotency_repo::delete_key(&state.orm, record_id).await?;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(err) => {
+            tracing::error!(error = ?err, record_id, "idempotency_response_read_failed");
+            return Err(ApiError::internal());
+        }
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let status = i32::from(parts.status.as_u16());
+    // 业务已执行但响应未能记录：返回 500 并保留处理中的键，避免客户端立即重试重复执行。
+    if let Err(err) =
+        idempotency_repo::save_response(&state.orm, record_id, status, content_type, body.to_vec())
+            .await
+    {
+        tracing::error!(error = ?err, record_id, "idempotency_save_failed");
+        return Err(ApiError::internal());
+    }
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+fn parse_key(raw: &HeaderValue) -> Result<String, ApiError> {
+    let key = raw.to_str().map(str::trim).unwrap_or("");
+    let valid = !key.is_empty()
+        && key.len() <= KEY_MAX_LEN
+        && key.bytes().all(|b| b.is_ascii_graphic());
+    if !valid {
+        return Err(ApiError::bad_request("invalid_idempotency_key"));
+    }
+    Ok(key.to_string())
+}
+
+fn request_hash(method: &str, path: &str, body: &Bytes) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn replay(record: idempotency_keys::Model, hash: &str) -> Result<Response, ApiError> {
+    if record.request_hash != hash {
+        return Err(ApiError::conflict("idempotency_key_reused"));
+    }
+    let Some(status) = record.status_code else {
+        return Err(ApiError::conflict("idempotency_request_in_progress"));
+    };
+    let status = u16::try_from(status)
+        .ok()
+        .and_then(|s| StatusCode::from_u16(s).ok())
+        .ok_or_else(ApiError::internal)?;
+
+    let mut response = Response::new(Body::from(record.response_body.unwrap_or_default()));
+    *response.status_mut() = status;
+    let headers = response.headers_mut();
+    if let Some(content_type) = record.content_type.and_then(|v| HeaderValue::from_str(&v).ok()) {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_and_request_hash() {
+        assert!(parse_key(&HeaderValue::from_static(" pay-42 ")).is_ok());
+        assert!(parse_key(&HeaderValue::from_static("")).is_err());
+        assert!(parse_key(&HeaderValue::from_static("a b")).is_err());
+        let long = "k".repeat(KEY_MAX_LEN + 1);
+        assert!(parse_key(&HeaderValue::from_str(&long).unwrap()).is_err());
+
+        let body = Bytes::from_static(br#"{"amount":"100.00"}"#);
+        let hash = request_hash("POST", "/payments", &body);
+        assert_eq!(hash, request_hash("POST", "/payments", &body));
+        assert_ne!(hash, request_hash("POST", "/payments", &Bytes::from_static(b"{}")));
+        assert_ne!(hash, request_hash("POST", "/refunds", &body));
+    }
+}
@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ROLE_PERMISSIONS: &[(&str, &[&str])] = &[
+    (
+        "admin",
+        &[
+            "audits.read",
+            "audits.write",
+            "metrics.read",
+            "reports.export",
+            "users.read",
+            "orders.read",
+            "orders.freeze",
+            "disputes.read",
+            "disputes.resolve",
+            "portfolios.review",
+            "merchants.review",
+            "photographers.review",
+            "roles.manage",
+        ],
+    ),
+    (
+        "ops",
+        &[
+            "metrics.read",
+            "reports.export",
+            "users.read",
+            "orders.read",
+            "orders.freeze",
+            "disputes.read",
+            "disputes.resolve",
+            "portfolios.review",
+            "merchants.review",
+        ],
+    ),
+    (
+        "manager",
+        &[
+            "metrics.read",
+            "reports.export",
+            "users.read",
+            "orders.read",
+            "disputes.read",
+        ],
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RolePermissions::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(RolePermissions::RoleId).big_integer().not_null())
+                    .col(ColumnDef::new(RolePermissions::Permission).text().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(RolePermissions::RoleId)
+                            .col(RolePermissions::Permission),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_permissions_role")
+                            .from(RolePermissions::Table, RolePermissions::RoleId)
+                            .to(Roles::Table, Roles::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_roles_role")
+                    .table(UserRoles::Table)
+                    .col(UserRoles::RoleId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        for (role, permissions) in ROLE_PERMISSIONS {
+            db.execute_unprepared(&format!(
+                "INSERT INTO roles (name) VALUES ('{role}') ON CONFLICT (name) DO NOTHING"
+            ))
+            .await?;
+            for permission in permissions.iter() {
+                db.execute_unprepared(&format!(
+                    "INSERT INTO role_permissions (role_id, permission) \
+                     SELECT id, '{permission}' FROM roles WHERE name = '{role}' \
+                     ON CONFLICT DO NOTHING"
+                ))
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_user_roles_role").table(UserRoles::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(RolePermissions::Table).if_exists().to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Roles {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum UserRoles {
+    Table,
+    RoleId,
+}
+
+#[derive(DeriveIden)]
+enum RolePermissions {
+    Table,
+    RoleId,
+    Permission,
+}
@@ -0,0 +1,112 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE orders DROP CONSTRAINT IF EXISTS orders_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE orders ADD CONSTRAINT orders_status_check CHECK (status IN \
+             ('confirmed', 'paid', 'ongoing', 'completed', 'reviewed', 'cancelled', 'frozen'))",
+        )
+        .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrderStatusHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OrderStatusHistory::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OrderStatusHistory::OrderId).big_integer().not_null())
+                    .col(ColumnDef::new(OrderStatusHistory::FromStatus).text())
+                    .col(ColumnDef::new(OrderStatusHistory::ToStatus).text().not_null())
+                    .col(ColumnDef::new(OrderStatusHistory::ActorId).big_integer())
+                    .col(ColumnDef::new(OrderStatusHistory::Reason).text())
+                    .col(
+                        ColumnDef::new(OrderStatusHistory::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_order_status_history_order")
+                            .from(OrderStatusHistory::Table, OrderStatusHistory::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_order_status_history_actor")
+                            .from(OrderStatusHistory::Table, OrderStatusHistory::ActorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_order_status_history_order_created")
+                    .table(OrderStatusHistory::Table)
+                    .col(OrderStatusHistory::OrderId)
+                    .col(OrderStatusHistory::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OrderStatusHistory::Table).if_exists().to_owned())
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE orders DROP CONSTRAINT IF EXISTS orders_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE orders ADD CONSTRAINT orders_status_check CHECK (status IN \
+             ('confirmed', 'paid', 'ongoing', 'completed', 'reviewed', 'cancelled'))",
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum OrderStatusHistory {
+    Table,
+    Id,
+    OrderId,
+    FromStatus,
+    ToStatus,
+    ActorId,
+    Reason,
+    CreatedAt,
+}
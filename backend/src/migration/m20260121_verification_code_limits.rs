@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VerificationCodes::Table)
+                    .add_column(
+                        ColumnDef::new(VerificationCodes::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(
+                        ColumnDef::new(VerificationCodes::ConsumedAt).timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_verification_codes_phone_created")
+                    .table(VerificationCodes::Table)
+                    .col(VerificationCodes::Phone)
+                    .col(VerificationCodes::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_verification_codes_phone_created")
+                    .table(VerificationCodes::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(VerificationCodes::Table)
+                    .drop_column(VerificationCodes::Attempts)
+                    .drop_column(VerificationCodes::ConsumedAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum VerificationCodes {
+    Table,
+    Phone,
+    Attempts,
+    ConsumedAt,
+    CreatedAt,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sessions::Table)
+                    .add_column(ColumnDef::new(Sessions::RefreshToken).text().null().unique_key())
+                    .add_column(ColumnDef::new(Sessions::RefreshExpiredAt).timestamp_with_time_zone())
+                    .add_column(ColumnDef::new(Sessions::UserAgent).text())
+                    .add_column(ColumnDef::new(Sessions::DeviceName).text())
+                    .add_column(ColumnDef::new(Sessions::Ip).string_len(64))
+                    .add_column(ColumnDef::new(Sessions::LastSeenAt).timestamp_with_time_zone())
+                    .add_column(ColumnDef::new(Sessions::RevokedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_sessions_user")
+                    .table(Sessions::Table)
+                    .col(Sessions::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_sessions_user").table(Sessions::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sessions::Table)
+                    .drop_column(Sessions::RefreshToken)
+                    .drop_column(Sessions::RefreshExpiredAt)
+                    .drop_column(Sessions::UserAgent)
+                    .drop_column(Sessions::DeviceName)
+                    .drop_column(Sessions::Ip)
+                    .drop_column(Sessions::LastSeenAt)
+                    .drop_column(Sessions::RevokedAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Sessions {
+    Table,
+    UserId,
+    RefreshToken,
+    RefreshExpiredAt,
+    UserAgent,
+    DeviceName,
+    Ip,
+    LastSeenAt,
+    RevokedAt,
+}
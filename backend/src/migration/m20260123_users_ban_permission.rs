@@ -0,0 +1,27 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO role_permissions (role_id, permission) \
+                 SELECT id, 'users.ban' FROM roles WHERE name IN ('admin', 'ops') \
+                 ON CONFLICT DO NOTHING",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DELETE FROM role_permissions WHERE permission = 'users.ban'")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Payments::Table)
+                    .add_column(ColumnDef::new(Payments::ProviderRef).text().null().unique_key())
+                    .add_column(ColumnDef::new(Payments::PayParams).json_binary())
+                    .add_column(ColumnDef::new(Payments::CallbackAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Payments::Table)
+                    .drop_column(Payments::ProviderRef)
+                    .drop_column(Payments::PayParams)
+                    .drop_column(Payments::CallbackAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Payments {
+    Table,
+    ProviderRef,
+    PayParams,
+    CallbackAt,
+}
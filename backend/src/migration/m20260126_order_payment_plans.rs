@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrderPaymentPlans::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OrderPaymentPlans::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OrderPaymentPlans::OrderId).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(OrderPaymentPlans::Stage)
+                            .text()
+                            .not_null()
+                            .check(
+                                Expr::col(OrderPaymentPlans::Stage)
+                                    .is_in(vec!["deposit", "mid", "final"]),
+                            ),
+                    )
+                    .col(ColumnDef::new(OrderPaymentPlans::Amount).decimal_len(12, 2).not_null())
+                    .col(ColumnDef::new(OrderPaymentPlans::DueAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(OrderPaymentPlans::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_order_payment_plans_order")
+                            .from(OrderPaymentPlans::Table, OrderPaymentPlans::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_order_payment_plans_order_stage")
+                    .table(OrderPaymentPlans::Table)
+                    .col(OrderPaymentPlans::OrderId)
+                    .col(OrderPaymentPlans::Stage)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OrderPaymentPlans::Table).if_exists().to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum OrderPaymentPlans {
+    Table,
+    Id,
+    OrderId,
+    Stage,
+    Amount,
+    DueAt,
+    CreatedAt,
+}
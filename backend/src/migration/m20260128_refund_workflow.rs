@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Refunds::Table)
+                    .add_column(ColumnDef::new(Refunds::ApprovedAmount).decimal_len(12, 2))
+                    .add_column(ColumnDef::new(Refunds::ReviewerId).big_integer())
+                    .add_column(ColumnDef::new(Refunds::ReviewNote).text())
+                    .add_column(ColumnDef::new(Refunds::ReviewedAt).timestamp_with_time_zone())
+                    .add_column(ColumnDef::new(Refunds::CompletionProofUrl).text())
+                    .add_column(ColumnDef::new(Refunds::CompletedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE refunds DROP CONSTRAINT IF EXISTS refunds_status_check")
+            .await?;
+        db.execute_unprepared("UPDATE refunds SET status = 'completed' WHERE status = 'paid'")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE refunds ADD CONSTRAINT refunds_status_check CHECK (status IN \
+             ('pending', 'approved', 'rejected', 'completed'))",
+        )
+        .await?;
+        db.execute_unprepared(
+            "INSERT INTO role_permissions (role_id, permission) \
+             SELECT id, 'refunds.review' FROM roles WHERE name IN ('admin', 'ops') \
+             ON CONFLICT DO NOTHING",
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_refunds_order")
+                    .table(Refunds::Table)
+                    .col(Refunds::OrderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_refunds_order").table(Refunds::Table).to_owned())
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared("DELETE FROM role_permissions WHERE permission = 'refunds.review'")
+            .await?;
+        db.execute_unprepared("ALTER TABLE refunds DROP CONSTRAINT IF EXISTS refunds_status_check")
+            .await?;
+        db.execute_unprepared("UPDATE refunds SET status = 'paid' WHERE status = 'completed'")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE refunds ADD CONSTRAINT refunds_status_check CHECK (status IN \
+             ('pending', 'approved', 'rejected', 'paid'))",
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Refunds::Table)
+                    .drop_column(Refunds::ApprovedAmount)
+                    .drop_column(Refunds::ReviewerId)
+                    .drop_column(Refunds::ReviewNote)
+                    .drop_column(Refunds::ReviewedAt)
+                    .drop_column(Refunds::CompletionProofUrl)
+                    .drop_column(Refunds::CompletedAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Refunds {
+    Table,
+    OrderId,
+    ApprovedAmount,
+    ReviewerId,
+    ReviewNote,
+    ReviewedAt,
+    CompletionProofUrl,
+    CompletedAt,
+}
@@ -0,0 +1,257 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LedgerAccounts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LedgerAccounts::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LedgerAccounts::OwnerType)
+                            .text()
+                            .not_null()
+                            .check(
+                                Expr::col(LedgerAccounts::OwnerType)
+                                    .is_in(vec!["user", "photographer", "team", "platform"]),
+                            ),
+                    )
+                    .col(ColumnDef::new(LedgerAccounts::OwnerId).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(LedgerAccounts::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_ledger_accounts_owner")
+                    .table(LedgerAccounts::Table)
+                    .col(LedgerAccounts::OwnerType)
+                    .col(LedgerAccounts::OwnerId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LedgerEntries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LedgerEntries::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LedgerEntries::EntryType)
+                            .text()
+                            .not_null()
+                            .check(Expr::col(LedgerEntries::EntryType).is_in(vec![
+                                "payment",
+                                "refund",
+                                "service_fee",
+                                "service_fee_reversal",
+                            ])),
+                    )
+                    .col(ColumnDef::new(LedgerEntries::OrderId).big_integer())
+                    .col(ColumnDef::new(LedgerEntries::ReferenceType).text().not_null())
+                    .col(ColumnDef::new(LedgerEntries::ReferenceId).big_integer().not_null())
+                    .col(ColumnDef::new(LedgerEntries::Memo).text())
+                    .col(
+                        ColumnDef::new(LedgerEntries::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ledger_entries_order")
+                            .from(LedgerEntries::Table, LedgerEntries::OrderId)
+                            .to(Orders::Table, Orders::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_ledger_entries_reference")
+                    .table(LedgerEntries::Table)
+                    .col(LedgerEntries::EntryType)
+                    .col(LedgerEntries::ReferenceType)
+                    .col(LedgerEntries::ReferenceId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ledger_entries_order")
+                    .table(LedgerEntries::Table)
+                    .col(LedgerEntries::OrderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LedgerLines::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LedgerLines::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LedgerLines::EntryId).big_integer().not_null())
+                    .col(ColumnDef::new(LedgerLines::AccountId).big_integer().not_null())
+                    .col(ColumnDef::new(LedgerLines::Amount).decimal_len(12, 2).not_null())
+                    .col(
+                        ColumnDef::new(LedgerLines::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ledger_lines_entry")
+                            .from(LedgerLines::Table, LedgerLines::EntryId)
+                            .to(LedgerEntries::Table, LedgerEntries::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ledger_lines_account")
+                            .from(LedgerLines::Table, LedgerLines::AccountId)
+                            .to(LedgerAccounts::Table, LedgerAccounts::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ledger_lines_account_created")
+                    .table(LedgerLines::Table)
+                    .col(LedgerLines::AccountId)
+                    .col(LedgerLines::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ledger_lines_entry")
+                    .table(LedgerLines::Table)
+                    .col(LedgerLines::EntryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 分录写入后不可修改或删除，更正通过追加冲销分录完成。
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "CREATE OR REPLACE FUNCTION ledger_reject_mutation() RETURNS trigger AS $$ \
+             BEGIN RAISE EXCEPTION 'ledger rows are immutable'; END; \
+             $$ LANGUAGE plpgsql",
+        )
+        .await?;
+        for table in ["ledger_entries", "ledger_lines"] {
+            db.execute_unprepared(&format!(
+                "CREATE TRIGGER {table}_immutable BEFORE UPDATE OR DELETE ON {table} \
+                 FOR EACH ROW EXECUTE FUNCTION ledger_reject_mutation()"
+            ))
+            .await?;
+        }
+        db.execute_unprepared(
+            "INSERT INTO role_permissions (role_id, permission) \
+             SELECT id, 'ledger.read' FROM roles WHERE name = 'admin' \
+             ON CONFLICT DO NOTHING",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DELETE FROM role_permissions WHERE permission = 'ledger.read'")
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(LedgerLines::Table).if_exists().to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(LedgerEntries::Table).if_exists().to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(LedgerAccounts::Table).if_exists().to_owned())
+            .await?;
+        db.execute_unprepared("DROP FUNCTION IF EXISTS ledger_reject_mutation()")
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum LedgerAccounts {
+    Table,
+    Id,
+    OwnerType,
+    OwnerId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum LedgerEntries {
+    Table,
+    Id,
+    EntryType,
+    OrderId,
+    ReferenceType,
+    ReferenceId,
+    Memo,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum LedgerLines {
+    Table,
+    Id,
+    EntryId,
+    AccountId,
+    Amount,
+    CreatedAt,
+}
@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .add_column(ColumnDef::new(Orders::QuoteVersion).integer())
+                    .add_column(ColumnDef::new(Orders::Location).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrderContracts::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OrderContracts::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OrderContracts::OrderId).big_integer().not_null())
+                    .col(ColumnDef::new(OrderContracts::Version).integer().not_null())
+                    .col(ColumnDef::new(OrderContracts::Terms).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(OrderContracts::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_order_contracts_order")
+                            .from(OrderContracts::Table, OrderContracts::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_order_contracts_order")
+                    .table(OrderContracts::Table)
+                    .col(OrderContracts::OrderId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OrderContracts::Table).if_exists().to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .drop_column(Orders::QuoteVersion)
+                    .drop_column(Orders::Location)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Id,
+    QuoteVersion,
+    Location,
+}
+
+#[derive(DeriveIden)]
+enum OrderContracts {
+    Table,
+    Id,
+    OrderId,
+    Version,
+    Terms,
+    CreatedAt,
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE demands DROP CONSTRAINT IF EXISTS demands_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE demands ADD CONSTRAINT demands_status_check CHECK (status IN \
+             ('draft', 'open', 'matched', 'closed'))",
+        )
+        .await?;
+        db.execute_unprepared("ALTER TABLE quotes DROP CONSTRAINT IF EXISTS quotes_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE quotes ADD CONSTRAINT quotes_status_check CHECK (status IN \
+             ('pending', 'accepted', 'rejected', 'expired'))",
+        )
+        .await?;
+        // 已有有效订单的需求标记为已成交。
+        db.execute_unprepared(
+            "UPDATE demands SET status = 'matched' WHERE status = 'open' AND EXISTS \
+             (SELECT 1 FROM orders WHERE orders.demand_id = demands.id \
+             AND orders.status <> 'cancelled')",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("UPDATE demands SET status = 'closed' WHERE status = 'matched'")
+            .await?;
+        db.execute_unprepared("UPDATE quotes SET status = 'expired' WHERE status = 'rejected'")
+            .await?;
+        db.execute_unprepared("ALTER TABLE demands DROP CONSTRAINT IF EXISTS demands_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE demands ADD CONSTRAINT demands_status_check CHECK (status IN \
+             ('draft', 'open', 'closed'))",
+        )
+        .await?;
+        db.execute_unprepared("ALTER TABLE quotes DROP CONSTRAINT IF EXISTS quotes_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE quotes ADD CONSTRAINT quotes_status_check CHECK (status IN \
+             ('pending', 'accepted', 'expired'))",
+        )
+        .await?;
+        Ok(())
+    }
+}
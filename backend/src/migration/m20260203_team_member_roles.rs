@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE team_members DROP CONSTRAINT IF EXISTS team_members_role_check",
+        )
+        .await?;
+        db.execute_unprepared(
+            "ALTER TABLE team_members ADD CONSTRAINT team_members_role_check CHECK (role IN \
+             ('owner', 'admin', 'member'))",
+        )
+        .await?;
+        // 团队所有者补录为 owner 成员，成员列表与权限判断统一走 team_members。
+        db.execute_unprepared(
+            "INSERT INTO team_members (team_id, user_id, role) \
+             SELECT id, owner_user_id, 'owner' FROM teams \
+             ON CONFLICT (team_id, user_id) DO UPDATE SET role = 'owner'",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DELETE FROM team_members WHERE role = 'owner'")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE team_members DROP CONSTRAINT IF EXISTS team_members_role_check",
+        )
+        .await?;
+        db.execute_unprepared(
+            "ALTER TABLE team_members ADD CONSTRAINT team_members_role_check CHECK (role IN \
+             ('admin', 'member'))",
+        )
+        .await?;
+        Ok(())
+    }
+}
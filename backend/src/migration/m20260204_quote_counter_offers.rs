@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        // 既有版本均由摄影师提交并已生效。
+        db.execute_unprepared(
+            "ALTER TABLE quote_versions \
+             ADD COLUMN IF NOT EXISTS proposer_role TEXT NOT NULL DEFAULT 'photographer', \
+             ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'adopted'",
+        )
+        .await?;
+        db.execute_unprepared(
+            "ALTER TABLE quote_versions ADD CONSTRAINT quote_versions_proposer_role_check \
+             CHECK (proposer_role IN ('photographer', 'customer'))",
+        )
+        .await?;
+        db.execute_unprepared(
+            "ALTER TABLE quote_versions ADD CONSTRAINT quote_versions_status_check \
+             CHECK (status IN ('proposed', 'adopted', 'declined', 'superseded'))",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE quote_versions \
+             DROP COLUMN IF EXISTS proposer_role, \
+             DROP COLUMN IF EXISTS status",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DemandRevisions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DemandRevisions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(DemandRevisions::DemandId).big_integer().not_null())
+                    .col(ColumnDef::new(DemandRevisions::Revision).integer().not_null())
+                    .col(ColumnDef::new(DemandRevisions::Changes).json_binary().not_null())
+                    .col(ColumnDef::new(DemandRevisions::Snapshot).json_binary().not_null())
+                    .col(ColumnDef::new(DemandRevisions::CreatedBy).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(DemandRevisions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_demand_revisions_demand")
+                            .from(DemandRevisions::Table, DemandRevisions::DemandId)
+                            .to(Demands::Table, Demands::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uniq_demand_revisions_demand_revision")
+                    .table(DemandRevisions::Table)
+                    .col(DemandRevisions::DemandId)
+                    .col(DemandRevisions::Revision)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared("ALTER TABLE quotes DROP CONSTRAINT IF EXISTS quotes_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE quotes ADD CONSTRAINT quotes_status_check CHECK (status IN \
+             ('pending', 'stale', 'accepted', 'rejected', 'expired'))",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("UPDATE quotes SET status = 'pending' WHERE status = 'stale'")
+            .await?;
+        db.execute_unprepared("ALTER TABLE quotes DROP CONSTRAINT IF EXISTS quotes_status_check")
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE quotes ADD CONSTRAINT quotes_status_check CHECK (status IN \
+             ('pending', 'accepted', 'rejected', 'expired'))",
+        )
+        .await?;
+        manager
+            .drop_table(Table::drop().table(DemandRevisions::Table).if_exists().to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Demands {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum DemandRevisions {
+    Table,
+    Id,
+    DemandId,
+    Revision,
+    Changes,
+    Snapshot,
+    CreatedBy,
+    CreatedAt,
+}
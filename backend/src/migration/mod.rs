@@ -3,6 +3,25 @@ use sea_orm_migration::prelude::*;
 mod m20260116_init;
 mod m20260117_merchant_assets;
 mod m20260118_add_username;
+mod m20260119_role_permissions;
+mod m20260120_order_status_history;
+mod m20260121_verification_code_limits;
+mod m20260122_session_refresh;
+mod m20260123_users_ban_permission;
+mod m20260124_users_reset_password_permission;
+mod m20260125_payment_intents;
+mod m20260126_order_payment_plans;
+mod m20260127_configs_manage_permission;
+mod m20260128_refund_workflow;
+mod m20260129_idempotency_keys;
+mod m20260130_ledger;
+mod m20260131_order_refund_policy;
+mod m20260201_order_contracts;
+mod m20260202_quote_matching;
+mod m20260203_team_member_roles;
+mod m20260204_quote_counter_offers;
+mod m20260205_demand_revisions;
+mod m20260207_session_rotated_tokens;
 
 pub struct Migrator;
 
@@ -13,6 +32,25 @@ impl MigratorTrait for Migrator {
             Box::new(m20260116_init::Migration),
             Box::new(m20260117_merchant_assets::Migration),
             Box::new(m20260118_add_username::Migration),
+            Box::new(m20260119_role_permissions::Migration),
+            Box::new(m20260120_order_status_history::Migration),
+            Box::new(m20260121_verification_code_limits::Migration),
+            Box::new(m20260122_session_refresh::Migration),
+            Box::new(m20260123_users_ban_permission::Migration),
+            Box::new(m20260124_users_reset_password_permission::Migration),
+            Box::new(m20260125_payment_intents::Migration),
+            Box::new(m20260126_order_payment_plans::Migration),
+            Box::new(m20260127_configs_manage_permission::Migration),
+            Box::new(m20260128_refund_workflow::Migration),
+            Box::new(m20260129_idempotency_keys::Migration),
+            Box::new(m20260130_ledger::Migration),
+            Box::new(m20260131_order_refund_policy::Migration),
+            Box::new(m20260201_order_contracts::Migration),
+            Box::new(m20260202_quote_matching::Migration),
+            Box::new(m20260203_team_member_roles::Migration),
+            Box::new(m20260204_quote_counter_offers::Migration),
+            Box::new(m20260205_demand_revisions::Migration),
+            Box::new(m20260207_session_rotated_tokens::Migration),
         ]
     }
 }
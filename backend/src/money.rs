@@ -0,0 +1,227 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// 金额统一保留两位小数（分）。
+pub const MONEY_SCALE: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum MoneyError {
+    #[error("invalid_amount")]
+    Invalid,
+    #[error("amount_precision_exceeded")]
+    TooPrecise,
+}
+
+/// 精确金额，内部为 `Decimal`，序列化为两位小数的字符串（如 `"800.00"`）。
+///
+/// 舍入规则：
+/// - 请求输入超过两位小数直接拒绝，不做静默舍入；
+/// - 计算结果（比例、费率）按四舍五入（远离零）保留到分，见 [`Money::mul_ratio`]。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(Decimal);
+
+impl Money {
+    pub const ZERO: Money = Money(Decimal::from_parts(0, 0, 0, false, MONEY_SCALE));
+
+    /// 从数据库或计算结果构造，超出两位的部分四舍五入。
+    pub fn from_decimal(value: Decimal) -> Self {
+        Money(round(value))
+    }
+
+    /// 严格构造：超过两位小数返回错误，用于外部输入。
+    pub fn try_from_decimal(value: Decimal) -> Result<Self, MoneyError> {
+        if value.normalize().scale() > MONEY_SCALE {
+            return Err(MoneyError::TooPrecise);
+        }
+        Ok(Money(round(value)))
+    }
+
+    pub fn amount(self) -> Decimal {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > Decimal::ZERO
+    }
+
+    /// 按比例计算金额（退款比例、服务费率、分期比例），结果四舍五入到分。
+    pub fn mul_ratio(self, ratio: Decimal) -> Self {
+        Money(round(self.0 * ratio))
+    }
+
+    /// 差额不足时取零，用于余额类计算。
+    pub fn saturating_sub(self, other: Money) -> Self {
+        if self > other { self - other } else { Money::ZERO }
+    }
+}
+
+impl Default for Money {
+    fn default() -> Self {
+        Money::ZERO
+    }
+}
+
+fn round(value: Decimal) -> Decimal {
+    let mut rounded =
+        value.round_dp_with_strategy(MONEY_SCALE, RoundingStrategy::MidpointAwayFromZero);
+    rounded.rescale(MONEY_SCALE);
+    rounded
+}
+
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = Decimal::from_str(s.trim()).map_err(|_| MoneyError::Invalid)?;
+        Money::try_from_decimal(value)
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(value: Decimal) -> Self {
+        Money::from_decimal(value)
+    }
+}
+
+impl From<Money> for Decimal {
+    fn from(value: Money) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        Money(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Money) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        Money(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Money) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+struct MoneyVisitor;
+
+impl Visitor<'_> for MoneyVisitor {
+    type Value = Money;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a decimal amount with at most two fractional digits")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Money, E> {
+        Money::from_str(v).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Money, E> {
+        Ok(Money::from_decimal(Decimal::from(v)))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Money, E> {
+        Ok(Money::from_decimal(Decimal::from(v)))
+    }
+
+    // 兼容旧客户端以 JSON number 传金额：取 f64 的最短十进制表示再按字符串解析。
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Money, E> {
+        if !v.is_finite() {
+            return Err(E::custom(MoneyError::Invalid));
+        }
+        Money::from_str(&v.to_string()).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(s: &str) -> Money {
+        Money::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_money_serializes_as_two_decimal_string() {
+        assert_eq!(serde_json::to_string(&money("800")).unwrap(), "\"800.00\"");
+        assert_eq!(serde_json::to_string(&money("0.1")).unwrap(), "\"0.10\"");
+        let parsed: Money = serde_json::from_str("\"12.34\"").unwrap();
+        assert_eq!(parsed, money("12.34"));
+        let parsed: Money = serde_json::from_str("0.3").unwrap();
+        assert_eq!(parsed, money("0.30"));
+        assert!(serde_json::from_str::<Money>("\"1.001\"").is_err());
+        assert!(serde_json::from_str::<Money>("\"abc\"").is_err());
+    }
+
+    #[test]
+    fn test_money_sums_reconcile_to_the_cent() {
+        let total: Money = std::iter::repeat_n(money("0.10"), 3).sum();
+        assert_eq!(total, money("0.30"));
+        assert_eq!(money("100.00").mul_ratio(Decimal::new(3333, 4)), money("33.33"));
+        assert_eq!(money("0.05").mul_ratio(Decimal::new(5, 1)), money("0.03"));
+        assert_eq!(money("10").saturating_sub(money("12")), Money::ZERO);
+        assert_eq!(Money::ZERO.to_string(), "0.00");
+    }
+}
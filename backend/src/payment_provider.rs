@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context};
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+pub const CHANNELS: &[&str] = &["wx", "alipay", "bank"];
+
+#[derive(Clone, Debug)]
+pub struct IntentRequest {
+    pub payment_id: i64,
+    pub order_id: i64,
+    pub amount: Decimal,
+    pub description: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PaymentIntent {
+    pub provider_ref: String,
+    pub pay_params: serde_json::Value,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallbackStatus {
+    Success,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CallbackEvent {
+    pub provider_ref: String,
+    pub amount: Decimal,
+    pub status: CallbackStatus,
+}
+
+#[async_trait::async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Registers a pending charge with the provider and returns what the client
+    /// needs to complete it (redirect url, SDK params, bank transfer reference).
+    async fn create_intent(&self, req: &IntentRequest) -> anyhow::Result<PaymentIntent>;
+
+    /// Authenticates an asynchronous notification. Must fail for any body whose
+    /// signature does not verify; the caller never trusts unverified callbacks.
+    fn verify_callback(&self, headers: &HeaderMap, body: &[u8]) -> anyhow::Result<CallbackEvent>;
+
+    /// Whether payments on this channel are settled by the payee confirming
+    /// receipt instead of by a provider callback.
+    fn settles_manually(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct PaymentGateway {
+    providers: HashMap<String, Arc<dyn PaymentProvider>>,
+}
+
+impl PaymentGateway {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, channel: &str, provider: Arc<dyn PaymentProvider>) -> Self {
+        self.providers.insert(channel.to_string(), provider);
+        self
+    }
+
+    pub fn provider(&self, channel: &str) -> Option<Arc<dyn PaymentProvider>> {
+        self.providers.get(channel).cloned()
+    }
+}
+
+/// Builds the gateway selected by `PAYMENT_PROVIDER`, which must be set explicitly:
+/// `manual` (offline transfer confirmed by the payee) or `mock` (only with `APP_ENV=dev`
+/// and a `PAYMENT_MOCK_SECRET`). Real channel providers register here as they are integrated.
+pub fn payment_gateway_from_env() -> anyhow::Result<PaymentGateway> {
+    let provider = env::var("PAYMENT_PROVIDER").context("PAYMENT_PROVIDER is required")?;
+    let app_env = env::var("APP_ENV").unwrap_or_default();
+    build_gateway(provider.trim(), app_env.trim(), env::var("PAYMENT_MOCK_SECRET").ok())
+}
+
+fn build_gateway(
+    provider: &str,
+    app_env: &str,
+    mock_secret: Option<String>,
+) -> anyhow::Result<PaymentGateway> {
+    let mut gateway = PaymentGateway::new();
+    match provider {
+        "manual" => {
+            for channel in CHANNELS {
+                gateway = gateway.register(channel, Arc::new(ManualPaymentProvider::new(channel)));
+            }
+        }
+        "mock" => {
+            if app_env != "dev" {
+                bail!("PAYMENT_PROVIDER=mock is only allowed with APP_ENV=dev");
+            }
+            let secret = mock_secret
+                .filter(|s| !s.trim().is_empty())
+                .ok_or_else(|| anyhow!("PAYMENT_MOCK_SECRET is required for the mock provider"))?;
+            for channel in CHANNELS {
+                gateway = gateway.register(
+                    channel,
+                    Arc::new(MockPaymentProvider::new(channel, secret.clone())),
+                );
+            }
+        }
+        other => bail!("unsupported PAYMENT_PROVIDER: {other}"),
+    }
+    Ok(gateway)
+}
+
+/// Offline settlement: the payer transfers outside the platform and uploads a
+/// proof, then the payee confirms receipt. No callback is ever accepted.
+pub struct ManualPaymentProvider {
+    channel: String,
+}
+
+impl ManualPaymentProvider {
+    pub fn new(channel: &str) -> Self {
+        Self {
+            channel: channel.to_string(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentProvider for ManualPaymentProvider {
+    async fn create_intent(&self, req: &IntentRequest) -> anyhow::Result<PaymentIntent> {
+        let provider_ref = format!("manual_{}_{}", self.channel, req.payment_id);
+        Ok(PaymentIntent {
+            pay_params: serde_json::json!({
+                "method": "manual",
+                "reference": provider_ref,
+                "amount": req.amount.to_string(),
+                "order_id": req.order_id,
+                "description": req.description,
+            }),
+            provider_ref,
+        })
+    }
+
+    fn verify_callback(&self, _headers: &HeaderMap, _body: &[u8]) -> anyhow::Result<CallbackEvent> {
+        bail!("manual channel does not accept callbacks")
+    }
+
+    fn settles_manually(&self) -> bool {
+        true
+    }
+}
+
+pub const MOCK_SIGNATURE_HEADER: &str = "x-mock-signature";
+
+/// Deterministic provider for local development and tests: intent refs are
+/// derived from the payment id and callbacks are the JSON `CallbackEvent`
+/// signed with HMAC-SHA256 over the raw body.
+pub struct MockPaymentProvider {
+    channel: String,
+    secret: String,
+}
+
+impl MockPaymentProvider {
+    pub fn new(channel: &str, secret: String) -> Self {
+        Self {
+            channel: channel.to_string(),
+            secret,
+        }
+    }
+
+    pub fn sign(&self, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("hmac accepts keys of any length");
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentProvider for MockPaymentProvider {
+    async fn create_intent(&self, req: &IntentRequest) -> anyhow::Result<PaymentIntent> {
+        let provider_ref = format!("mock_{}_{}", self.channel, req.payment_id);
+        Ok(PaymentIntent {
+            pay_params: serde_json::json!({
+                "pay_url": format!("mock://pay/{provider_ref}"),
+                "amount": req.amount.to_string(),
+                "order_id": req.order_id,
+                "description": req.description,
+            }),
+            provider_ref,
+        })
+    }
+
+    fn verify_callback(&self, headers: &HeaderMap, body: &[u8]) -> anyhow::Result<CallbackEvent> {
+        let signature = headers
+            .get(MOCK_SIGNATURE_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| anyhow!("missing signature"))?;
+        let signature = hex::decode(signature).map_err(|_| anyhow!("malformed signature"))?;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("hmac accepts keys of any length");
+        mac.update(body);
+        mac.verify_slice(&signature)
+            .map_err(|_| anyhow!("invalid signature"))?;
+
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn callback_body() -> Vec<u8> {
+        serde_json::to_vec(&CallbackEvent {
+            provider_ref: "mock_wx_1".to_string(),
+            amount: Decimal::new(80000, 2),
+            status: CallbackStatus::Success,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mock_intent_is_deterministic() {
+        let provider = MockPaymentProvider::new("wx", "secret".to_string());
+        let req = IntentRequest {
+            payment_id: 1,
+            order_id: 2,
+            amount: Decimal::new(80000, 2),
+            description: "order 2".to_string(),
+        };
+        let a = provider.create_intent(&req).await.unwrap();
+        let b = provider.create_intent(&req).await.unwrap();
+        assert_eq!(a.provider_ref, "mock_wx_1");
+        assert_eq!(a.provider_ref, b.provider_ref);
+    }
+
+    #[test]
+    fn test_mock_callback_signature() {
+        let provider = MockPaymentProvider::new("wx", "secret".to_string());
+        let body = callback_body();
+        let mut headers = HeaderMap::new();
+        headers.insert(MOCK_SIGNATURE_HEADER, provider.sign(&body).parse().unwrap());
+        let event = provider.verify_callback(&headers, &body).unwrap();
+        assert_eq!(event.provider_ref, "mock_wx_1");
+        assert_eq!(event.status, CallbackStatus::Success);
+
+        let mut tampered = body.clone();
+        tampered[5] ^= 1;
+        assert!(provider.verify_callback(&headers, &tampered).is_err());
+        assert!(provider.verify_callback(&HeaderMap::new(), &body).is_err());
+
+        let other = MockPaymentProvider::new("wx", "other".to_string());
+        assert!(other.verify_callback(&headers, &body).is_err());
+    }
+
+    #[test]
+    fn test_build_gateway() {
+        let secret = Some("secret".to_string());
+        assert!(build_gateway("mock", "prod", secret.clone()).is_err());
+        assert!(build_gateway("mock", "dev", None).is_err());
+        assert!(build_gateway("mock", "dev", secret.clone()).is_ok());
+        assert!(build_gateway("stripe", "dev", secret).is_err());
+
+        let manual = build_gateway("manual", "", None).unwrap();
+        let provider = manual.provider("bank").unwrap();
+        assert!(provider.settles_manually());
+        assert!(provider.verify_callback(&HeaderMap::new(), &callback_body()).is_err());
+    }
+}
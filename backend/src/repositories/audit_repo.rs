@@ -0,0 +1,22 @@
+use sea_orm::{ActiveModelTrait, ConnectionTrait, Set};
+
+use crate::entity::audit_logs;
+
+pub async fn create_audit_log<C: ConnectionTrait>(
+    orm: &C,
+    admin_id: i64,
+    action: &str,
+    target_type: &str,
+    target_id: i64,
+    detail: serde_json::Value,
+) -> anyhow::Result<audit_logs::Model> {
+    let model = audit_logs::ActiveModel {
+        admin_id: Set(admin_id),
+        action: Set(action.to_string()),
+        target_type: Set(Some(target_type.to_string())),
+        target_id: Set(Some(target_id)),
+        detail: Set(Some(detail)),
+        ..Default::default()
+    };
+    Ok(model.insert(orm).await?)
+}
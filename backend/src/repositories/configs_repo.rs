@@ -1,9 +1,12 @@
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    Set,
+};
 
 use crate::entity::configs;
 
-pub async fn find_config_by_key(
-    orm: &DatabaseConnection,
+pub async fn find_config_by_key<C: ConnectionTrait>(
+    orm: &C,
     key: &str,
 ) -> anyhow::Result<Option<configs::Model>> {
     Ok(configs::Entity::find()
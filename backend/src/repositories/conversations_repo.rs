@@ -82,15 +82,6 @@ pub async fn find_order_by_id(
     Ok(orders::Entity::find_by_id(order_id).one(orm).await?)
 }
 
-pub async fn find_photographer_by_id(
-    orm: &DatabaseConnection,
-    photographer_id: i64,
-) -> anyhow::Result<Option<photographers::Model>> {
-    Ok(photographers::Entity::find_by_id(photographer_id)
-        .one(orm)
-        .await?)
-}
-
 pub async fn list_order_ids_by_user(
     orm: &DatabaseConnection,
     user_id: i64,
@@ -126,3 +117,16 @@ pub async fn list_order_ids_by_photographer(
         .all(orm)
         .await?)
 }
+
+pub async fn list_order_ids_by_teams(
+    orm: &DatabaseConnection,
+    team_ids: Vec<i64>,
+) -> anyhow::Result<Vec<i64>> {
+    Ok(orders::Entity::find()
+        .select_only()
+        .column(orders::Column::Id)
+        .filter(orders::Column::TeamId.is_in(team_ids))
+        .into_tuple::<i64>()
+        .all(orm)
+        .await?)
+}
@@ -1,10 +1,10 @@
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
 };
 use sea_orm::prelude::Expr;
 
-use crate::entity::{demand_attachments, demands};
+use crate::entity::{demand_attachments, demand_revisions, demands};
 
 pub struct DemandListFilter {
     pub city_id: Option<i64>,
@@ -27,8 +27,8 @@ pub async fn create_demand(
     Ok(model.insert(orm).await?)
 }
 
-pub async fn create_attachment(
-    orm: &DatabaseConnection,
+pub async fn create_attachment<C: ConnectionTrait>(
+    orm: &C,
     model: demand_attachments::ActiveModel,
 ) -> anyhow::Result<demand_attachments::Model> {
     Ok(model.insert(orm).await?)
@@ -117,8 +117,8 @@ pub async fn find_demand_by_id(
     Ok(demands::Entity::find_by_id(id).one(orm).await?)
 }
 
-pub async fn list_attachments_by_demand(
-    orm: &DatabaseConnection,
+pub async fn list_attachments_by_demand<C: ConnectionTrait>(
+    orm: &C,
     demand_id: i64,
 ) -> anyhow::Result<Vec<demand_attachments::Model>> {
     Ok(demand_attachments::Entity::find()
@@ -127,8 +127,8 @@ pub async fn list_attachments_by_demand(
         .await?)
 }
 
-pub async fn update_demand_status(
-    orm: &DatabaseConnection,
+pub async fn update_demand_status<C: ConnectionTrait>(
+    orm: &C,
     demand: demands::Model,
     status: String,
 ) -> anyhow::Result<demands::Model> {
@@ -136,3 +136,71 @@ pub async fn update_demand_status(
     model.status = Set(status);
     Ok(model.update(orm).await?)
 }
+
+pub async fn update_demand<C: ConnectionTrait>(
+    orm: &C,
+    model: demands::ActiveModel,
+) -> anyhow::Result<demands::Model> {
+    Ok(model.update(orm).await?)
+}
+
+pub async fn delete_attachments_by_demand<C: ConnectionTrait>(
+    orm: &C,
+    demand_id: i64,
+) -> anyhow::Result<()> {
+    demand_attachments::Entity::delete_many()
+        .filter(demand_attachments::Column::DemandId.eq(demand_id))
+        .exec(orm)
+        .await?;
+    Ok(())
+}
+
+pub async fn next_revision<C: ConnectionTrait>(orm: &C, demand_id: i64) -> anyhow::Result<i32> {
+    let latest: Option<Option<i32>> = demand_revisions::Entity::find()
+        .select_only()
+        .column_as(demand_revisions::Column::Revision.max(), "revision")
+        .filter(demand_revisions::Column::DemandId.eq(demand_id))
+        .into_tuple()
+        .one(orm)
+        .await?;
+    Ok(latest.flatten().unwrap_or(0) + 1)
+}
+
+pub async fn create_revision<C: ConnectionTrait>(
+    orm: &C,
+    model: demand_revisions::ActiveModel,
+) -> anyhow::Result<demand_revisions::Model> {
+    Ok(model.insert(orm).await?)
+}
+
+pub async fn list_revisions(
+    orm: &DatabaseConnection,
+    demand_id: i64,
+) -> anyhow::Result<Vec<demand_revisions::Model>> {
+    Ok(demand_revisions::Entity::find()
+        .filter(demand_revisions::Column::DemandId.eq(demand_id))
+        .order_by_desc(demand_revisions::Column::Revision)
+        .all(orm)
+        .await?)
+}
+
+/// 关闭拍摄时间已过的 open 需求（无结束时间时以开始时间为准），返回本次被关闭的需求。
+pub async fn close_stale_demands<C: ConnectionTrait>(
+    orm: &C,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Vec<demands::Model>> {
+    let passed = Condition::any()
+        .add(demands::Column::ScheduleEnd.lte(now))
+        .add(
+            Condition::all()
+                .add(demands::Column::ScheduleEnd.is_null())
+                .add(demands::Column::ScheduleStart.lte(now)),
+        );
+    Ok(demands::Entity::update_many()
+        .col_expr(demands::Column::Status, Expr::value("closed"))
+        .col_expr(demands::Column::UpdatedAt, Expr::value(now))
+        .filter(demands::Column::Status.eq("open"))
+        .filter(passed)
+        .exec_with_returning(orm)
+        .await?)
+}
@@ -0,0 +1,96 @@
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter,
+    TryInsertResult,
+};
+
+use crate::entity::idempotency_keys;
+
+pub async fn find_key<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    key: &str,
+) -> anyhow::Result<Option<idempotency_keys::Model>> {
+    Ok(idempotency_keys::Entity::find()
+        .filter(idempotency_keys::Column::UserId.eq(user_id))
+        .filter(idempotency_keys::Column::Key.eq(key))
+        .one(orm)
+        .await?)
+}
+
+/// 占用幂等键，返回新记录 id；键已存在时不写入并返回 None。
+pub async fn try_claim_key<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    key: &str,
+    request_hash: &str,
+    expires_at: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Option<i64>> {
+    let model = idempotency_keys::ActiveModel {
+        user_id: Set(user_id),
+        key: Set(key.to_string()),
+        request_hash: Set(request_hash.to_string()),
+        expires_at: Set(expires_at.into()),
+        ..Default::default()
+    };
+    let result = idempotency_keys::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([idempotency_keys::Column::UserId, idempotency_keys::Column::Key])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(orm)
+        .await?;
+    Ok(match result {
+        TryInsertResult::Inserted(inserted) => Some(inserted.last_insert_id),
+        TryInsertResult::Empty | TryInsertResult::Conflicted => None,
+    })
+}
+
+pub async fn save_response<C: ConnectionTrait>(
+    orm: &C,
+    id: i64,
+    status_code: i32,
+    content_type: Option<String>,
+    body: Vec<u8>,
+) -> anyhow::Result<()> {
+    idempotency_keys::Entity::update_many()
+        .col_expr(idempotency_keys::Column::StatusCode, Expr::value(status_code))
+        .col_expr(idempotency_keys::Column::ContentType, Expr::value(content_type))
+        .col_expr(idempotency_keys::Column::ResponseBody, Expr::value(body))
+        .filter(idempotency_keys::Column::Id.eq(id))
+        .exec(orm)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_key<C: ConnectionTrait>(orm: &C, id: i64) -> anyhow::Result<()> {
+    idempotency_keys::Entity::delete_by_id(id).exec(orm).await?;
+    Ok(())
+}
+
+/// 清理已过期的键，以及处理中但超过 `lock_before` 仍未写入响应的键（进程中断遗留）。
+pub async fn delete_stale_key<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    key: &str,
+    now: chrono::DateTime<chrono::Utc>,
+    lock_before: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<()> {
+    idempotency_keys::Entity::delete_many()
+        .filter(idempotency_keys::Column::UserId.eq(user_id))
+        .filter(idempotency_keys::Column::Key.eq(key))
+        .filter(
+            Condition::any()
+                .add(idempotency_keys::Column::ExpiresAt.lte(now))
+                .add(
+                    Condition::all()
+                        .add(idempotency_keys::Column::StatusCode.is_null())
+                        .add(idempotency_keys::Column::CreatedAt.lte(lock_before)),
+                ),
+        )
+        .exec(orm)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    prelude::Decimal, sea_query::OnConflict, ActiveModelTrait, ActiveValue::Set, ColumnTrait,
+    ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    TryInsertResult,
+};
+
+use crate::entity::{ledger_accounts, ledger_entries, ledger_lines};
+
+/// 取账户，不存在时创建（并发创建由唯一索引兜底）。
+pub async fn ensure_account<C: ConnectionTrait>(
+    orm: &C,
+    owner_type: &str,
+    owner_id: i64,
+) -> anyhow::Result<ledger_accounts::Model> {
+    let model = ledger_accounts::ActiveModel {
+        owner_type: Set(owner_type.to_string()),
+        owner_id: Set(owner_id),
+        ..Default::default()
+    };
+    ledger_accounts::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([
+                ledger_accounts::Column::OwnerType,
+                ledger_accounts::Column::OwnerId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec(orm)
+        .await?;
+    find_account(orm, owner_type, owner_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("ledger account missing after insert"))
+}
+
+pub async fn find_account<C: ConnectionTrait>(
+    orm: &C,
+    owner_type: &str,
+    owner_id: i64,
+) -> anyhow::Result<Option<ledger_accounts::Model>> {
+    Ok(ledger_accounts::Entity::find()
+        .filter(ledger_accounts::Column::OwnerType.eq(owner_type))
+        .filter(ledger_accounts::Column::OwnerId.eq(owner_id))
+        .one(orm)
+        .await?)
+}
+
+pub async fn find_account_by_id<C: ConnectionTrait>(
+    orm: &C,
+    account_id: i64,
+) -> anyhow::Result<Option<ledger_accounts::Model>> {
+    Ok(ledger_accounts::Entity::find_by_id(account_id).one(orm).await?)
+}
+
+pub async fn list_accounts<C: ConnectionTrait>(
+    orm: &C,
+    owner_type: Option<String>,
+    owner_id: Option<i64>,
+    page: u64,
+    page_size: u64,
+) -> anyhow::Result<(Vec<ledger_accounts::Model>, u64)> {
+    let offset = (page - 1) * page_size;
+    let mut query = ledger_accounts::Entity::find();
+    if let Some(owner_type) = owner_type {
+        query = query.filter(ledger_accounts::Column::OwnerType.eq(owner_type));
+    }
+    if let Some(owner_id) = owner_id {
+        query = query.filter(ledger_accounts::Column::OwnerId.eq(owner_id));
+    }
+    let total = query.clone().count(orm).await?;
+    let rows = query
+        .order_by_asc(ledger_accounts::Column::Id)
+        .limit(page_size)
+        .offset(offset)
+        .all(orm)
+        .await?;
+    Ok((rows, total))
+}
+
+/// 写入分录头；同一业务引用已记账时不写入并返回 None。
+pub async fn try_insert_entry<C: ConnectionTrait>(
+    orm: &C,
+    entry: ledger_entries::ActiveModel,
+) -> anyhow::Result<Option<i64>> {
+    let result = ledger_entries::Entity::insert(entry)
+        .on_conflict(
+            OnConflict::columns([
+                ledger_entries::Column::EntryType,
+                ledger_entries::Column::ReferenceType,
+                ledger_entries::Column::ReferenceId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec(orm)
+        .await?;
+    Ok(match result {
+        TryInsertResult::Inserted(inserted) => Some(inserted.last_insert_id),
+        TryInsertResult::Empty | TryInsertResult::Conflicted => None,
+    })
+}
+
+pub async fn create_line<C: ConnectionTrait>(
+    orm: &C,
+    line: ledger_lines::ActiveModel,
+) -> anyhow::Result<ledger_lines::Model> {
+    Ok(line.insert(orm).await?)
+}
+
+pub async fn find_entry_by_reference<C: ConnectionTrait>(
+    orm: &C,
+    entry_type: &str,
+    reference_type: &str,
+    reference_id: i64,
+) -> anyhow::Result<Option<ledger_entries::Model>> {
+    Ok(ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::EntryType.eq(entry_type))
+        .filter(ledger_entries::Column::ReferenceType.eq(reference_type))
+        .filter(ledger_entries::Column::ReferenceId.eq(reference_id))
+        .one(orm)
+        .await?)
+}
+
+pub async fn list_entries_by_references<C: ConnectionTrait>(
+    orm: &C,
+    entry_type: &str,
+    reference_type: &str,
+    reference_ids: Vec<i64>,
+) -> anyhow::Result<Vec<ledger_entries::Model>> {
+    if reference_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::EntryType.eq(entry_type))
+        .filter(ledger_entries::Column::ReferenceType.eq(reference_type))
+        .filter(ledger_entries::Column::ReferenceId.is_in(reference_ids))
+        .all(orm)
+        .await?)
+}
+
+pub async fn list_entries_by_ids<C: ConnectionTrait>(
+    orm: &C,
+    entry_ids: Vec<i64>,
+) -> anyhow::Result<Vec<ledger_entries::Model>> {
+    if entry_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(ledger_entries::Entity::find()
+        .filter(ledger_entries::Column::Id.is_in(entry_ids))
+        .all(orm)
+        .await?)
+}
+
+pub async fn list_lines_by_entries<C: ConnectionTrait>(
+    orm: &C,
+    entry_ids: Vec<i64>,
+) -> anyhow::Result<Vec<ledger_lines::Model>> {
+    if entry_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(ledger_lines::Entity::find()
+        .filter(ledger_lines::Column::EntryId.is_in(entry_ids))
+        .order_by_asc(ledger_lines::Column::Id)
+        .all(orm)
+        .await?)
+}
+
+pub async fn sum_balances<C: ConnectionTrait>(
+    orm: &C,
+    account_ids: Vec<i64>,
+) -> anyhow::Result<HashMap<i64, Decimal>> {
+    if account_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows: Vec<(i64, Option<Decimal>)> = ledger_lines::Entity::find()
+        .select_only()
+        .column(ledger_lines::Column::AccountId)
+        .column_as(ledger_lines::Column::Amount.sum(), "balance")
+        .filter(ledger_lines::Column::AccountId.is_in(account_ids))
+        .group_by(ledger_lines::Column::AccountId)
+        .into_tuple()
+        .all(orm)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, balance)| (id, balance.unwrap_or(Decimal::ZERO)))
+        .collect())
+}
+
+/// 账户在 `before` 之前（不含）的余额，`before` 为空时为当前余额。
+pub async fn sum_account_before<C: ConnectionTrait>(
+    orm: &C,
+    account_id: i64,
+    before: Option<chrono::DateTime<chrono::Utc>>,
+) -> anyhow::Result<Decimal> {
+    let mut query = ledger_lines::Entity::find()
+        .select_only()
+        .column_as(ledger_lines::Column::Amount.sum(), "balance")
+        .filter(ledger_lines::Column::AccountId.eq(account_id));
+    if let Some(before) = before {
+        query = query.filter(ledger_lines::Column::CreatedAt.lt(before));
+    }
+    let balance: Option<Option<Decimal>> = query.into_tuple().one(orm).await?;
+    Ok(balance.flatten().unwrap_or(Decimal::ZERO))
+}
+
+pub async fn list_account_lines<C: ConnectionTrait>(
+    orm: &C,
+    account_id: i64,
+    start: Option<chrono::DateTime<chrono::Utc>>,
+    end: Option<chrono::DateTime<chrono::Utc>>,
+    page: u64,
+    page_size: u64,
+) -> anyhow::Result<(Vec<ledger_lines::Model>, u64)> {
+    let offset = (page - 1) * page_size;
+    let mut query =
+        ledger_lines::Entity::find().filter(ledger_lines::Column::AccountId.eq(account_id));
+    if let Some(start) = start {
+        query = query.filter(ledger_lines::Column::CreatedAt.gte(start));
+    }
+    if let Some(end) = end {
+        query = query.filter(ledger_lines::Column::CreatedAt.lt(end));
+    }
+    let total = query.clone().count(orm).await?;
+    let rows = query
+        .order_by_asc(ledger_lines::Column::CreatedAt)
+        .order_by_asc(ledger_lines::Column::Id)
+        .limit(page_size)
+        .offset(offset)
+        .all(orm)
+        .await?;
+    Ok((rows, total))
+}
+
+/// 不分页地列出账户在区间内的全部明细行。
+pub async fn list_account_lines_between<C: ConnectionTrait>(
+    orm: &C,
+    account_id: i64,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Vec<ledger_lines::Model>> {
+    Ok(ledger_lines::Entity::find()
+        .filter(ledger_lines::Column::AccountId.eq(account_id))
+        .filter(ledger_lines::Column::CreatedAt.gte(start))
+        .filter(ledger_lines::Column::CreatedAt.lt(end))
+        .order_by_asc(ledger_lines::Column::CreatedAt)
+        .order_by_asc(ledger_lines::Column::Id)
+        .all(orm)
+        .await?)
+}
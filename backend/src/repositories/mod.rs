@@ -1,9 +1,16 @@
+pub mod audit_repo;
 pub mod configs_repo;
 pub mod demands_repo;
 pub mod conversations_repo;
+pub mod idempotency_repo;
+pub mod ledger_repo;
 pub mod merchants_repo;
 pub mod messages_repo;
 pub mod notifications_repo;
 pub mod orders_repo;
 pub mod quotes_repo;
+pub mod refunds_repo;
+pub mod roles_repo;
+pub mod sessions_repo;
 pub mod teams_repo;
+pub mod users_repo;
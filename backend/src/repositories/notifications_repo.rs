@@ -1,4 +1,7 @@
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
 use sea_orm::sea_query::Expr;
 
 use crate::entity::notifications;
@@ -78,3 +81,20 @@ pub async fn update_notification_read(
     }
     Ok(model.update(orm).await?)
 }
+
+pub async fn create_notification<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    r#type: &str,
+    title: String,
+    content: Option<String>,
+) -> anyhow::Result<notifications::Model> {
+    let model = notifications::ActiveModel {
+        user_id: Set(user_id),
+        r#type: Set(r#type.to_string()),
+        title: Set(title),
+        content: Set(content),
+        ..Default::default()
+    };
+    Ok(model.insert(orm).await?)
+}
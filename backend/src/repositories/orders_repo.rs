@@ -1,9 +1,12 @@
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set,
 };
 
-use crate::entity::{order_items, orders, payments, photographers, refunds};
+use crate::entity::{
+    order_contracts, order_items, order_payment_plans, order_status_history, orders, payments,
+    photographers, refunds,
+};
 
 pub struct OrderListFilter {
     pub status: Option<String>,
@@ -68,6 +71,16 @@ pub async fn find_order_by_id<C: ConnectionTrait>(
     Ok(orders::Entity::find_by_id(order_id).one(orm).await?)
 }
 
+pub async fn find_order_for_update<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+) -> anyhow::Result<Option<orders::Model>> {
+    Ok(orders::Entity::find_by_id(order_id)
+        .lock_exclusive()
+        .one(orm)
+        .await?)
+}
+
 pub async fn list_order_items<C: ConnectionTrait>(
     orm: &C,
     order_id: i64,
@@ -78,6 +91,38 @@ pub async fn list_order_items<C: ConnectionTrait>(
         .await?)
 }
 
+pub async fn create_order_item<C: ConnectionTrait>(
+    orm: &C,
+    model: order_items::ActiveModel,
+) -> anyhow::Result<order_items::Model> {
+    Ok(model.insert(orm).await?)
+}
+
+pub async fn create_contract<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+    version: i32,
+    terms: serde_json::Value,
+) -> anyhow::Result<order_contracts::Model> {
+    let model = order_contracts::ActiveModel {
+        order_id: Set(order_id),
+        version: Set(version),
+        terms: Set(terms),
+        ..Default::default()
+    };
+    Ok(model.insert(orm).await?)
+}
+
+pub async fn find_contract_by_order<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+) -> anyhow::Result<Option<order_contracts::Model>> {
+    Ok(order_contracts::Entity::find()
+        .filter(order_contracts::Column::OrderId.eq(order_id))
+        .one(orm)
+        .await?)
+}
+
 pub async fn list_success_payments<C: ConnectionTrait>(
     orm: &C,
     order_id: i64,
@@ -89,6 +134,67 @@ pub async fn list_success_payments<C: ConnectionTrait>(
         .await?)
 }
 
+/// 按到账时间区间列出成功支付，用于对账。
+pub async fn list_success_payments_paid_between<C: ConnectionTrait>(
+    orm: &C,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Vec<payments::Model>> {
+    Ok(payments::Entity::find()
+        .filter(payments::Column::Status.eq("success"))
+        .filter(payments::Column::PaidAt.gte(start))
+        .filter(payments::Column::PaidAt.lt(end))
+        .order_by_asc(payments::Column::Id)
+        .all(orm)
+        .await?)
+}
+
+/// 按到账时间区间列出某收款人的成功支付。
+/// 承接方的订单：个人摄影师承接的订单，或由指定团队承接的订单（个人优先，与账本一致）。
+pub fn provider_orders_condition(photographer_id: Option<i64>, team_ids: &[i64]) -> Condition {
+    Condition::any()
+        .add_option(photographer_id.map(|id| orders::Column::PhotographerId.eq(id)))
+        .add(
+            Condition::all()
+                .add(orders::Column::PhotographerId.is_null())
+                .add(orders::Column::TeamId.is_in(team_ids.to_vec())),
+        )
+}
+
+pub async fn list_success_payments_by_provider<C: ConnectionTrait>(
+    orm: &C,
+    photographer_id: Option<i64>,
+    team_ids: &[i64],
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Vec<payments::Model>> {
+    let order_ids = orders::Entity::find()
+        .select_only()
+        .column(orders::Column::Id)
+        .filter(provider_orders_condition(photographer_id, team_ids))
+        .into_query();
+    Ok(payments::Entity::find()
+        .filter(payments::Column::OrderId.in_subquery(order_ids))
+        .filter(payments::Column::Status.eq("success"))
+        .filter(payments::Column::PaidAt.gte(start))
+        .filter(payments::Column::PaidAt.lt(end))
+        .order_by_asc(payments::Column::PaidAt)
+        .order_by_asc(payments::Column::Id)
+        .all(orm)
+        .await?)
+}
+
+pub async fn list_payments<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+) -> anyhow::Result<Vec<payments::Model>> {
+    Ok(payments::Entity::find()
+        .filter(payments::Column::OrderId.eq(order_id))
+        .order_by_asc(payments::Column::Id)
+        .all(orm)
+        .await?)
+}
+
 pub async fn find_photographer_user_id<C: ConnectionTrait>(
     orm: &C,
     photographer_id: i64,
@@ -114,6 +220,7 @@ pub async fn update_order_status_cancelled<C: ConnectionTrait>(
     let mut model: orders::ActiveModel = order.into();
     model.status = Set("cancelled".to_string());
     model.cancelled_at = Set(Some(cancelled_at.into()));
+    model.updated_at = Set(cancelled_at.into());
     Ok(model.update(orm).await?)
 }
 
@@ -124,12 +231,108 @@ pub async fn update_order_status<C: ConnectionTrait>(
 ) -> anyhow::Result<orders::Model> {
     let mut model: orders::ActiveModel = order.into();
     model.status = Set(status);
+    model.updated_at = Set(chrono::Utc::now().into());
     Ok(model.update(orm).await?)
 }
 
+pub async fn create_status_history<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+    from_status: Option<String>,
+    to_status: String,
+    actor_id: Option<i64>,
+    reason: Option<String>,
+) -> anyhow::Result<order_status_history::Model> {
+    let model = order_status_history::ActiveModel {
+        order_id: Set(order_id),
+        from_status: Set(from_status),
+        to_status: Set(to_status),
+        actor_id: Set(actor_id),
+        reason: Set(reason),
+        ..Default::default()
+    };
+    Ok(model.insert(orm).await?)
+}
+
+pub async fn list_status_history<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+) -> anyhow::Result<Vec<order_status_history::Model>> {
+    Ok(order_status_history::Entity::find()
+        .filter(order_status_history::Column::OrderId.eq(order_id))
+        .order_by_asc(order_status_history::Column::CreatedAt)
+        .order_by_asc(order_status_history::Column::Id)
+        .all(orm)
+        .await?)
+}
+
 pub async fn create_payment<C: ConnectionTrait>(
     orm: &C,
     payment: payments::ActiveModel,
 ) -> anyhow::Result<payments::Model> {
     Ok(payment.insert(orm).await?)
 }
+
+pub async fn update_payment<C: ConnectionTrait>(
+    orm: &C,
+    payment: payments::ActiveModel,
+) -> anyhow::Result<payments::Model> {
+    Ok(payment.update(orm).await?)
+}
+
+pub async fn find_payment_for_update<C: ConnectionTrait>(
+    orm: &C,
+    payment_id: i64,
+) -> anyhow::Result<Option<payments::Model>> {
+    Ok(payments::Entity::find_by_id(payment_id)
+        .lock_exclusive()
+        .one(orm)
+        .await?)
+}
+
+pub async fn find_payment_by_provider_ref_for_update<C: ConnectionTrait>(
+    orm: &C,
+    provider_ref: &str,
+) -> anyhow::Result<Option<payments::Model>> {
+    Ok(payments::Entity::find()
+        .filter(payments::Column::ProviderRef.eq(provider_ref))
+        .lock_exclusive()
+        .one(orm)
+        .await?)
+}
+
+pub async fn find_last_status_change_to<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+    to_status: &str,
+) -> anyhow::Result<Option<order_status_history::Model>> {
+    Ok(order_status_history::Entity::find()
+        .filter(order_status_history::Column::OrderId.eq(order_id))
+        .filter(order_status_history::Column::ToStatus.eq(to_status))
+        .order_by_desc(order_status_history::Column::CreatedAt)
+        .order_by_desc(order_status_history::Column::Id)
+        .one(orm)
+        .await?)
+}
+
+pub async fn create_payment_plan<C: ConnectionTrait>(
+    orm: &C,
+    items: Vec<order_payment_plans::ActiveModel>,
+) -> anyhow::Result<Vec<order_payment_plans::Model>> {
+    let mut rows = Vec::with_capacity(items.len());
+    for item in items {
+        rows.push(item.insert(orm).await?);
+    }
+    Ok(rows)
+}
+
+pub async fn list_payment_plan<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+) -> anyhow::Result<Vec<order_payment_plans::Model>> {
+    Ok(order_payment_plans::Entity::find()
+        .filter(order_payment_plans::Column::OrderId.eq(order_id))
+        .order_by_asc(order_payment_plans::Column::Id)
+        .all(orm)
+        .await?)
+}
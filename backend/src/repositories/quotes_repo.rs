@@ -1,51 +1,41 @@
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, UpdateMany,
 };
 use sea_orm::prelude::Expr;
 
 use crate::entity::{demands, orders, quote_items, quote_versions, quotes, photographers};
 
-pub async fn expire_quotes_for_demand(
-    orm: &DatabaseConnection,
-    demand_id: i64,
-) -> Result<(), DbErr> {
-    let now = chrono::Utc::now();
+/// 将已过有效期的未决报价（pending/stale）置为 expired，返回本次被更新的报价。
+pub async fn expire_due_quotes<C: ConnectionTrait>(
+    orm: &C,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<quotes::Model>, DbErr> {
     quotes::Entity::update_many()
         .col_expr(quotes::Column::Status, Expr::value("expired"))
-        .filter(quotes::Column::DemandId.eq(demand_id))
-        .filter(quotes::Column::Status.eq("pending"))
+        .col_expr(quotes::Column::UpdatedAt, Expr::value(now))
+        .filter(quotes::Column::Status.is_in(["pending", "stale"]))
         .filter(quotes::Column::ExpiresAt.lte(now))
-        .exec(orm)
-        .await?;
-    Ok(())
+        .exec_with_returning(orm)
+        .await
 }
 
-pub async fn expire_quotes_for_photographer(
-    orm: &DatabaseConnection,
-    photographer_id: i64,
-) -> Result<(), DbErr> {
-    let now = chrono::Utc::now();
-    quotes::Entity::update_many()
-        .col_expr(quotes::Column::Status, Expr::value("expired"))
-        .filter(quotes::Column::PhotographerId.eq(photographer_id))
-        .filter(quotes::Column::Status.eq("pending"))
-        .filter(quotes::Column::ExpiresAt.lte(now))
-        .exec(orm)
-        .await?;
-    Ok(())
-}
-
-pub async fn expire_quote_by_id(orm: &DatabaseConnection, quote_id: i64) -> Result<(), DbErr> {
-    let now = chrono::Utc::now();
+/// 将指定需求下的未决报价（pending/stale）置为 expired，返回本次被更新的报价。
+pub async fn expire_pending_quotes_by_demands<C: ConnectionTrait>(
+    orm: &C,
+    demand_ids: Vec<i64>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<quotes::Model>, DbErr> {
+    if demand_ids.is_empty() {
+        return Ok(Vec::new());
+    }
     quotes::Entity::update_many()
         .col_expr(quotes::Column::Status, Expr::value("expired"))
-        .filter(quotes::Column::Id.eq(quote_id))
-        .filter(quotes::Column::Status.eq("pending"))
-        .filter(quotes::Column::ExpiresAt.lte(now))
-        .exec(orm)
-        .await?;
-    Ok(())
+        .col_expr(quotes::Column::UpdatedAt, Expr::value(now))
+        .filter(quotes::Column::DemandId.is_in(demand_ids))
+        .filter(quotes::Column::Status.is_in(["pending", "stale"]))
+        .exec_with_returning(orm)
+        .await
 }
 
 pub async fn list_quotes_by_demand(
@@ -81,8 +71,8 @@ pub async fn list_quote_items_by_quote_ids(
         .await?)
 }
 
-pub async fn list_quote_items_by_quote_id(
-    orm: &DatabaseConnection,
+pub async fn list_quote_items_by_quote_id<C: ConnectionTrait>(
+    orm: &C,
     quote_id: i64,
 ) -> anyhow::Result<Vec<quote_items::Model>> {
     Ok(quote_items::Entity::find()
@@ -98,13 +88,33 @@ pub async fn find_quote_by_id<C: ConnectionTrait>(
     Ok(quotes::Entity::find_by_id(quote_id).one(orm).await?)
 }
 
-pub async fn find_demand_by_id(
-    orm: &DatabaseConnection,
+pub async fn find_demand_by_id<C: ConnectionTrait>(
+    orm: &C,
     demand_id: i64,
 ) -> anyhow::Result<Option<demands::Model>> {
     Ok(demands::Entity::find_by_id(demand_id).one(orm).await?)
 }
 
+pub async fn find_quote_for_update<C: ConnectionTrait>(
+    orm: &C,
+    quote_id: i64,
+) -> anyhow::Result<Option<quotes::Model>> {
+    Ok(quotes::Entity::find_by_id(quote_id)
+        .lock_exclusive()
+        .one(orm)
+        .await?)
+}
+
+pub async fn find_demand_for_update<C: ConnectionTrait>(
+    orm: &C,
+    demand_id: i64,
+) -> anyhow::Result<Option<demands::Model>> {
+    Ok(demands::Entity::find_by_id(demand_id)
+        .lock_exclusive()
+        .one(orm)
+        .await?)
+}
+
 pub async fn find_photographer_by_id<C: ConnectionTrait>(
     orm: &C,
     photographer_id: i64,
@@ -124,9 +134,11 @@ pub async fn find_photographer_by_user(
         .await?)
 }
 
-pub async fn list_quotes_by_photographer(
+/// 个人摄影师报价与指定团队报价。
+pub async fn list_quotes_by_provider(
     orm: &DatabaseConnection,
-    photographer_id: i64,
+    photographer_id: Option<i64>,
+    team_ids: Vec<i64>,
     status: Option<String>,
     demand_id: Option<i64>,
     page: u64,
@@ -134,8 +146,11 @@ pub async fn list_quotes_by_photographer(
 ) -> anyhow::Result<(Vec<quotes::Model>, u64)> {
     let offset = (page - 1) * page_size;
 
-    let mut query = quotes::Entity::find()
-        .filter(quotes::Column::PhotographerId.eq(photographer_id));
+    let mut query = quotes::Entity::find().filter(
+        Condition::any()
+            .add_option(photographer_id.map(|id| quotes::Column::PhotographerId.eq(id)))
+            .add(quotes::Column::TeamId.is_in(team_ids)),
+    );
     if let Some(status) = status {
         query = query.filter(quotes::Column::Status.eq(status));
     }
@@ -190,6 +205,64 @@ pub async fn find_order_by_quote_id<C: ConnectionTrait>(
         .await?)
 }
 
+/// 需求下未取消的订单（一个需求同时只允许一笔有效订单）。
+pub async fn find_active_order_by_demand<C: ConnectionTrait>(
+    orm: &C,
+    demand_id: i64,
+) -> anyhow::Result<Option<orders::Model>> {
+    Ok(orders::Entity::find()
+        .filter(orders::Column::DemandId.eq(demand_id))
+        .filter(orders::Column::Status.ne("cancelled"))
+        .one(orm)
+        .await?)
+}
+
+/// 需求下的未决报价（pending/stale）。
+pub async fn list_pending_quotes_by_demand<C: ConnectionTrait>(
+    orm: &C,
+    demand_id: i64,
+) -> anyhow::Result<Vec<quotes::Model>> {
+    Ok(quotes::Entity::find()
+        .filter(quotes::Column::DemandId.eq(demand_id))
+        .filter(quotes::Column::Status.is_in(["pending", "stale"]))
+        .all(orm)
+        .await?)
+}
+
+/// 需求变更后将其 pending 报价置为 stale，返回本次被更新的报价。
+pub async fn mark_pending_quotes_stale<C: ConnectionTrait>(
+    orm: &C,
+    demand_id: i64,
+) -> Result<Vec<quotes::Model>, DbErr> {
+    quotes::Entity::update_many()
+        .col_expr(quotes::Column::Status, Expr::value("stale"))
+        .col_expr(quotes::Column::UpdatedAt, Expr::value(chrono::Utc::now()))
+        .filter(quotes::Column::DemandId.eq(demand_id))
+        .filter(quotes::Column::Status.eq("pending"))
+        .exec_with_returning(orm)
+        .await
+}
+
+/// 成交时关闭同需求下其余未决报价（pending/stale）。
+pub async fn reject_quotes<C: ConnectionTrait>(
+    orm: &C,
+    quote_ids: Vec<i64>,
+) -> anyhow::Result<()> {
+    if quote_ids.is_empty() {
+        return Ok(());
+    }
+    reject_quotes_query(quote_ids).exec(orm).await?;
+    Ok(())
+}
+
+fn reject_quotes_query(quote_ids: Vec<i64>) -> UpdateMany<quotes::Entity> {
+    quotes::Entity::update_many()
+        .col_expr(quotes::Column::Status, Expr::value("rejected"))
+        .col_expr(quotes::Column::UpdatedAt, Expr::value(chrono::Utc::now()))
+        .filter(quotes::Column::Id.is_in(quote_ids))
+        .filter(quotes::Column::Status.is_in(["pending", "stale"]))
+}
+
 pub async fn create_quote<C: ConnectionTrait>(
     orm: &C,
     model: quotes::ActiveModel,
@@ -211,6 +284,7 @@ pub async fn create_quote_version<C: ConnectionTrait>(
     Ok(model.insert(orm).await?)
 }
 
+/// 更新生效版本；新版本重新进入 pending。
 pub async fn update_quote<C: ConnectionTrait>(
     orm: &C,
     quote: quotes::Model,
@@ -219,6 +293,7 @@ pub async fn update_quote<C: ConnectionTrait>(
     expires_at: chrono::DateTime<chrono::Utc>,
 ) -> anyhow::Result<quotes::Model> {
     let mut model: quotes::ActiveModel = quote.into();
+    model.status = Set("pending".to_string());
     model.total_price = Set(total_price);
     model.version = Set(version);
     model.expires_at = Set(Some(expires_at.into()));
@@ -258,9 +333,74 @@ pub async fn list_quote_versions(
         .await?)
 }
 
+pub async fn find_quote_version<C: ConnectionTrait>(
+    orm: &C,
+    quote_id: i64,
+    version: i32,
+) -> anyhow::Result<Option<quote_versions::Model>> {
+    Ok(quote_versions::Entity::find()
+        .filter(quote_versions::Column::QuoteId.eq(quote_id))
+        .filter(quote_versions::Column::Version.eq(version))
+        .one(orm)
+        .await?)
+}
+
+/// 报价下一个版本号；还价与摄影师修改共用同一版本序列。
+pub async fn next_quote_version<C: ConnectionTrait>(
+    orm: &C,
+    quote_id: i64,
+) -> anyhow::Result<i32> {
+    let latest: Option<Option<i32>> = quote_versions::Entity::find()
+        .select_only()
+        .column_as(quote_versions::Column::Version.max(), "version")
+        .filter(quote_versions::Column::QuoteId.eq(quote_id))
+        .into_tuple()
+        .one(orm)
+        .await?;
+    Ok(latest.flatten().unwrap_or(0) + 1)
+}
+
+pub async fn update_quote_version_status<C: ConnectionTrait>(
+    orm: &C,
+    version: quote_versions::Model,
+    status: &str,
+) -> anyhow::Result<quote_versions::Model> {
+    let mut model: quote_versions::ActiveModel = version.into();
+    model.status = Set(status.to_string());
+    Ok(model.update(orm).await?)
+}
+
+/// 将报价下待回应的还价置为 superseded。
+pub async fn supersede_proposed_versions<C: ConnectionTrait>(
+    orm: &C,
+    quote_id: i64,
+) -> anyhow::Result<()> {
+    quote_versions::Entity::update_many()
+        .col_expr(quote_versions::Column::Status, Expr::value("superseded"))
+        .filter(quote_versions::Column::QuoteId.eq(quote_id))
+        .filter(quote_versions::Column::Status.eq("proposed"))
+        .exec(orm)
+        .await?;
+    Ok(())
+}
+
 pub async fn create_order<C: ConnectionTrait>(
     orm: &C,
     model: orders::ActiveModel,
 ) -> anyhow::Result<orders::Model> {
     Ok(model.insert(orm).await?)
 }
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryTrait};
+
+    use super::*;
+
+    #[test]
+    fn test_reject_quotes_closes_stale_siblings() {
+        let sql = reject_quotes_query(vec![1, 2]).build(DbBackend::Postgres).to_string();
+        assert!(sql.contains(r#""status" IN ('pending', 'stale')"#), "{sql}");
+        assert!(sql.contains(r#""id" IN (1, 2)"#), "{sql}");
+    }
+}
@@ -0,0 +1,97 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+};
+
+use crate::entity::{orders, refunds};
+use crate::repositories::orders_repo;
+
+pub async fn find_refund_by_id<C: ConnectionTrait>(
+    orm: &C,
+    refund_id: i64,
+) -> anyhow::Result<Option<refunds::Model>> {
+    Ok(refunds::Entity::find_by_id(refund_id).one(orm).await?)
+}
+
+pub async fn list_refunds_by_order<C: ConnectionTrait>(
+    orm: &C,
+    order_id: i64,
+) -> anyhow::Result<Vec<refunds::Model>> {
+    Ok(refunds::Entity::find()
+        .filter(refunds::Column::OrderId.eq(order_id))
+        .order_by_asc(refunds::Column::Id)
+        .all(orm)
+        .await?)
+}
+
+/// 列出用户作为下单方或承接摄影师参与的订单上的退款。
+pub async fn list_refunds_for_participant<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    photographer_ids: Vec<i64>,
+    team_ids: Vec<i64>,
+    status: Option<String>,
+    page: u64,
+    page_size: u64,
+) -> anyhow::Result<(Vec<refunds::Model>, u64)> {
+    let offset = (page - 1) * page_size;
+
+    let mut participant = Condition::any().add(orders::Column::UserId.eq(user_id));
+    if !photographer_ids.is_empty() {
+        participant = participant.add(orders::Column::PhotographerId.is_in(photographer_ids));
+    }
+    if !team_ids.is_empty() {
+        participant = participant.add(orders::Column::TeamId.is_in(team_ids));
+    }
+    let order_ids = orders::Entity::find()
+        .select_only()
+        .column(orders::Column::Id)
+        .filter(participant)
+        .into_query();
+
+    let mut query = refunds::Entity::find().filter(refunds::Column::OrderId.in_subquery(order_ids));
+    if let Some(status) = status {
+        query = query.filter(refunds::Column::Status.eq(status));
+    }
+
+    let total = query.clone().count(orm).await?;
+    let rows = query
+        .order_by_desc(refunds::Column::CreatedAt)
+        .order_by_desc(refunds::Column::Id)
+        .limit(page_size)
+        .offset(offset)
+        .all(orm)
+        .await?;
+    Ok((rows, total))
+}
+
+/// 按完成时间区间列出摄影师承接订单上已完成的退款。
+pub async fn list_completed_refunds_by_provider<C: ConnectionTrait>(
+    orm: &C,
+    photographer_id: Option<i64>,
+    team_ids: &[i64],
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Vec<refunds::Model>> {
+    let order_ids = orders::Entity::find()
+        .select_only()
+        .column(orders::Column::Id)
+        .filter(orders_repo::provider_orders_condition(photographer_id, team_ids))
+        .into_query();
+    Ok(refunds::Entity::find()
+        .filter(refunds::Column::OrderId.in_subquery(order_ids))
+        .filter(refunds::Column::Status.eq("completed"))
+        .filter(refunds::Column::CompletedAt.gte(start))
+        .filter(refunds::Column::CompletedAt.lt(end))
+        .order_by_asc(refunds::Column::CompletedAt)
+        .order_by_asc(refunds::Column::Id)
+        .all(orm)
+        .await?)
+}
+
+pub async fn update_refund<C: ConnectionTrait>(
+    orm: &C,
+    refund: refunds::ActiveModel,
+) -> anyhow::Result<refunds::Model> {
+    Ok(refund.update(orm).await?)
+}
@@ -0,0 +1,132 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+
+use crate::entity::{role_permissions, roles, user_roles};
+
+pub async fn list_roles(orm: &DatabaseConnection) -> anyhow::Result<Vec<roles::Model>> {
+    Ok(roles::Entity::find()
+        .order_by_asc(roles::Column::Id)
+        .all(orm)
+        .await?)
+}
+
+pub async fn find_role_by_name<C: ConnectionTrait>(
+    orm: &C,
+    name: &str,
+) -> anyhow::Result<Option<roles::Model>> {
+    Ok(roles::Entity::find()
+        .filter(roles::Column::Name.eq(name))
+        .one(orm)
+        .await?)
+}
+
+pub async fn list_roles_by_ids<C: ConnectionTrait>(
+    orm: &C,
+    role_ids: Vec<i64>,
+) -> anyhow::Result<Vec<roles::Model>> {
+    if role_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(roles::Entity::find()
+        .filter(roles::Column::Id.is_in(role_ids))
+        .order_by_asc(roles::Column::Id)
+        .all(orm)
+        .await?)
+}
+
+pub async fn list_user_roles<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<user_roles::Model>> {
+    Ok(user_roles::Entity::find()
+        .filter(user_roles::Column::UserId.eq(user_id))
+        .all(orm)
+        .await?)
+}
+
+pub async fn list_user_role_ids<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<i64>> {
+    Ok(user_roles::Entity::find()
+        .select_only()
+        .column(user_roles::Column::RoleId)
+        .filter(user_roles::Column::UserId.eq(user_id))
+        .into_tuple::<i64>()
+        .all(orm)
+        .await?)
+}
+
+pub async fn find_user_role<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    role_id: i64,
+) -> anyhow::Result<Option<user_roles::Model>> {
+    Ok(user_roles::Entity::find()
+        .filter(user_roles::Column::UserId.eq(user_id))
+        .filter(user_roles::Column::RoleId.eq(role_id))
+        .one(orm)
+        .await?)
+}
+
+pub async fn create_user_role<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    role_id: i64,
+    scope: Option<String>,
+) -> anyhow::Result<user_roles::Model> {
+    let model = user_roles::ActiveModel {
+        user_id: Set(user_id),
+        role_id: Set(role_id),
+        scope: Set(scope),
+    };
+    Ok(model.insert(orm).await?)
+}
+
+pub async fn delete_user_role<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    role_id: i64,
+) -> anyhow::Result<()> {
+    user_roles::Entity::delete_many()
+        .filter(user_roles::Column::UserId.eq(user_id))
+        .filter(user_roles::Column::RoleId.eq(role_id))
+        .exec(orm)
+        .await?;
+    Ok(())
+}
+
+pub async fn list_permissions_by_role_ids<C: ConnectionTrait>(
+    orm: &C,
+    role_ids: Vec<i64>,
+) -> anyhow::Result<Vec<role_permissions::Model>> {
+    if role_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(role_permissions::Entity::find()
+        .filter(role_permissions::Column::RoleId.is_in(role_ids))
+        .order_by_asc(role_permissions::Column::Permission)
+        .all(orm)
+        .await?)
+}
+
+pub async fn replace_role_permissions<C: ConnectionTrait>(
+    orm: &C,
+    role_id: i64,
+    permissions: Vec<String>,
+) -> anyhow::Result<()> {
+    role_permissions::Entity::delete_many()
+        .filter(role_permissions::Column::RoleId.eq(role_id))
+        .exec(orm)
+        .await?;
+    for permission in permissions {
+        let model = role_permissions::ActiveModel {
+            role_id: Set(role_id),
+            permission: Set(permission),
+        };
+        model.insert(orm).await?;
+    }
+    Ok(())
+}
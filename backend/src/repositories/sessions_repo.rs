@@ -0,0 +1,178 @@
+use sea_orm::{
+    prelude::DateTimeWithTimeZone, sea_query::Expr, ActiveModelTrait, ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+};
+
+use crate::entity::{session_rotated_tokens, sessions};
+
+pub async fn create_session<C: ConnectionTrait>(
+    orm: &C,
+    session: sessions::ActiveModel,
+) -> anyhow::Result<sessions::Model> {
+    Ok(session.insert(orm).await?)
+}
+
+pub async fn find_active_by_token<C: ConnectionTrait>(
+    orm: &C,
+    token: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Option<sessions::Model>> {
+    Ok(sessions::Entity::find()
+        .filter(sessions::Column::Token.eq(token))
+        .filter(sessions::Column::ExpiredAt.gt(now))
+        .filter(sessions::Column::RevokedAt.is_null())
+        .one(orm)
+        .await?)
+}
+
+pub async fn find_by_refresh_token<C: ConnectionTrait>(
+    orm: &C,
+    refresh_token: &str,
+) -> anyhow::Result<Option<sessions::Model>> {
+    Ok(sessions::Entity::find()
+        .filter(sessions::Column::RefreshToken.eq(refresh_token))
+        .one(orm)
+        .await?)
+}
+
+pub async fn find_session_by_id<C: ConnectionTrait>(
+    orm: &C,
+    session_id: i64,
+) -> anyhow::Result<Option<sessions::Model>> {
+    Ok(sessions::Entity::find_by_id(session_id).one(orm).await?)
+}
+
+pub async fn list_active_sessions<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<Vec<sessions::Model>> {
+    Ok(sessions::Entity::find()
+        .filter(sessions::Column::UserId.eq(user_id))
+        .filter(sessions::Column::RevokedAt.is_null())
+        .filter(
+            sea_orm::Condition::any()
+                .add(sessions::Column::ExpiredAt.gt(now))
+                .add(sessions::Column::RefreshExpiredAt.gt(now)),
+        )
+        .order_by_desc(sessions::Column::CreatedAt)
+        .all(orm)
+        .await?)
+}
+
+pub struct RotateTokens {
+    pub token: String,
+    pub refresh_token: String,
+    pub expired_at: chrono::DateTime<chrono::Utc>,
+    pub refresh_expired_at: chrono::DateTime<chrono::Utc>,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+/// Swaps both tokens only if the presented refresh token is still current,
+/// so a refresh token can be redeemed at most once.
+pub async fn rotate_session_tokens<C: ConnectionTrait>(
+    orm: &C,
+    session_id: i64,
+    old_refresh_token: &str,
+    tokens: RotateTokens,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<u64> {
+    let mut update = sessions::Entity::update_many()
+        .col_expr(sessions::Column::Token, Expr::value(tokens.token))
+        .col_expr(sessions::Column::RefreshToken, Expr::value(tokens.refresh_token))
+        .col_expr(
+            sessions::Column::ExpiredAt,
+            Expr::value(DateTimeWithTimeZone::from(tokens.expired_at)),
+        )
+        .col_expr(
+            sessions::Column::RefreshExpiredAt,
+            Expr::value(DateTimeWithTimeZone::from(tokens.refresh_expired_at)),
+        )
+        .col_expr(sessions::Column::LastSeenAt, Expr::value(DateTimeWithTimeZone::from(now)));
+    if let Some(user_agent) = tokens.user_agent {
+        update = update.col_expr(sessions::Column::UserAgent, Expr::value(user_agent));
+    }
+    if let Some(ip) = tokens.ip {
+        update = update.col_expr(sessions::Column::Ip, Expr::value(ip));
+    }
+    let result = update
+        .filter(sessions::Column::Id.eq(session_id))
+        .filter(sessions::Column::RefreshToken.eq(old_refresh_token))
+        .filter(sessions::Column::RevokedAt.is_null())
+        .exec(orm)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Remembers a refresh token that has been rotated away so a later replay can
+/// be recognised as token theft.
+pub async fn record_rotated_token<C: ConnectionTrait>(
+    orm: &C,
+    session_id: i64,
+    refresh_token: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<()> {
+    session_rotated_tokens::ActiveModel {
+        refresh_token: Set(refresh_token.to_string()),
+        session_id: Set(session_id),
+        rotated_at: Set(now.into()),
+    }
+    .insert(orm)
+    .await?;
+    Ok(())
+}
+
+pub async fn find_session_id_by_rotated_token<C: ConnectionTrait>(
+    orm: &C,
+    refresh_token: &str,
+) -> anyhow::Result<Option<i64>> {
+    Ok(session_rotated_tokens::Entity::find_by_id(refresh_token.to_string())
+        .one(orm)
+        .await?
+        .map(|row| row.session_id))
+}
+
+pub async fn revoke_session<C: ConnectionTrait>(
+    orm: &C,
+    session_id: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<u64> {
+    let result = sessions::Entity::update_many()
+        .col_expr(sessions::Column::RevokedAt, Expr::value(DateTimeWithTimeZone::from(now)))
+        .filter(sessions::Column::Id.eq(session_id))
+        .filter(sessions::Column::RevokedAt.is_null())
+        .exec(orm)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn revoke_user_sessions<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<u64> {
+    let result = sessions::Entity::update_many()
+        .col_expr(sessions::Column::RevokedAt, Expr::value(DateTimeWithTimeZone::from(now)))
+        .filter(sessions::Column::UserId.eq(user_id))
+        .filter(sessions::Column::RevokedAt.is_null())
+        .exec(orm)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn revoke_user_sessions_except<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    keep_session_id: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<u64> {
+    let result = sessions::Entity::update_many()
+        .col_expr(sessions::Column::RevokedAt, Expr::value(DateTimeWithTimeZone::from(now)))
+        .filter(sessions::Column::UserId.eq(user_id))
+        .filter(sessions::Column::Id.ne(keep_session_id))
+        .filter(sessions::Column::RevokedAt.is_null())
+        .exec(orm)
+        .await?;
+    Ok(result.rows_affected)
+}
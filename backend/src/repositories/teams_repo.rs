@@ -1,9 +1,12 @@
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
 
 use crate::entity::{team_members, teams};
 
-pub async fn create_team(
-    orm: &DatabaseConnection,
+pub async fn create_team<C: ConnectionTrait>(
+    orm: &C,
     owner_user_id: i64,
     name: String,
     status: String,
@@ -18,8 +21,8 @@ pub async fn create_team(
     Ok(model.insert(orm).await?)
 }
 
-pub async fn list_owned_team_ids(
-    orm: &DatabaseConnection,
+pub async fn list_owned_team_ids<C: ConnectionTrait>(
+    orm: &C,
     user_id: i64,
 ) -> anyhow::Result<Vec<i64>> {
     let rows = teams::Entity::find()
@@ -33,8 +36,8 @@ pub async fn list_owned_team_ids(
     Ok(rows)
 }
 
-pub async fn list_team_members_by_user(
-    orm: &DatabaseConnection,
+pub async fn list_team_members_by_user<C: ConnectionTrait>(
+    orm: &C,
     user_id: i64,
 ) -> anyhow::Result<Vec<team_members::Model>> {
     Ok(team_members::Entity::find()
@@ -58,8 +61,8 @@ pub async fn list_teams_by_ids(
         .await?)
 }
 
-pub async fn find_team_by_id(
-    orm: &DatabaseConnection,
+pub async fn find_team_by_id<C: ConnectionTrait>(
+    orm: &C,
     team_id: i64,
 ) -> anyhow::Result<Option<teams::Model>> {
     Ok(teams::Entity::find_by_id(team_id).one(orm).await?)
@@ -76,8 +79,8 @@ pub async fn update_team_name(
     Ok(model.update(orm).await?)
 }
 
-pub async fn find_team_member(
-    orm: &DatabaseConnection,
+pub async fn find_team_member<C: ConnectionTrait>(
+    orm: &C,
     team_id: i64,
     user_id: i64,
 ) -> anyhow::Result<Option<team_members::Model>> {
@@ -99,8 +102,8 @@ pub async fn list_team_members(
         .await?)
 }
 
-pub async fn create_team_member(
-    orm: &DatabaseConnection,
+pub async fn create_team_member<C: ConnectionTrait>(
+    orm: &C,
     team_id: i64,
     user_id: i64,
     role: String,
@@ -0,0 +1,250 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set,
+};
+
+use crate::entity::{
+    demands, messages, notifications, orders, payments, photographers, quotes, reviews,
+    user_profiles, users, verification_codes,
+};
+
+pub async fn find_user_by_id<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Option<users::Model>> {
+    Ok(users::Entity::find_by_id(user_id).one(orm).await?)
+}
+
+pub async fn update_user_status<C: ConnectionTrait>(
+    orm: &C,
+    user: users::Model,
+    status: &str,
+) -> anyhow::Result<users::Model> {
+    let mut model: users::ActiveModel = user.into();
+    model.status = Set(status.to_string());
+    model.updated_at = Set(chrono::Utc::now().into());
+    Ok(model.update(orm).await?)
+}
+
+pub async fn update_password_hash<C: ConnectionTrait>(
+    orm: &C,
+    user: users::Model,
+    password_hash: Option<String>,
+) -> anyhow::Result<users::Model> {
+    let mut model: users::ActiveModel = user.into();
+    model.password_hash = Set(password_hash);
+    model.updated_at = Set(chrono::Utc::now().into());
+    Ok(model.update(orm).await?)
+}
+
+pub async fn find_user_by_phone<C: ConnectionTrait>(
+    orm: &C,
+    phone: &str,
+) -> anyhow::Result<Option<users::Model>> {
+    Ok(users::Entity::find()
+        .filter(users::Column::Phone.eq(phone))
+        .one(orm)
+        .await?)
+}
+
+pub async fn find_profile<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Option<user_profiles::Model>> {
+    Ok(user_profiles::Entity::find()
+        .filter(user_profiles::Column::UserId.eq(user_id))
+        .one(orm)
+        .await?)
+}
+
+pub async fn anonymize_user<C: ConnectionTrait>(
+    orm: &C,
+    user: users::Model,
+    now: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<users::Model> {
+    let user_id = user.id;
+    let mut model: users::ActiveModel = user.into();
+    model.phone = Set(format!("deleted_{user_id}"));
+    model.username = Set(None);
+    model.email = Set(None);
+    model.password_hash = Set(None);
+    model.status = Set("deleted".to_string());
+    model.deleted_at = Set(Some(now.into()));
+    model.updated_at = Set(now.into());
+    let updated = model.update(orm).await?;
+
+    if let Some(profile) = find_profile(orm, user_id).await? {
+        let mut profile: user_profiles::ActiveModel = profile.into();
+        profile.nickname = Set(None);
+        profile.avatar_url = Set(None);
+        profile.gender = Set(None);
+        profile.birthday = Set(None);
+        profile.city_id = Set(None);
+        profile.bio = Set(None);
+        profile.updated_at = Set(now.into());
+        profile.update(orm).await?;
+    }
+
+    Ok(updated)
+}
+
+pub async fn delete_verification_codes<C: ConnectionTrait>(
+    orm: &C,
+    phone: &str,
+) -> anyhow::Result<()> {
+    verification_codes::Entity::delete_many()
+        .filter(verification_codes::Column::Phone.eq(phone))
+        .exec(orm)
+        .await?;
+    Ok(())
+}
+
+pub async fn list_photographer_ids<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<i64>> {
+    Ok(photographers::Entity::find()
+        .filter(photographers::Column::UserId.eq(user_id))
+        .all(orm)
+        .await?
+        .into_iter()
+        .map(|p| p.id)
+        .collect())
+}
+
+/// 用户作为下单方、摄影师或团队所有者（团队订单收款人）参与的进行中订单数。
+pub async fn count_open_orders<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    photographer_ids: Vec<i64>,
+    owned_team_ids: Vec<i64>,
+) -> anyhow::Result<u64> {
+    let mut cond = participant_condition(user_id, photographer_ids);
+    if !owned_team_ids.is_empty() {
+        cond = cond.add(orders::Column::TeamId.is_in(owned_team_ids));
+    }
+    Ok(orders::Entity::find()
+        .filter(cond)
+        .filter(orders::Column::Status.is_in(["confirmed", "paid", "ongoing", "frozen"]))
+        .count(orm)
+        .await?)
+}
+
+fn participant_condition(user_id: i64, photographer_ids: Vec<i64>) -> Condition {
+    let mut cond = Condition::any().add(orders::Column::UserId.eq(user_id));
+    if !photographer_ids.is_empty() {
+        cond = cond.add(orders::Column::PhotographerId.is_in(photographer_ids));
+    }
+    cond
+}
+
+pub async fn export_demands<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    Ok(demands::Entity::find()
+        .filter(demands::Column::UserId.eq(user_id))
+        .order_by_asc(demands::Column::Id)
+        .into_json()
+        .all(orm)
+        .await?)
+}
+
+pub async fn export_quotes<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    photographer_ids: Vec<i64>,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let demand_ids: Vec<i64> = demands::Entity::find()
+        .filter(demands::Column::UserId.eq(user_id))
+        .all(orm)
+        .await?
+        .into_iter()
+        .map(|d| d.id)
+        .collect();
+    if demand_ids.is_empty() && photographer_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut cond = Condition::any();
+    if !demand_ids.is_empty() {
+        cond = cond.add(quotes::Column::DemandId.is_in(demand_ids));
+    }
+    if !photographer_ids.is_empty() {
+        cond = cond.add(quotes::Column::PhotographerId.is_in(photographer_ids));
+    }
+    Ok(quotes::Entity::find()
+        .filter(cond)
+        .order_by_asc(quotes::Column::Id)
+        .into_json()
+        .all(orm)
+        .await?)
+}
+
+pub async fn export_orders<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+    photographer_ids: Vec<i64>,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    Ok(orders::Entity::find()
+        .filter(participant_condition(user_id, photographer_ids))
+        .order_by_asc(orders::Column::Id)
+        .into_json()
+        .all(orm)
+        .await?)
+}
+
+pub async fn export_payments<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    Ok(payments::Entity::find()
+        .filter(
+            Condition::any()
+                .add(payments::Column::PayerId.eq(user_id))
+                .add(payments::Column::PayeeId.eq(user_id)),
+        )
+        .order_by_asc(payments::Column::Id)
+        .into_json()
+        .all(orm)
+        .await?)
+}
+
+pub async fn export_reviews<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    Ok(reviews::Entity::find()
+        .filter(
+            Condition::any()
+                .add(reviews::Column::RaterId.eq(user_id))
+                .add(reviews::Column::RateeId.eq(user_id)),
+        )
+        .order_by_asc(reviews::Column::Id)
+        .into_json()
+        .all(orm)
+        .await?)
+}
+
+pub async fn export_messages<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    Ok(messages::Entity::find()
+        .filter(messages::Column::SenderId.eq(user_id))
+        .order_by_asc(messages::Column::Id)
+        .into_json()
+        .all(orm)
+        .await?)
+}
+
+pub async fn export_notifications<C: ConnectionTrait>(
+    orm: &C,
+    user_id: i64,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    Ok(notifications::Entity::find()
+        .filter(notifications::Column::UserId.eq(user_id))
+        .order_by_asc(notifications::Column::Id)
+        .into_json()
+        .all(orm)
+        .await?)
+}
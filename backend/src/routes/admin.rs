@@ -1,6 +1,6 @@
-use axum::{routing::{get, post}, Router};
+use axum::{routing::{delete, get, post, put}, Router};
 
-use crate::handlers::admin;
+use crate::handlers::{admin, ledger, refunds, roles, users};
 use crate::state::AppState;
 
 pub fn router() -> Router<AppState> {
@@ -10,6 +10,13 @@ pub fn router() -> Router<AppState> {
         .route("/metrics/trends", get(admin::get_metrics_trends))
         .route("/reports/orders", get(admin::export_orders_report))
         .route("/users", get(admin::list_admin_users))
+        .route("/users/:id/roles", get(roles::list_user_roles).post(roles::grant_role))
+        .route("/users/:id/roles/:role", delete(roles::revoke_role))
+        .route("/users/:id/ban", post(users::ban_user))
+        .route("/users/:id/unban", post(users::unban_user))
+        .route("/users/:id/password/reset", post(users::force_password_reset))
+        .route("/roles", get(roles::list_roles))
+        .route("/roles/:role/permissions", put(roles::update_role_permissions))
         .route("/orders", get(admin::list_admin_orders))
         .route("/orders/:id", get(admin::get_admin_order_detail))
         .route("/disputes", get(admin::list_admin_disputes))
@@ -24,5 +31,10 @@ pub fn router() -> Router<AppState> {
         .route("/merchant-templates", get(admin::list_merchant_templates))
         .route("/photographers/:id/review", post(admin::review_photographer))
         .route("/orders/:id/freeze", post(admin::freeze_order))
+        .route("/orders/:id/unfreeze", post(admin::unfreeze_order))
         .route("/disputes/:id/resolve", post(admin::resolve_dispute))
+        .route("/refunds/:id/review", post(refunds::review_refund))
+        .route("/ledger/accounts", get(ledger::list_accounts))
+        .route("/ledger/accounts/:id/statement", get(ledger::get_statement))
+        .route("/ledger/reconciliation", get(ledger::reconcile_payments))
 }
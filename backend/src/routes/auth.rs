@@ -1,13 +1,28 @@
-use axum::{routing::post, Json, Router};
+use axum::{http::HeaderMap, routing::post, Json, Router};
 use bcrypt::verify;
-use chrono::{Duration, Utc};
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbBackend, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, Statement, TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 use crate::error::{ApiError, ApiResult};
+use crate::handlers::sessions;
+use crate::middleware::auth::is_user_active;
+use crate::dto::users::PasswordResp;
+use crate::repositories::users_repo;
+use crate::services::{roles_service, users_service};
+use crate::services::sessions_service::{self, SessionMeta};
+use crate::sms::SmsMessage;
 use crate::state::AppState;
-use crate::entity::{sessions, users, verification_codes};
+use crate::entity::{users, verification_codes};
+
+const CODE_TTL_MINUTES: i64 = 5;
+const CODE_MAX_ATTEMPTS: i32 = 5;
+const CODE_SEND_COOLDOWN_SECONDS: i64 = 60;
+const CODE_DAILY_QUOTA: u64 = 10;
 
 #[derive(Deserialize)]
 struct SendCodeReq {
@@ -27,11 +42,22 @@ struct LoginReq {
     password: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct ResetPasswordReq {
+    phone: String,
+    code: String,
+    new_password: String,
+}
+
 #[derive(Serialize)]
 struct LoginResp {
     token: String,
+    refresh_token: String,
+    expired_at: i64,
+    refresh_expired_at: i64,
     user: LoginUser,
     roles: Vec<String>,
+    permissions: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -45,6 +71,9 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/code", post(send_code))
         .route("/login", post(login))
+        .route("/refresh", post(sessions::refresh))
+        .route("/logout", post(sessions::logout))
+        .route("/password/reset", post(reset_password))
 }
 
 async fn send_code(
@@ -55,17 +84,53 @@ async fn send_code(
         return Err(ApiError::bad_request("invalid_phone"));
     }
 
+    // Serialize sends per phone for the rest of the transaction, so parallel requests
+    // cannot all pass the cooldown and daily quota checks before any of them inserts.
+    let txn = state.orm.begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        [req.phone.clone().into()],
+    ))
+    .await?;
+
+    let now = Utc::now();
+    let last_sent_at = verification_codes::Entity::find()
+        .filter(verification_codes::Column::Phone.eq(&req.phone))
+        .order_by_desc(verification_codes::Column::CreatedAt)
+        .one(&txn)
+        .await?
+        .map(|c| c.created_at.with_timezone(&Utc));
+    let sent_today = verification_codes::Entity::find()
+        .filter(verification_codes::Column::Phone.eq(&req.phone))
+        .filter(verification_codes::Column::CreatedAt.gt(now - Duration::days(1)))
+        .count(&txn)
+        .await?;
+    check_send_allowed(last_sent_at, sent_today, now).map_err(ApiError::too_many_requests)?;
+
     let code = generate_code();
-    let expired_at = Utc::now() + Duration::minutes(5);
+    let expired_at = now + Duration::minutes(CODE_TTL_MINUTES);
 
     let model = verification_codes::ActiveModel {
         phone: Set(req.phone),
         code: Set(code),
         expired_at: Set(expired_at.into()),
+        attempts: Set(0),
+        consumed_at: Set(None),
         ..Default::default()
     };
 
-    let inserted = model.insert(&state.orm).await?;
+    let inserted = model.insert(&txn).await?;
+    txn.commit().await?;
+
+    state
+        .sms
+        .send(&SmsMessage::verification_code(&inserted.phone, &inserted.code))
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "sms_send_failed");
+            ApiError::internal()
+        })?;
 
     Ok(Json(crate::common::ApiResponse::ok(SendCodeResp {
         expired_at: inserted.expired_at.timestamp(),
@@ -73,6 +138,7 @@ async fn send_code(
 }
 
 async fn login(
+    headers: HeaderMap,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<LoginReq>,
 ) -> ApiResult<LoginResp> {
@@ -85,17 +151,14 @@ async fn login(
             return Err(ApiError::bad_request("invalid_phone"));
         }
 
-        let verify_code = verification_codes::Entity::find()
+        let latest = verification_codes::Entity::find()
             .filter(verification_codes::Column::Phone.eq(phone))
-            .filter(verification_codes::Column::Code.eq(code))
-            .filter(verification_codes::Column::ExpiredAt.gt(Utc::now()))
             .order_by_desc(verification_codes::Column::CreatedAt)
             .one(&state.orm)
-            .await?;
+            .await?
+            .ok_or_else(|| ApiError::bad_request("invalid_code"))?;
 
-        if verify_code.is_none() {
-            return Err(ApiError::bad_request("invalid_code"));
-        }
+        consume_code(&state.orm, latest, code).await?;
 
         ensure_user(&state.orm, phone).await?
     } else if let Some(password) = req.password.as_deref() {
@@ -134,27 +197,182 @@ async fn login(
     } else {
         return Err(ApiError::bad_request("login_payload_required"));
     };
-    let token = Uuid::new_v4().to_string();
-
-    let session = sessions::ActiveModel {
-        user_id: Set(user.id),
-        token: Set(token.clone()),
-        expired_at: Set((Utc::now() + Duration::days(7)).into()),
-        ..Default::default()
-    };
+    if !is_user_active(&user) {
+        return Err(ApiError::forbidden_with("account_disabled"));
+    }
 
-    session.insert(&state.orm).await?;
+    let tokens =
+        sessions_service::issue_session(&state.orm, user.id, SessionMeta::from_headers(&headers))
+            .await?;
 
-    let roles = resolve_roles(&user.phone);
+    let roles = roles_service::resolve_roles(&state.orm, user.id).await?;
+    let permissions = roles_service::resolve_permissions(&state.orm, user.id).await?;
     let resp = LoginResp {
-        token,
+        token: tokens.token,
+        refresh_token: tokens.refresh_token,
+        expired_at: tokens.expired_at,
+        refresh_expired_at: tokens.refresh_expired_at,
         user: LoginUser { id: user.id, phone: user.phone, status: user.status },
         roles,
+        permissions,
     };
 
     Ok(Json(crate::common::ApiResponse::ok(resp)))
 }
 
+async fn reset_password(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<ResetPasswordReq>,
+) -> ApiResult<PasswordResp> {
+    if !is_valid_phone(&req.phone) {
+        return Err(ApiError::bad_request("invalid_phone"));
+    }
+
+    let latest = verification_codes::Entity::find()
+        .filter(verification_codes::Column::Phone.eq(&req.phone))
+        .order_by_desc(verification_codes::Column::CreatedAt)
+        .one(&state.orm)
+        .await?
+        .ok_or_else(|| ApiError::bad_request("invalid_code"))?;
+
+    // Verify the code before revealing anything about the account, so this endpoint
+    // cannot be used to probe which phones are registered.
+    let code_id = latest.id;
+    verify_code(&state.orm, latest, &req.code).await?;
+    let user = users_repo::find_user_by_phone(&state.orm, &req.phone)
+        .await?
+        .ok_or_else(|| ApiError::bad_request("invalid_code"))?;
+    if !is_user_active(&user) {
+        return Err(ApiError::forbidden_with("account_disabled"));
+    }
+    // Reject a weak password before the code is spent, so the user can retry with the same code.
+    users_service::validate_password_strength(&req.new_password, &user)?;
+    mark_code_consumed(&state.orm, code_id).await?;
+
+    let txn = state.orm.begin().await?;
+    let resp = users_service::reset_password(&txn, user, &req.new_password).await?;
+    txn.commit().await?;
+
+    Ok(Json(crate::common::ApiResponse::ok(resp)))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum CodeCheck {
+    Valid,
+    Mismatch,
+    Locked,
+    Expired,
+    Consumed,
+}
+
+/// Checks a submitted code against the most recent code sent to the phone.
+/// Older codes are never considered, so sending a new code invalidates them.
+fn check_code(record: &verification_codes::Model, input: &str, now: DateTime<Utc>) -> CodeCheck {
+    if record.consumed_at.is_some() {
+        return CodeCheck::Consumed;
+    }
+    if record.expired_at.with_timezone(&Utc) <= now {
+        return CodeCheck::Expired;
+    }
+    if record.attempts >= CODE_MAX_ATTEMPTS {
+        return CodeCheck::Locked;
+    }
+    if constant_time_eq(record.code.as_bytes(), input.as_bytes()) {
+        CodeCheck::Valid
+    } else {
+        CodeCheck::Mismatch
+    }
+}
+
+async fn consume_code(
+    db: &sea_orm::DatabaseConnection,
+    record: verification_codes::Model,
+    input: &str,
+) -> Result<(), ApiError> {
+    let code_id = record.id;
+    verify_code(db, record, input).await?;
+    mark_code_consumed(db, code_id).await
+}
+
+/// Spends one attempt on the code and compares it, leaving the code unconsumed.
+async fn verify_code(
+    db: &sea_orm::DatabaseConnection,
+    record: verification_codes::Model,
+    input: &str,
+) -> Result<(), ApiError> {
+    match check_code(&record, input, Utc::now()) {
+        CodeCheck::Valid | CodeCheck::Mismatch => {}
+        CodeCheck::Locked => return Err(ApiError::too_many_requests("code_attempts_exceeded")),
+        CodeCheck::Expired | CodeCheck::Consumed => {
+            return Err(ApiError::bad_request("invalid_code"));
+        }
+    }
+
+    // Claim an attempt before trusting the comparison, so parallel guesses each
+    // spend one and at most CODE_MAX_ATTEMPTS of them are ever compared.
+    let claimed = verification_codes::Entity::update_many()
+        .col_expr(
+            verification_codes::Column::Attempts,
+            Expr::col(verification_codes::Column::Attempts).add(1),
+        )
+        .filter(verification_codes::Column::Id.eq(record.id))
+        .filter(verification_codes::Column::ConsumedAt.is_null())
+        .filter(verification_codes::Column::Attempts.lt(CODE_MAX_ATTEMPTS))
+        .exec_with_returning(db)
+        .await?;
+    if claimed.is_empty() {
+        return Err(ApiError::too_many_requests("code_attempts_exceeded"));
+    }
+    if !constant_time_eq(record.code.as_bytes(), input.as_bytes()) {
+        return Err(ApiError::bad_request("invalid_code"));
+    }
+    Ok(())
+}
+
+async fn mark_code_consumed(
+    db: &sea_orm::DatabaseConnection,
+    code_id: i64,
+) -> Result<(), ApiError> {
+    // Conditional update so two concurrent logins cannot both consume the code.
+    let result = verification_codes::Entity::update_many()
+        .col_expr(
+            verification_codes::Column::ConsumedAt,
+            Expr::value(sea_orm::prelude::DateTimeWithTimeZone::from(Utc::now())),
+        )
+        .filter(verification_codes::Column::Id.eq(code_id))
+        .filter(verification_codes::Column::ConsumedAt.is_null())
+        .exec(db)
+        .await?;
+    if result.rows_affected == 1 {
+        Ok(())
+    } else {
+        Err(ApiError::bad_request("invalid_code"))
+    }
+}
+
+fn check_send_allowed(
+    last_sent_at: Option<DateTime<Utc>>,
+    sent_today: u64,
+    now: DateTime<Utc>,
+) -> Result<(), &'static str> {
+    if let Some(last) = last_sent_at
+        && now - last < Duration::seconds(CODE_SEND_COOLDOWN_SECONDS)
+    {
+        return Err("code_send_too_frequent");
+    }
+    if sent_today >= CODE_DAILY_QUOTA {
+        return Err("code_daily_quota_exceeded");
+    }
+    Ok(())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 async fn ensure_user(db: &sea_orm::DatabaseConnection, phone: &str) -> Result<users::Model, ApiError> {
     if let Some(user) = users::Entity::find()
         .filter(users::Column::Phone.eq(phone))
@@ -190,40 +408,8 @@ fn is_valid_username(username: &str) -> bool {
 }
 
 fn generate_code() -> String {
-    let nanos = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.subsec_nanos())
-        .unwrap_or(0);
-    format!("{:06}", (nanos % 1_000_000))
-}
-
-fn resolve_roles(phone: &str) -> Vec<String> {
-    let mut roles = Vec::new();
-    if env_list_contains("ADMIN_PHONES", phone) {
-        roles.push("admin".to_string());
-    }
-    if env_list_contains("OPS_PHONES", phone) {
-        roles.push("ops".to_string());
-    }
-    if env_list_contains("MANAGER_PHONES", phone) {
-        roles.push("manager".to_string());
-    }
-    if roles.is_empty() {
-        roles.push("user".to_string());
-    }
-    roles
-}
-
-fn env_list_contains(key: &str, phone: &str) -> bool {
-    std::env::var(key)
-        .ok()
-        .map(|v| {
-            v.split(',')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .any(|s| s == phone)
-        })
-        .unwrap_or(false)
+    let n: u32 = rand::rngs::OsRng.gen_range(0..1_000_000);
+    format!("{:06}", n)
 }
 
 #[cfg(test)]
@@ -245,6 +431,70 @@ mod tests {
         assert!(code.chars().all(|c| c.is_ascii_digit()));
     }
 
+    fn code_record(code: &str, attempts: i32) -> verification_codes::Model {
+        let now = Utc::now();
+        verification_codes::Model {
+            id: 1,
+            phone: "13800138000".to_string(),
+            code: code.to_string(),
+            expired_at: (now + Duration::minutes(CODE_TTL_MINUTES)).into(),
+            attempts,
+            consumed_at: None,
+            created_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn test_check_code_locks_after_max_attempts() {
+        let mut record = code_record("123456", 0);
+        let now = Utc::now();
+        for guess in 0..CODE_MAX_ATTEMPTS {
+            let input = format!("{:06}", guess);
+            assert_eq!(check_code(&record, &input, now), CodeCheck::Mismatch);
+            record.attempts += 1;
+        }
+        // Even the right code is refused once the attempt budget is spent.
+        assert_eq!(check_code(&record, "123456", now), CodeCheck::Locked);
+    }
+
+    #[test]
+    fn test_check_code_rejects_replay() {
+        let mut record = code_record("123456", 0);
+        let now = Utc::now();
+        assert_eq!(check_code(&record, "123456", now), CodeCheck::Valid);
+        record.consumed_at = Some(now.into());
+        assert_eq!(check_code(&record, "123456", now), CodeCheck::Consumed);
+    }
+
+    #[test]
+    fn test_check_code_rejects_expired() {
+        let record = code_record("123456", 0);
+        let later = Utc::now() + Duration::minutes(CODE_TTL_MINUTES + 1);
+        assert_eq!(check_code(&record, "123456", later), CodeCheck::Expired);
+    }
+
+    #[test]
+    fn test_check_send_allowed() {
+        let now = Utc::now();
+        assert!(check_send_allowed(None, 0, now).is_ok());
+        assert_eq!(
+            check_send_allowed(Some(now - Duration::seconds(10)), 1, now),
+            Err("code_send_too_frequent")
+        );
+        let cooled_down = now - Duration::seconds(CODE_SEND_COOLDOWN_SECONDS);
+        assert!(check_send_allowed(Some(cooled_down), 1, now).is_ok());
+        assert_eq!(
+            check_send_allowed(None, CODE_DAILY_QUOTA, now),
+            Err("code_daily_quota_exceeded")
+        );
+    }
+
+    #[test]
+    fn test_generate_code_varies() {
+        let codes: std::collections::HashSet<String> = (0..20).map(|_| generate_code()).collect();
+        assert!(codes.len() > 1);
+    }
+
     #[test]
     fn test_is_valid_username() {
         assert!(is_valid_username("admin_user"));
@@ -4,8 +4,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::middleware::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
+use crate::repositories::orders_repo;
+use crate::services::order_state_service::{self, OrderStatus};
+use crate::services::provider_access_service::{self, ProviderAction};
 use crate::state::AppState;
-use crate::entity::{deliveries, delivery_items, orders, photographers};
+use crate::entity::{deliveries, delivery_items, orders};
 
 #[derive(Deserialize)]
 struct DeliveryItemReq {
@@ -57,7 +60,7 @@ struct DeliveryDetailResp {
 }
 
 async fn list_deliveries(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<DeliveryListQuery>,
 ) -> ApiResult<Vec<DeliveryDetailResp>> {
@@ -65,17 +68,13 @@ async fn list_deliveries(
         .one(&state.orm)
         .await?
         .ok_or_else(ApiError::not_found)?;
-    let photographer_user = match order.photographer_id {
-        Some(pid) => photographers::Entity::find_by_id(pid)
-            .one(&state.orm)
-            .await?
-            .map(|p| p.user_id),
-        None => None,
-    };
-
-    if order.user_id != user_id && photographer_user != Some(user_id) {
-        return Err(ApiError::forbidden());
-    }
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::View,
+    )
+    .await?;
 
     let rows = deliveries::Entity::find()
         .filter(deliveries::Column::OrderId.eq(q.order_id))
@@ -122,7 +121,7 @@ async fn list_deliveries(
 }
 
 async fn create_delivery(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateDeliveryReq>,
 ) -> ApiResult<DeliveryResp> {
@@ -130,25 +129,33 @@ async fn create_delivery(
         return Err(ApiError::bad_request("items_required"));
     }
 
-    let order = orders::Entity::find_by_id(req.order_id)
-        .one(&state.orm)
+    let txn = state.orm.begin().await?;
+    // 锁定订单行，避免与冻结等并发状态变更互相覆盖。
+    let order = orders_repo::find_order_for_update(&txn, req.order_id)
         .await?
         .ok_or_else(ApiError::not_found)?;
 
-    let photographer_user = match order.photographer_id {
-        Some(pid) => photographers::Entity::find_by_id(pid)
-            .one(&state.orm)
-            .await?
-            .map(|p| p.user_id),
-        None => None,
-    };
-
-    if photographer_user != Some(user_id) {
-        return Err(ApiError::forbidden());
+    provider_access_service::ensure_provider_action(
+        &txn,
+        order.photographer_id,
+        order.team_id,
+        user_id,
+        ProviderAction::Deliver,
+    )
+    .await?;
+    order_state_service::ensure_not_frozen(&order)?;
+
+    if OrderStatus::of(&order)? != OrderStatus::Ongoing {
+        order_state_service::transition_order(
+            &txn,
+            order,
+            OrderStatus::Ongoing,
+            Some(user_id),
+            Some("delivery_submitted".to_string()),
+        )
+        .await?;
     }
 
-    let txn = state.orm.begin().await?;
-
     let delivery = deliveries::ActiveModel {
         order_id: Set(req.order_id),
         status: Set("submitted".to_string()),
@@ -168,10 +175,6 @@ async fn create_delivery(
         di.insert(&txn).await?;
     }
 
-    let mut order_model: orders::ActiveModel = order.into();
-    order_model.status = Set("ongoing".to_string());
-    order_model.update(&txn).await?;
-
     txn.commit().await?;
 
     Ok(Json(crate::common::ApiResponse::ok(DeliveryResp {
@@ -182,17 +185,23 @@ async fn create_delivery(
 }
 
 async fn accept_delivery(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(delivery_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<DeliveryResp> {
-    let delivery = deliveries::Entity::find_by_id(delivery_id)
+    let order_id = deliveries::Entity::find_by_id(delivery_id)
         .one(&state.orm)
         .await?
-        .ok_or_else(ApiError::not_found)?;
+        .ok_or_else(ApiError::not_found)?
+        .order_id;
 
-    let order = orders::Entity::find_by_id(delivery.order_id)
-        .one(&state.orm)
+    let txn = state.orm.begin().await?;
+    // 先锁订单行，再在锁内重新读取交付，串行化验收与冻结等状态变更。
+    let order = orders_repo::find_order_for_update(&txn, order_id)
+        .await?
+        .ok_or_else(ApiError::not_found)?;
+    let delivery = deliveries::Entity::find_by_id(delivery_id)
+        .one(&txn)
         .await?
         .ok_or_else(ApiError::not_found)?;
 
@@ -202,17 +211,21 @@ async fn accept_delivery(
     if delivery.status != "submitted" {
         return Err(ApiError::bad_request("invalid_status"));
     }
-
-    let txn = state.orm.begin().await?;
+    order_state_service::ensure_not_frozen(&order)?;
 
     let mut delivery_model: deliveries::ActiveModel = delivery.into();
     delivery_model.status = Set("accepted".to_string());
     delivery_model.accepted_at = Set(Some(chrono::Utc::now().into()));
     let updated = delivery_model.update(&txn).await?;
 
-    let mut order_model: orders::ActiveModel = order.into();
-    order_model.status = Set("completed".to_string());
-    order_model.update(&txn).await?;
+    order_state_service::transition_order(
+        &txn,
+        order,
+        OrderStatus::Completed,
+        Some(user_id),
+        Some("delivery_accepted".to_string()),
+    )
+    .await?;
 
     txn.commit().await?;
 
@@ -6,7 +6,8 @@ use crate::state::AppState;
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", post(demands::create_demand).get(demands::list_demands))
-        .route("/:id", get(demands::get_demand))
+        .route("/:id", get(demands::get_demand).put(demands::update_demand))
+        .route("/:id/revisions", get(demands::list_demand_revisions))
         .route(
             "/:id/merchant-assets",
             get(demands::list_demand_merchant_assets),
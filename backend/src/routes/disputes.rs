@@ -4,8 +4,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::middleware::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
+use crate::services::provider_access_service::{self, ProviderAction};
 use crate::state::AppState;
-use crate::entity::{dispute_evidence, disputes, orders, photographers};
+use crate::entity::{dispute_evidence, disputes, orders};
 
 #[derive(Deserialize)]
 struct EvidenceReq {
@@ -32,7 +33,7 @@ pub fn router() -> Router<AppState> {
 }
 
 async fn create_dispute(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateDisputeReq>,
 ) -> ApiResult<DisputeResp> {
@@ -45,17 +46,13 @@ async fn create_dispute(
         .await?
         .ok_or_else(ApiError::not_found)?;
 
-    let photographer_user = match order.photographer_id {
-        Some(pid) => photographers::Entity::find_by_id(pid)
-            .one(&state.orm)
-            .await?
-            .map(|p| p.user_id),
-        None => None,
-    };
-
-    if order.user_id != user_id && photographer_user != Some(user_id) {
-        return Err(ApiError::forbidden());
-    }
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::ManageOrder,
+    )
+    .await?;
 
     let txn = state.orm.begin().await?;
 
@@ -1,6 +1,8 @@
+use axum::middleware::from_fn_with_state;
 use axum::routing::get;
 use axum::Router;
 
+use crate::middleware::idempotency::idempotency;
 use crate::state::AppState;
 
 mod admin;
@@ -23,7 +25,8 @@ mod teams;
 mod users;
 mod uploads;
 
-pub fn create_router() -> Router<AppState> {
+pub fn create_router(state: AppState) -> Router<AppState> {
+    let idempotent = from_fn_with_state(state, idempotency);
     Router::new()
         .route("/health", get(health))
         .nest("/auth", auth::router())
@@ -31,10 +34,10 @@ pub fn create_router() -> Router<AppState> {
         .nest("/photographers", photographers::router())
         .nest("/portfolios", photographers::portfolio_router())
         .nest("/demands", demands::router())
-        .nest("/quotes", quotes::router())
-        .nest("/orders", orders::router())
-        .nest("/payments", payments::router())
-        .nest("/refunds", refunds::router())
+        .nest("/quotes", quotes::router().route_layer(idempotent.clone()))
+        .nest("/orders", orders::router().route_layer(idempotent.clone()))
+        .nest("/payments", payments::router().route_layer(idempotent.clone()))
+        .nest("/refunds", refunds::router().route_layer(idempotent.clone()))
         .nest("/deliveries", deliveries::router())
         .nest("/reviews", reviews::router())
         .nest("/disputes", disputes::router())
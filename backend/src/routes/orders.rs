@@ -7,6 +7,9 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(orders::list_orders))
         .route("/:id", get(orders::get_order))
+        .route("/:id/contract", get(orders::get_order_contract))
+        .route("/:id/timeline", get(orders::get_order_timeline))
+        .route("/:id/payment-plan", get(orders::get_payment_plan))
         .route("/:id/refund-preview", get(orders::refund_preview))
         .route("/:id/cancel", post(orders::cancel_order))
 }
@@ -4,5 +4,8 @@ use crate::handlers::payments;
 use crate::state::AppState;
 
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", post(payments::create_payment))
+    Router::new()
+        .route("/", post(payments::create_payment))
+        .route("/:id/confirm", post(payments::confirm_payment))
+        .route("/callbacks/:channel", post(payments::payment_callback))
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::middleware::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
+use crate::money::Money;
 use crate::state::AppState;
 use crate::entity::{order_items, orders, photographers, portfolio_items, portfolios, users};
 use crate::dto::pagination::{normalize_pagination, Paged};
@@ -61,7 +62,7 @@ struct PortfolioItemResp {
 struct PhotographerOrderListItem {
     id: i64,
     status: String,
-    total_amount: f64,
+    total_amount: Money,
     demand_id: Option<i64>,
     created_at: String,
 }
@@ -69,7 +70,7 @@ struct PhotographerOrderListItem {
 #[derive(Serialize)]
 struct PhotographerOrderItem {
     name: String,
-    price: f64,
+    price: Money,
     quantity: i32,
 }
 
@@ -78,8 +79,8 @@ struct PhotographerOrderDetail {
     id: i64,
     status: String,
     pay_type: String,
-    total_amount: f64,
-    service_fee: f64,
+    total_amount: Money,
+    service_fee: Money,
     schedule_start: Option<String>,
     schedule_end: Option<String>,
     user_id: i64,
@@ -95,6 +96,8 @@ pub fn router() -> Router<AppState> {
         .route("/me", get(get_my_photographer))
         .route("/me/orders", get(list_my_orders))
         .route("/me/orders/:id", get(get_my_order))
+        .route("/me/earnings", get(photographer_handlers::get_my_earnings))
+        .route("/me/earnings/export", get(photographer_handlers::export_my_earnings))
         .route("/:id", get(get_photographer))
 }
 
@@ -105,7 +108,7 @@ pub fn portfolio_router() -> Router<AppState> {
 }
 
 async fn create_photographer(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreatePhotographerReq>,
 ) -> ApiResult<PhotographerResp> {
@@ -154,7 +157,7 @@ async fn get_photographer(
 }
 
 async fn get_my_photographer(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<PhotographerResp> {
     let row = photographers::Entity::find()
@@ -181,7 +184,7 @@ struct PhotographerOrderQuery {
 }
 
 async fn list_my_orders(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<PhotographerOrderQuery>,
 ) -> ApiResult<Paged<PhotographerOrderListItem>> {
@@ -222,7 +225,7 @@ async fn list_my_orders(
         .map(|o| PhotographerOrderListItem {
             id: o.id,
             status: o.status,
-            total_amount: decimal_to_f64(o.total_amount),
+            total_amount: Money::from(o.total_amount),
             demand_id: o.demand_id,
             created_at: o.created_at.to_rfc3339(),
         })
@@ -234,7 +237,7 @@ async fn list_my_orders(
 }
 
 async fn get_my_order(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(order_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<PhotographerOrderDetail> {
@@ -260,7 +263,7 @@ async fn get_my_order(
         .into_iter()
         .map(|it| PhotographerOrderItem {
             name: it.name,
-            price: decimal_to_f64(it.price),
+            price: Money::from(it.price),
             quantity: it.quantity,
         })
         .collect();
@@ -276,8 +279,8 @@ async fn get_my_order(
         id: order.id,
         status: order.status,
         pay_type: order.pay_type,
-        total_amount: decimal_to_f64(order.total_amount),
-        service_fee: decimal_to_f64(order.service_fee),
+        total_amount: Money::from(order.total_amount),
+        service_fee: Money::from(order.service_fee),
         schedule_start: order.schedule_start.map(|d| d.to_rfc3339()),
         schedule_end: order.schedule_end.map(|d| d.to_rfc3339()),
         user_id: order.user_id,
@@ -289,7 +292,7 @@ async fn get_my_order(
 }
 
 async fn create_portfolio(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreatePortfolioReq>,
 ) -> ApiResult<PortfolioResp> {
@@ -320,7 +323,7 @@ async fn create_portfolio(
 }
 
 async fn add_portfolio_item(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(portfolio_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreatePortfolioItemReq>,
@@ -362,7 +365,7 @@ struct PortfolioListQuery {
 }
 
 async fn list_portfolios(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<PortfolioListQuery>,
 ) -> ApiResult<Vec<PortfolioResp>> {
@@ -406,7 +409,7 @@ async fn list_portfolios(
 }
 
 async fn list_portfolio_items(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(portfolio_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<Vec<PortfolioItemResp>> {
@@ -440,7 +443,3 @@ async fn list_portfolio_items(
 
     Ok(Json(crate::common::ApiResponse::ok(items)))
 }
-
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
-}
@@ -9,6 +9,10 @@ pub fn router() -> Router<AppState> {
         .route("/mine", get(quotes::list_my_quotes))
         .route("/:id", get(quotes::get_quote).put(quotes::update_quote))
         .route("/:id/versions", get(quotes::list_quote_versions))
+        .route("/:id/versions/diff", get(quotes::diff_quote_versions))
+        .route("/:id/counter-offers", post(quotes::create_counter_offer))
+        .route("/:id/versions/:version/accept", post(quotes::accept_counter_offer))
+        .route("/:id/versions/:version/decline", post(quotes::decline_counter_offer))
         .route("/:id/withdraw", post(quotes::withdraw_quote))
         .route("/:id/accept", post(quotes::accept_quote))
 }
@@ -1,8 +1,12 @@
-use axum::{routing::post, Router};
+use axum::{routing::{get, post}, Router};
 
 use crate::handlers::refunds;
 use crate::state::AppState;
 
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", post(refunds::create_refund))
+    Router::new()
+        .route("/", get(refunds::list_refunds).post(refunds::create_refund))
+        .route("/:id/approve", post(refunds::approve_refund))
+        .route("/:id/reject", post(refunds::reject_refund))
+        .route("/:id/complete", post(refunds::complete_refund))
 }
@@ -1,11 +1,14 @@
 use axum::{routing::post, Json, Router};
-use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use sea_orm::{ActiveModelTrait, Set, TransactionTrait};
 use serde::{Deserialize, Serialize};
 
 use crate::middleware::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
+use crate::repositories::orders_repo;
+use crate::services::order_state_service::{self, OrderStatus};
+use crate::services::provider_access_service;
 use crate::state::AppState;
-use crate::entity::{orders, photographers, reviews};
+use crate::entity::reviews;
 
 #[derive(Deserialize)]
 struct CreateReviewReq {
@@ -27,7 +30,7 @@ pub fn router() -> Router<AppState> {
 }
 
 async fn create_review(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateReviewReq>,
 ) -> ApiResult<ReviewResp> {
@@ -35,38 +38,43 @@ async fn create_review(
         return Err(ApiError::bad_request("invalid_score"));
     }
 
-    let order = orders::Entity::find_by_id(req.order_id)
-        .one(&state.orm)
+    let txn = state.orm.begin().await?;
+    // 锁定订单行，避免与冻结等并发状态变更互相覆盖。
+    let order = orders_repo::find_order_for_update(&txn, req.order_id)
         .await?
         .ok_or_else(ApiError::not_found)?;
 
     if order.user_id != user_id {
         return Err(ApiError::forbidden());
     }
+    order_state_service::ensure_not_frozen(&order)?;
 
-    let photographer_user = match order.photographer_id {
-        Some(pid) => photographers::Entity::find_by_id(pid)
-            .one(&state.orm)
-            .await?
-            .map(|p| p.user_id),
-        None => None,
-    };
+    let provider_user =
+        provider_access_service::provider_user_id(&txn, order.photographer_id, order.team_id)
+            .await?;
+
+    order_state_service::transition_order(
+        &txn,
+        order,
+        OrderStatus::Reviewed,
+        Some(user_id),
+        Some("review_created".to_string()),
+    )
+    .await?;
 
     let model = reviews::ActiveModel {
         order_id: Set(req.order_id),
         rater_id: Set(user_id),
-        ratee_id: Set(photographer_user.unwrap_or(user_id)),
+        ratee_id: Set(provider_user.unwrap_or(user_id)),
         score: Set(req.score),
         tags: Set(req.tags.map(|v| serde_json::json!(v))),
         comment: Set(req.comment),
         ..Default::default()
     };
 
-    let inserted = model.insert(&state.orm).await?;
+    let inserted = model.insert(&txn).await?;
 
-    let mut order_model: orders::ActiveModel = order.into();
-    order_model.status = Set("reviewed".to_string());
-    order_model.update(&state.orm).await?;
+    txn.commit().await?;
 
     Ok(Json(crate::common::ApiResponse::ok(ReviewResp {
         id: inserted.id,
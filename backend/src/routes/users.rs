@@ -1,9 +1,11 @@
-use axum::{routing::get, Json, Router};
+use axum::{routing::{delete, get, post}, Json, Router};
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 use serde::{Deserialize, Serialize};
 
 use crate::middleware::auth::AuthUser;
 use crate::error::{ApiError, ApiResult};
+use crate::handlers::sessions;
+use crate::handlers::users::{change_password, delete_account, export_account};
 use crate::state::AppState;
 use crate::entity::{user_profiles, users};
 
@@ -35,11 +37,15 @@ struct UpdateProfileReq {
 
 pub fn router() -> Router<AppState> {
     Router::new()
-        .route("/me", get(get_me).put(update_me))
+        .route("/me", get(get_me).put(update_me).delete(delete_account))
+        .route("/me/export", get(export_account))
+        .route("/me/password", post(change_password))
+        .route("/me/sessions", get(sessions::list_sessions))
+        .route("/me/sessions/:id", delete(sessions::revoke_session))
 }
 
 async fn get_me(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<MeResp> {
     let user = users::Entity::find_by_id(user_id)
@@ -69,7 +75,7 @@ async fn get_me(
 }
 
 async fn update_me(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<UpdateProfileReq>,
 ) -> ApiResult<ProfileResp> {
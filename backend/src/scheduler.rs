@@ -0,0 +1,67 @@
+use std::env;
+use std::time::Duration;
+
+use anyhow::Context;
+use tokio::time::MissedTickBehavior;
+
+use crate::services::expiry_service;
+use crate::state::AppState;
+
+const DEFAULT_INTERVAL_SECS: u64 = 60;
+
+/// Reads `SCHEDULER_INTERVAL_SECS` (60 by default); `0` disables background jobs.
+pub fn scheduler_interval_from_env() -> anyhow::Result<Option<Duration>> {
+    parse_interval(env::var("SCHEDULER_INTERVAL_SECS").ok().as_deref())
+}
+
+fn parse_interval(value: Option<&str>) -> anyhow::Result<Option<Duration>> {
+    let secs = match value.map(str::trim) {
+        None | Some("") => DEFAULT_INTERVAL_SECS,
+        Some(raw) => raw
+            .parse::<u64>()
+            .with_context(|| format!("invalid SCHEDULER_INTERVAL_SECS: {raw}"))?,
+    };
+    Ok((secs > 0).then(|| Duration::from_secs(secs)))
+}
+
+/// Runs the periodic jobs (quote expiry, stale demand closing) on a background task.
+///
+/// Every job claims rows with a conditional `UPDATE ... RETURNING`, so running
+/// several server instances never notifies the same row twice.
+pub fn spawn_scheduler(state: AppState, interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            run_jobs(&state).await;
+        }
+    })
+}
+
+async fn run_jobs(state: &AppState) {
+    let now = chrono::Utc::now();
+    match expiry_service::expire_quotes(&state.orm, now).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!(count, "scheduler_quotes_expired"),
+        Err(err) => tracing::warn!(error = ?err, "scheduler_expire_quotes_failed"),
+    }
+    match expiry_service::close_stale_demands(&state.orm, now).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!(count, "scheduler_demands_closed"),
+        Err(err) => tracing::warn!(error = ?err, "scheduler_close_demands_failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval(None).unwrap(), Some(Duration::from_secs(60)));
+        assert_eq!(parse_interval(Some("30")).unwrap(), Some(Duration::from_secs(30)));
+        assert_eq!(parse_interval(Some("0")).unwrap(), None);
+        assert!(parse_interval(Some("soon")).is_err());
+    }
+}
@@ -1,16 +1,21 @@
 use axum::Json;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    QuerySelect, Set, TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
 use chrono::{Duration, NaiveDate, TimeZone, Utc};
 
+use crate::common::render_csv;
 use crate::middleware::auth::AuthUser;
 use crate::dto::pagination::{normalize_pagination, Paged};
 use crate::error::{ApiError, ApiResult};
+use crate::money::Money;
+use crate::repositories::orders_repo;
+use crate::services::order_state_service::{self, OrderStatus};
+use crate::services::roles_service::ensure_permission;
 use crate::state::AppState;
 use crate::entity::{
     audit_logs, deliveries, delivery_items, dispute_evidence, disputes, merchant_approvals, merchants,
@@ -33,11 +38,11 @@ pub struct AuditResp {
 }
 
 pub async fn create_audit(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<CreateAuditReq>,
 ) -> ApiResult<AuditResp> {
-    ensure_role_access(&state.orm, user_id, &["admin"]).await?;
+    ensure_permission(&state.orm, user_id, "audits.write").await?;
 
     let model = audit_logs::ActiveModel {
         admin_id: Set(user_id),
@@ -76,11 +81,11 @@ pub struct AuditListItem {
 }
 
 pub async fn list_audits(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<AuditListQuery>,
 ) -> ApiResult<Paged<AuditListItem>> {
-    ensure_role_access(&state.orm, user_id, &["admin"]).await?;
+    ensure_permission(&state.orm, user_id, "audits.read").await?;
 
     let (page, page_size) = normalize_pagination(q.page, q.page_size);
     let offset = (page - 1) * page_size;
@@ -153,8 +158,9 @@ pub struct AdminMetricsResp {
     disputes_period: u64,
     pending_photographers: u64,
     pending_merchant_approvals: u64,
-    revenue_today: f64,
-    revenue_period: f64,
+    revenue_today: Money,
+    revenue_period: Money,
+    service_fee_period: Money,
 }
 
 #[derive(Serialize)]
@@ -162,7 +168,7 @@ pub struct AdminTrendPoint {
     date: String,
     orders: u64,
     disputes: u64,
-    revenue: f64,
+    revenue: Money,
 }
 
 #[derive(Serialize)]
@@ -187,7 +193,8 @@ pub struct OrderReportItem {
     photographer_id: Option<i64>,
     status: String,
     pay_type: String,
-    total_amount: f64,
+    total_amount: Money,
+    service_fee: Money,
     created_at: String,
 }
 
@@ -201,11 +208,11 @@ pub struct OrderReportResp {
 }
 
 pub async fn get_metrics(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<MetricsQuery>,
 ) -> ApiResult<AdminMetricsResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "metrics.read").await?;
 
     let period_days = normalize_days(q.days, 7, 90);
     let now = Utc::now();
@@ -224,6 +231,14 @@ pub async fn get_metrics(
         .filter(orders::Column::CreatedAt.gte(today_start))
         .count(&state.orm)
         .await?;
+    let service_fee_period = orders::Entity::find()
+        .filter(orders::Column::CreatedAt.gte(period_start))
+        .filter(orders::Column::Status.ne("cancelled"))
+        .all(&state.orm)
+        .await?
+        .into_iter()
+        .map(|o| Money::from(o.service_fee))
+        .sum::<Money>();
 
     let disputes_open = disputes::Entity::find()
         .filter(dispute_open_condition())
@@ -250,10 +265,10 @@ pub async fn get_metrics(
         .all(&state.orm)
         .await?;
 
-    let mut revenue_period = 0.0;
-    let mut revenue_today = 0.0;
+    let mut revenue_period = Money::ZERO;
+    let mut revenue_today = Money::ZERO;
     for payment in payments_rows {
-        let amount = decimal_to_f64(payment.amount);
+        let amount = Money::from(payment.amount);
         revenue_period += amount;
         if let Some(paid_at) = payment.paid_at
             && paid_at.with_timezone(&Utc) >= today_start
@@ -272,27 +287,28 @@ pub async fn get_metrics(
         disputes_period,
         pending_photographers,
         pending_merchant_approvals,
-        revenue_today: round_currency(revenue_today),
-        revenue_period: round_currency(revenue_period),
+        revenue_today,
+        revenue_period,
+        service_fee_period,
     })))
 }
 
 pub async fn get_metrics_trends(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<MetricsQuery>,
 ) -> ApiResult<AdminTrendResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "metrics.read").await?;
 
     let days = normalize_days(q.days, 7, 90);
     let today = Utc::now().date_naive();
     let start_date = today - Duration::days(days.saturating_sub(1));
     let start_dt = day_start(start_date);
 
-    let mut stats: HashMap<NaiveDate, (u64, u64, f64)> = HashMap::new();
+    let mut stats: HashMap<NaiveDate, (u64, u64, Money)> = HashMap::new();
     let mut date = start_date;
     while date <= today {
-        stats.insert(date, (0, 0, 0.0));
+        stats.insert(date, (0, 0, Money::ZERO));
         date = date.succ_opt().unwrap_or(today + Duration::days(1));
     }
 
@@ -328,7 +344,7 @@ pub async fn get_metrics_trends(
         if let Some(paid_at) = payment.paid_at {
             let date = paid_at.with_timezone(&Utc).date_naive();
             if let Some(entry) = stats.get_mut(&date) {
-                entry.2 += decimal_to_f64(payment.amount);
+                entry.2 += Money::from(payment.amount);
             }
         }
     }
@@ -336,12 +352,12 @@ pub async fn get_metrics_trends(
     let mut items = Vec::new();
     let mut date = start_date;
     while date <= today {
-        let (orders_count, dispute_count, revenue) = stats.get(&date).cloned().unwrap_or((0, 0, 0.0));
+        let (orders_count, dispute_count, revenue) = stats.get(&date).cloned().unwrap_or_default();
         items.push(AdminTrendPoint {
             date: date.format("%Y-%m-%d").to_string(),
             orders: orders_count,
             disputes: dispute_count,
-            revenue: round_currency(revenue),
+            revenue,
         });
         date = date.succ_opt().unwrap_or(today + Duration::days(1));
     }
@@ -350,11 +366,11 @@ pub async fn get_metrics_trends(
 }
 
 pub async fn export_orders_report(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<OrdersReportQuery>,
 ) -> ApiResult<OrderReportResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "reports.export").await?;
 
     let limit = q.limit.unwrap_or(500).min(5000);
     let mut query = orders::Entity::find();
@@ -385,7 +401,8 @@ pub async fn export_orders_report(
             photographer_id: o.photographer_id,
             status: o.status,
             pay_type: o.pay_type,
-            total_amount: decimal_to_f64(o.total_amount),
+            total_amount: Money::from(o.total_amount),
+            service_fee: Money::from(o.service_fee),
             created_at: o.created_at.to_rfc3339(),
         })
         .collect();
@@ -429,11 +446,11 @@ pub struct AdminUserListItem {
 }
 
 pub async fn list_admin_users(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<AdminUserListQuery>,
 ) -> ApiResult<Paged<AdminUserListItem>> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "users.read").await?;
 
     let (page, page_size) = normalize_pagination(q.page, q.page_size);
     let offset = (page - 1) * page_size;
@@ -595,16 +612,16 @@ pub struct AdminOrderListItem {
     photographer_phone: Option<String>,
     status: String,
     pay_type: String,
-    total_amount: f64,
+    total_amount: Money,
     created_at: String,
 }
 
 pub async fn list_admin_orders(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<AdminOrderListQuery>,
 ) -> ApiResult<Paged<AdminOrderListItem>> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "orders.read").await?;
 
     let (page, page_size) = normalize_pagination(q.page, q.page_size);
     let offset = (page - 1) * page_size;
@@ -686,7 +703,7 @@ pub async fn list_admin_orders(
                 photographer_phone,
                 status: o.status,
                 pay_type: o.pay_type,
-                total_amount: decimal_to_f64(o.total_amount),
+                total_amount: Money::from(o.total_amount),
                 created_at: o.created_at.to_rfc3339(),
             }
         })
@@ -719,11 +736,11 @@ pub struct AdminDisputeListItem {
 }
 
 pub async fn list_admin_disputes(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<AdminDisputeListQuery>,
 ) -> ApiResult<Paged<AdminDisputeListItem>> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "disputes.read").await?;
 
     let (page, page_size) = normalize_pagination(q.page, q.page_size);
     let offset = (page - 1) * page_size;
@@ -808,11 +825,11 @@ pub struct AdminPortfolioListItem {
 }
 
 pub async fn list_admin_portfolios(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<AdminPortfolioListQuery>,
 ) -> ApiResult<Paged<AdminPortfolioListItem>> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops"]).await?;
+    ensure_permission(&state.orm, user_id, "portfolios.review").await?;
 
     let (page, page_size) = normalize_pagination(q.page, q.page_size);
     let offset = (page - 1) * page_size;
@@ -902,52 +919,6 @@ pub async fn list_admin_portfolios(
     })))
 }
 
-pub async fn ensure_role_access(
-    db: &sea_orm::DatabaseConnection,
-    user_id: i64,
-    allowed: &[&str],
-) -> Result<(), ApiError> {
-    let user = users::Entity::find_by_id(user_id)
-        .one(db)
-        .await?
-        .ok_or_else(ApiError::unauthorized)?;
-    let roles = resolve_roles(&user.phone);
-    if roles.iter().any(|role| allowed.contains(&role.as_str())) {
-        Ok(())
-    } else {
-        Err(ApiError::forbidden())
-    }
-}
-
-fn resolve_roles(phone: &str) -> Vec<String> {
-    let mut roles = Vec::new();
-    if env_list_contains("ADMIN_PHONES", phone) {
-        roles.push("admin".to_string());
-    }
-    if env_list_contains("OPS_PHONES", phone) {
-        roles.push("ops".to_string());
-    }
-    if env_list_contains("MANAGER_PHONES", phone) {
-        roles.push("manager".to_string());
-    }
-    if roles.is_empty() {
-        roles.push("user".to_string());
-    }
-    roles
-}
-
-fn env_list_contains(key: &str, phone: &str) -> bool {
-    std::env::var(key)
-        .ok()
-        .map(|v| {
-            v.split(',')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .any(|s| s == phone)
-        })
-        .unwrap_or(false)
-}
-
 fn normalize_days(input: Option<i64>, default: i64, max: i64) -> i64 {
     let value = input.unwrap_or(default);
     let value = if value <= 0 { default } else { value };
@@ -971,36 +942,30 @@ fn dispute_open_condition() -> Condition {
         .add(disputes::Column::Status.ne("rejected"))
 }
 
-fn round_currency(value: f64) -> f64 {
-    (value * 100.0).round() / 100.0
-}
-
 fn render_orders_csv(items: &[OrderReportItem]) -> String {
-    let mut out = String::from("id,user_id,photographer_id,status,pay_type,total_amount,created_at\n");
-    for item in items {
-        let photographer = item.photographer_id.map(|v| v.to_string()).unwrap_or_default();
-        let row = [
+    let header = [
+        "id",
+        "user_id",
+        "photographer_id",
+        "status",
+        "pay_type",
+        "total_amount",
+        "service_fee",
+        "created_at",
+    ];
+    let rows = items.iter().map(|item| {
+        vec![
             item.id.to_string(),
             item.user_id.to_string(),
-            photographer,
+            item.photographer_id.map(|v| v.to_string()).unwrap_or_default(),
             item.status.clone(),
             item.pay_type.clone(),
             item.total_amount.to_string(),
+            item.service_fee.to_string(),
             item.created_at.clone(),
-        ];
-        out.push_str(&row.iter().map(|v| escape_csv(v)).collect::<Vec<_>>().join(","));
-        out.push('\n');
-    }
-    out
-}
-
-fn escape_csv(input: &str) -> String {
-    if input.contains(',') || input.contains('"') || input.contains('\n') {
-        let escaped = input.replace('"', "\"\"");
-        format!("\"{}\"", escaped)
-    } else {
-        input.to_string()
-    }
+        ]
+    });
+    render_csv(&header, rows)
 }
 
 pub async fn load_photographer_user_ids(
@@ -1068,21 +1033,17 @@ pub async fn load_merchant_user_ids_by_ids(
     Ok(set)
 }
 
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
-}
-
 #[derive(Serialize)]
 pub struct AdminOrderItemResp {
     name: String,
-    price: f64,
+    price: Money,
     quantity: i32,
 }
 
 #[derive(Serialize)]
 pub struct AdminPaymentResp {
     id: i64,
-    amount: f64,
+    amount: Money,
     status: String,
     pay_channel: String,
     paid_at: Option<String>,
@@ -1092,10 +1053,13 @@ pub struct AdminPaymentResp {
 #[derive(Serialize)]
 pub struct AdminRefundResp {
     id: i64,
-    amount: f64,
+    amount: Money,
+    approved_amount: Option<Money>,
     status: String,
     reason: Option<String>,
     proof_url: Option<String>,
+    review_note: Option<String>,
+    completion_proof_url: Option<String>,
     created_at: String,
 }
 
@@ -1129,9 +1093,9 @@ pub struct AdminOrderDetailResp {
     id: i64,
     status: String,
     pay_type: String,
-    total_amount: f64,
-    deposit_amount: f64,
-    service_fee: f64,
+    total_amount: Money,
+    deposit_amount: Money,
+    service_fee: Money,
     schedule_start: Option<String>,
     schedule_end: Option<String>,
     created_at: String,
@@ -1148,11 +1112,11 @@ pub struct AdminOrderDetailResp {
 }
 
 pub async fn get_admin_order_detail(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(order_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<AdminOrderDetailResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "orders.read").await?;
 
     let order = orders::Entity::find_by_id(order_id)
         .one(&state.orm)
@@ -1188,7 +1152,7 @@ pub async fn get_admin_order_detail(
         .into_iter()
         .map(|it| AdminOrderItemResp {
             name: it.name,
-            price: decimal_to_f64(it.price),
+            price: Money::from(it.price),
             quantity: it.quantity,
         })
         .collect::<Vec<_>>();
@@ -1201,7 +1165,7 @@ pub async fn get_admin_order_detail(
         .into_iter()
         .map(|p| AdminPaymentResp {
             id: p.id,
-            amount: decimal_to_f64(p.amount),
+            amount: Money::from(p.amount),
             status: p.status,
             pay_channel: p.pay_channel,
             paid_at: p.paid_at.map(|d| d.to_rfc3339()),
@@ -1217,10 +1181,13 @@ pub async fn get_admin_order_detail(
         .into_iter()
         .map(|r| AdminRefundResp {
             id: r.id,
-            amount: decimal_to_f64(r.amount),
+            amount: Money::from(r.amount),
+            approved_amount: r.approved_amount.map(Money::from),
             status: r.status,
             reason: r.reason,
             proof_url: r.proof_url,
+            review_note: r.review_note,
+            completion_proof_url: r.completion_proof_url,
             created_at: r.created_at.to_rfc3339(),
         })
         .collect::<Vec<_>>();
@@ -1276,9 +1243,9 @@ pub async fn get_admin_order_detail(
         id: order.id,
         status: order.status,
         pay_type: order.pay_type,
-        total_amount: decimal_to_f64(order.total_amount),
-        deposit_amount: decimal_to_f64(order.deposit_amount),
-        service_fee: decimal_to_f64(order.service_fee),
+        total_amount: Money::from(order.total_amount),
+        deposit_amount: Money::from(order.deposit_amount),
+        service_fee: Money::from(order.service_fee),
         schedule_start: order.schedule_start.map(|d| d.to_rfc3339()),
         schedule_end: order.schedule_end.map(|d| d.to_rfc3339()),
         created_at: order.created_at.to_rfc3339(),
@@ -1319,11 +1286,11 @@ pub struct AdminDisputeDetailResp {
 }
 
 pub async fn get_admin_dispute_detail(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(dispute_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
 ) -> ApiResult<AdminDisputeDetailResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops", "manager"]).await?;
+    ensure_permission(&state.orm, user_id, "disputes.read").await?;
 
     let dispute = disputes::Entity::find_by_id(dispute_id)
         .one(&state.orm)
@@ -1388,11 +1355,11 @@ pub struct MerchantApprovalListItem {
 }
 
 pub async fn list_merchant_approvals(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<MerchantApprovalListQuery>,
 ) -> ApiResult<Paged<MerchantApprovalListItem>> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops"]).await?;
+    ensure_permission(&state.orm, user_id, "merchants.review").await?;
 
     let (page, page_size) = normalize_pagination(q.page, q.page_size);
     let offset = (page - 1) * page_size;
@@ -1461,12 +1428,12 @@ pub struct ReviewMerchantApprovalResp {
 }
 
 pub async fn review_merchant_approval(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(approval_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<ReviewMerchantApprovalReq>,
 ) -> ApiResult<ReviewMerchantApprovalResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops"]).await?;
+    ensure_permission(&state.orm, user_id, "merchants.review").await?;
     if !matches!(req.status.as_str(), "approved" | "rejected") {
         return Err(ApiError::bad_request("invalid_status"));
     }
@@ -1509,7 +1476,7 @@ pub struct MerchantTemplateListQuery {
 pub struct MerchantTemplateItemResp {
     name: String,
     quantity: i32,
-    price: f64,
+    price: Money,
 }
 
 #[derive(Serialize)]
@@ -1524,11 +1491,11 @@ pub struct MerchantTemplateListItem {
 }
 
 pub async fn list_merchant_templates(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::State(state): axum::extract::State<AppState>,
     axum::extract::Query(q): axum::extract::Query<MerchantTemplateListQuery>,
 ) -> ApiResult<Paged<MerchantTemplateListItem>> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops"]).await?;
+    ensure_permission(&state.orm, user_id, "merchants.review").await?;
 
     let (page, page_size) = normalize_pagination(q.page, q.page_size);
     let offset = (page - 1) * page_size;
@@ -1576,7 +1543,7 @@ pub async fn list_merchant_templates(
             .push(MerchantTemplateItemResp {
                 name: item.name,
                 quantity: item.quantity,
-                price: decimal_to_f64(item.price),
+                price: Money::from(item.price),
             });
     }
 
@@ -1614,12 +1581,12 @@ pub struct ReviewPhotographerResp {
 }
 
 pub async fn review_photographer(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(photographer_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<ReviewPhotographerReq>,
 ) -> ApiResult<ReviewPhotographerResp> {
-    ensure_role_access(&state.orm, user_id, &["admin"]).await?;
+    ensure_permission(&state.orm, user_id, "photographers.review").await?;
     if !matches!(req.status.as_str(), "approved" | "rejected") {
         return Err(ApiError::bad_request("invalid_status"));
     }
@@ -1662,12 +1629,12 @@ pub struct ReviewPortfolioResp {
 }
 
 pub async fn review_portfolio(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(portfolio_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<ReviewPortfolioReq>,
 ) -> ApiResult<ReviewPortfolioResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops"]).await?;
+    ensure_permission(&state.orm, user_id, "portfolios.review").await?;
     if !matches!(req.status.as_str(), "approved" | "rejected") {
         return Err(ApiError::bad_request("invalid_status"));
     }
@@ -1709,21 +1676,26 @@ pub struct FreezeOrderResp {
 }
 
 pub async fn freeze_order(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(order_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<FreezeOrderReq>,
 ) -> ApiResult<FreezeOrderResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops"]).await?;
+    ensure_permission(&state.orm, user_id, "orders.freeze").await?;
 
-    let order = orders::Entity::find_by_id(order_id)
-        .one(&state.orm)
+    let txn = state.orm.begin().await?;
+    let order = orders_repo::find_order_for_update(&txn, order_id)
         .await?
         .ok_or_else(ApiError::not_found)?;
 
-    let mut model: orders::ActiveModel = order.into();
-    model.status = Set("frozen".to_string());
-    let updated = model.update(&state.orm).await?;
+    let updated = order_state_service::transition_order(
+        &txn,
+        order,
+        OrderStatus::Frozen,
+        Some(user_id),
+        req.reason.clone(),
+    )
+    .await?;
 
     let audit = audit_logs::ActiveModel {
         admin_id: Set(user_id),
@@ -1733,7 +1705,59 @@ pub async fn freeze_order(
         detail: Set(Some(json!({ "reason": req.reason }))),
         ..Default::default()
     };
-    audit.insert(&state.orm).await?;
+    audit.insert(&txn).await?;
+    txn.commit().await?;
+
+    Ok(Json(crate::common::ApiResponse::ok(FreezeOrderResp {
+        id: updated.id,
+        status: updated.status,
+    })))
+}
+
+#[derive(Deserialize)]
+pub struct UnfreezeOrderReq {
+    reason: Option<String>,
+    restore_status: Option<String>,
+}
+
+pub async fn unfreeze_order(
+    AuthUser { user_id, .. }: AuthUser,
+    axum::extract::Path(order_id): axum::extract::Path<i64>,
+    axum::extract::State(state): axum::extract::State<AppState>,
+    Json(req): Json<UnfreezeOrderReq>,
+) -> ApiResult<FreezeOrderResp> {
+    ensure_permission(&state.orm, user_id, "orders.freeze").await?;
+    let requested = match req.restore_status.as_deref() {
+        Some(s) => Some(
+            OrderStatus::parse(s).ok_or_else(|| ApiError::bad_request("invalid_restore_status"))?,
+        ),
+        None => None,
+    };
+
+    let txn = state.orm.begin().await?;
+    let order = orders_repo::find_order_for_update(&txn, order_id)
+        .await?
+        .ok_or_else(ApiError::not_found)?;
+
+    let updated = order_state_service::unfreeze_order(
+        &txn,
+        order,
+        requested,
+        Some(user_id),
+        req.reason.clone(),
+    )
+    .await?;
+
+    let audit = audit_logs::ActiveModel {
+        admin_id: Set(user_id),
+        action: Set("order_unfreeze".to_string()),
+        target_type: Set(Some("order".to_string())),
+        target_id: Set(Some(order_id)),
+        detail: Set(Some(json!({ "reason": req.reason, "restored_status": updated.status }))),
+        ..Default::default()
+    };
+    audit.insert(&txn).await?;
+    txn.commit().await?;
 
     Ok(Json(crate::common::ApiResponse::ok(FreezeOrderResp {
         id: updated.id,
@@ -1754,12 +1778,12 @@ pub struct ResolveDisputeResp {
 }
 
 pub async fn resolve_dispute(
-    AuthUser { user_id }: AuthUser,
+    AuthUser { user_id, .. }: AuthUser,
     axum::extract::Path(dispute_id): axum::extract::Path<i64>,
     axum::extract::State(state): axum::extract::State<AppState>,
     Json(req): Json<ResolveDisputeReq>,
 ) -> ApiResult<ResolveDisputeResp> {
-    ensure_role_access(&state.orm, user_id, &["admin", "ops"]).await?;
+    ensure_permission(&state.orm, user_id, "disputes.resolve").await?;
     if req.resolution.trim().is_empty() {
         return Err(ApiError::bad_request("resolution_required"));
     }
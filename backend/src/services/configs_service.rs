@@ -1,6 +1,7 @@
 use crate::dto::configs::{ConfigResp, UpdateConfigReq};
 use crate::errors::{DomainError, ServiceResult};
 use crate::repositories::configs_repo;
+use crate::services::{fee_service, payment_plan_service, refund_policy_service, roles_service};
 use crate::state::AppState;
 
 pub async fn get_config(
@@ -20,9 +21,21 @@ pub async fn get_config(
 
 pub async fn upsert_config(
     state: &AppState,
+    operator_id: i64,
     key: String,
     req: UpdateConfigReq,
 ) -> ServiceResult<ConfigResp> {
+    roles_service::ensure_permission(&state.orm, operator_id, "configs.manage").await?;
+    if key == fee_service::FEE_CONFIG_KEY {
+        fee_service::FeeRuleSet::parse(&req.value)?;
+    }
+    if key == refund_policy_service::REFUND_POLICY_CONFIG_KEY {
+        refund_policy_service::RefundPolicySet::parse(&req.value)?;
+    }
+    if key == payment_plan_service::PLAN_CONFIG_KEY {
+        payment_plan_service::PlanSettings::parse(&req.value)?;
+    }
+
     let existing = configs_repo::find_config_by_key(&state.orm, &key).await?;
 
     let saved = match existing {
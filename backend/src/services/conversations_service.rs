@@ -2,6 +2,7 @@ use crate::dto::conversations::{CreateConversationReq, ConversationResp, ListCon
 use crate::dto::pagination::{normalize_pagination, Paged};
 use crate::errors::{DomainError, ServiceResult};
 use crate::repositories::conversations_repo;
+use crate::services::provider_access_service::{self, ProviderAction};
 use crate::state::AppState;
 
 pub async fn create_conversation(
@@ -81,22 +82,14 @@ async fn ensure_order_participant(
         .await?
         .ok_or(DomainError::NotFound)?;
 
-    if order.user_id == user_id {
-        return Ok(());
-    }
-
-    let photographer_user = match order.photographer_id {
-        Some(pid) => conversations_repo::find_photographer_by_id(&state.orm, pid)
-            .await?
-            .map(|p| p.user_id),
-        None => None,
-    };
-
-    if photographer_user == Some(user_id) {
-        Ok(())
-    } else {
-        Err(DomainError::Forbidden.into())
-    }
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::Chat,
+    )
+    .await?;
+    Ok(())
 }
 
 async fn load_order_ids_for_user(state: &AppState, user_id: i64) -> ServiceResult<Vec<i64>> {
@@ -109,6 +102,13 @@ async fn load_order_ids_for_user(state: &AppState, user_id: i64) -> ServiceResul
         ids.append(&mut photo_orders);
     }
 
+    let team_ids = provider_access_service::list_team_ids(&state.orm, user_id).await?;
+    if !team_ids.is_empty() {
+        let mut team_orders =
+            conversations_repo::list_order_ids_by_teams(&state.orm, team_ids).await?;
+        ids.append(&mut team_orders);
+    }
+
     ids.sort_unstable();
     ids.dedup();
     Ok(ids)
@@ -1,16 +1,18 @@
-use std::str::FromStr;
-
 use crate::dto::demands::{
-    AttachmentResp, CreateDemandReq, DemandDetail, DemandListItem, DemandListQuery,
-    DemandMerchantAssetItem, DemandMerchantAssetQuery, DemandResp,
+    AttachmentReq, AttachmentResp, CreateDemandReq, DemandDetail, DemandListItem,
+    DemandListQuery, DemandMerchantAssetItem, DemandMerchantAssetQuery, DemandResp,
+    DemandRevisionItem, UpdateDemandReq,
 };
 use crate::dto::pagination::{normalize_pagination, Paged};
 use crate::errors::{DomainError, ServiceResult};
-use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter};
+use crate::money::Money;
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, TransactionTrait};
+use serde::Serialize;
 
 use crate::repositories::demands_repo;
 use crate::repositories::merchants_repo;
-use crate::repositories::teams_repo;
+use crate::repositories::quotes_repo;
+use crate::services::{notifications_service, provider_access_service};
 use crate::state::AppState;
 
 pub async fn create_demand(
@@ -38,8 +40,8 @@ pub async fn create_demand(
         location: sea_orm::ActiveValue::Set(req.location),
         schedule_start: sea_orm::ActiveValue::Set(Some(start.into())),
         schedule_end: sea_orm::ActiveValue::Set(Some(end.into())),
-        budget_min: sea_orm::ActiveValue::Set(req.budget_min.map(decimal_from_f64)),
-        budget_max: sea_orm::ActiveValue::Set(req.budget_max.map(decimal_from_f64)),
+        budget_min: sea_orm::ActiveValue::Set(req.budget_min.map(Money::amount)),
+        budget_max: sea_orm::ActiveValue::Set(req.budget_max.map(Money::amount)),
         people_count: sea_orm::ActiveValue::Set(req.people_count),
         style_tags: sea_orm::ActiveValue::Set(req.style_tags.map(|v| serde_json::json!(v))),
         status: sea_orm::ActiveValue::Set("open".to_string()),
@@ -99,8 +101,8 @@ pub async fn list_demands(
         status: query.status,
         schedule_start,
         schedule_end,
-        min_budget: query.min_budget.map(decimal_from_f64),
-        max_budget: query.max_budget.map(decimal_from_f64),
+        min_budget: query.min_budget.map(Money::amount),
+        max_budget: query.max_budget.map(Money::amount),
         style_tag: query.style_tag.and_then(|s| {
             let trimmed = s.trim().to_string();
             if trimmed.is_empty() { None } else { Some(trimmed) }
@@ -154,8 +156,8 @@ pub async fn get_demand(
         location: row.location,
         schedule_start: row.schedule_start.map(|d| d.to_rfc3339()),
         schedule_end: row.schedule_end.map(|d| d.to_rfc3339()),
-        budget_min: row.budget_min.map(decimal_to_f64),
-        budget_max: row.budget_max.map(decimal_to_f64),
+        budget_min: row.budget_min.map(Money::from),
+        budget_max: row.budget_max.map(Money::from),
         people_count: row.people_count,
         style_tags: row.style_tags.and_then(|v| serde_json::from_value(v).ok()),
         is_merchant: row.is_merchant,
@@ -164,6 +166,143 @@ pub async fn get_demand(
     })
 }
 
+/// 修改 open 需求并记录修订；已有 pending 报价置为 stale 并通知报价方重新确认。
+pub async fn update_demand(
+    state: &AppState,
+    user_id: i64,
+    demand_id: i64,
+    req: UpdateDemandReq,
+) -> ServiceResult<DemandDetail> {
+    let txn = state.orm.begin().await?;
+    let demand = quotes_repo::find_demand_for_update(&txn, demand_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if demand.user_id != user_id {
+        return Err(DomainError::Forbidden.into());
+    }
+    if demand.status != "open" {
+        return Err(DomainError::BadRequest("demand_not_editable".to_string()).into());
+    }
+
+    let attachments = demands_repo::list_attachments_by_demand(&txn, demand_id).await?;
+    let before = DemandSnapshot::new(&demand, &attachments);
+    let mut after = before.clone();
+    if let Some(location) = req.location {
+        after.location = Some(location);
+    }
+    if let Some(start) = req.schedule_start.as_deref() {
+        after.schedule_start = Some(parse_datetime(start)?.to_rfc3339());
+    }
+    if let Some(end) = req.schedule_end.as_deref() {
+        after.schedule_end = Some(parse_datetime(end)?.to_rfc3339());
+    }
+    if req.budget_min.is_some() {
+        after.budget_min = req.budget_min;
+    }
+    if req.budget_max.is_some() {
+        after.budget_max = req.budget_max;
+    }
+    if req.people_count.is_some() {
+        after.people_count = req.people_count;
+    }
+    if req.style_tags.is_some() {
+        after.style_tags = req.style_tags;
+    }
+    if let Some(list) = req.attachments.as_ref() {
+        after.attachments = list.iter().map(AttachmentSnapshot::from_req).collect();
+    }
+    after.validate()?;
+
+    let changes = snapshot_changes(&before, &after);
+    if changes.is_empty() {
+        return get_demand(state, demand_id).await;
+    }
+
+    let mut model: crate::entity::demands::ActiveModel = demand.into();
+    model.location = sea_orm::ActiveValue::Set(after.location.clone());
+    model.schedule_start = sea_orm::ActiveValue::Set(parse_optional(&after.schedule_start)?);
+    model.schedule_end = sea_orm::ActiveValue::Set(parse_optional(&after.schedule_end)?);
+    model.budget_min = sea_orm::ActiveValue::Set(after.budget_min.map(Money::amount));
+    model.budget_max = sea_orm::ActiveValue::Set(after.budget_max.map(Money::amount));
+    model.people_count = sea_orm::ActiveValue::Set(after.people_count);
+    model.style_tags =
+        sea_orm::ActiveValue::Set(after.style_tags.as_ref().map(|v| serde_json::json!(v)));
+    model.updated_at = sea_orm::ActiveValue::Set(chrono::Utc::now().into());
+    demands_repo::update_demand(&txn, model).await?;
+
+    if let Some(list) = req.attachments {
+        demands_repo::delete_attachments_by_demand(&txn, demand_id).await?;
+        for att in list {
+            let a = crate::entity::demand_attachments::ActiveModel {
+                demand_id: sea_orm::ActiveValue::Set(demand_id),
+                file_url: sea_orm::ActiveValue::Set(att.file_url),
+                file_type: sea_orm::ActiveValue::Set(att.file_type),
+                ..Default::default()
+            };
+            demands_repo::create_attachment(&txn, a).await?;
+        }
+    }
+
+    let revision = demands_repo::next_revision(&txn, demand_id).await?;
+    let changes = serde_json::Value::Object(changes);
+    let snapshot = serde_json::to_value(&after).map_err(anyhow::Error::from)?;
+    let record = crate::entity::demand_revisions::ActiveModel {
+        demand_id: sea_orm::ActiveValue::Set(demand_id),
+        revision: sea_orm::ActiveValue::Set(revision),
+        changes: sea_orm::ActiveValue::Set(changes),
+        snapshot: sea_orm::ActiveValue::Set(snapshot),
+        created_by: sea_orm::ActiveValue::Set(user_id),
+        ..Default::default()
+    };
+    demands_repo::create_revision(&txn, record).await?;
+
+    let stale = quotes_repo::mark_pending_quotes_stale(&txn, demand_id).await?;
+    for quote in &stale {
+        let recipient =
+            provider_access_service::provider_user_id(&txn, quote.photographer_id, quote.team_id)
+                .await?;
+        let Some(recipient) = recipient else {
+            continue;
+        };
+        notifications_service::notify(
+            &txn,
+            recipient,
+            "quote_stale",
+            "需求已变更".to_string(),
+            Some(format!(
+                "需求 #{} 已修改（修订 {}），你的报价 #{} 需重新确认后才能被接受",
+                demand_id, revision, quote.id
+            )),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+
+    get_demand(state, demand_id).await
+}
+
+pub async fn list_demand_revisions(
+    state: &AppState,
+    demand_id: i64,
+) -> ServiceResult<Vec<DemandRevisionItem>> {
+    demands_repo::find_demand_by_id(&state.orm, demand_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    let rows = demands_repo::list_revisions(&state.orm, demand_id).await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| DemandRevisionItem {
+            id: r.id,
+            revision: r.revision,
+            changes: r.changes,
+            snapshot: r.snapshot,
+            created_by: r.created_by,
+            created_at: r.created_at.to_rfc3339(),
+        })
+        .collect())
+}
+
 pub async fn list_demand_merchant_assets(
     state: &AppState,
     user_id: i64,
@@ -260,13 +399,101 @@ fn parse_datetime(input: &str) -> Result<chrono::DateTime<chrono::Utc>, DomainEr
         .map_err(|_| DomainError::BadRequest("invalid_datetime".to_string()))
 }
 
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
+fn parse_optional(
+    input: &Option<String>,
+) -> Result<Option<sea_orm::prelude::DateTimeWithTimeZone>, DomainError> {
+    input
+        .as_deref()
+        .map(|value| parse_datetime(value).map(Into::into))
+        .transpose()
+}
+
+/// 需求可修改字段的快照，用于生成修订记录。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct DemandSnapshot {
+    location: Option<String>,
+    schedule_start: Option<String>,
+    schedule_end: Option<String>,
+    budget_min: Option<Money>,
+    budget_max: Option<Money>,
+    people_count: Option<i32>,
+    style_tags: Option<Vec<String>>,
+    attachments: Vec<AttachmentSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct AttachmentSnapshot {
+    file_url: String,
+    file_type: Option<String>,
+}
+
+impl AttachmentSnapshot {
+    fn from_req(att: &AttachmentReq) -> Self {
+        Self {
+            file_url: att.file_url.clone(),
+            file_type: att.file_type.clone(),
+        }
+    }
 }
 
-fn decimal_from_f64(v: f64) -> sea_orm::prelude::Decimal {
-    sea_orm::prelude::Decimal::from_str(&v.to_string())
-        .unwrap_or(sea_orm::prelude::Decimal::ZERO)
+impl DemandSnapshot {
+    fn new(
+        demand: &crate::entity::demands::Model,
+        attachments: &[crate::entity::demand_attachments::Model],
+    ) -> Self {
+        Self {
+            location: demand.location.clone(),
+            schedule_start: demand.schedule_start.map(|d| d.to_utc().to_rfc3339()),
+            schedule_end: demand.schedule_end.map(|d| d.to_utc().to_rfc3339()),
+            budget_min: demand.budget_min.map(Money::from),
+            budget_max: demand.budget_max.map(Money::from),
+            people_count: demand.people_count,
+            style_tags: demand
+                .style_tags
+                .clone()
+                .and_then(|v| serde_json::from_value(v).ok()),
+            attachments: attachments
+                .iter()
+                .map(|a| AttachmentSnapshot {
+                    file_url: a.file_url.clone(),
+                    file_type: a.file_type.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), DomainError> {
+        if let (Some(start), Some(end)) = (&self.schedule_start, &self.schedule_end)
+            && parse_datetime(start)? >= parse_datetime(end)?
+        {
+            return Err(DomainError::BadRequest("invalid_schedule".to_string()));
+        }
+        if let (Some(min), Some(max)) = (self.budget_min, self.budget_max)
+            && min > max
+        {
+            return Err(DomainError::BadRequest("invalid_budget_range".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 逐字段比较快照，返回 `{ field: { from, to } }`。
+fn snapshot_changes(
+    before: &DemandSnapshot,
+    after: &DemandSnapshot,
+) -> serde_json::Map<String, serde_json::Value> {
+    let (Ok(serde_json::Value::Object(before)), Ok(serde_json::Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return serde_json::Map::new();
+    };
+    after
+        .into_iter()
+        .filter_map(|(field, to)| {
+            let from = before.get(&field).cloned().unwrap_or(serde_json::Value::Null);
+            (from != to).then(|| (field, serde_json::json!({ "from": from, "to": to })))
+        })
+        .collect()
 }
 
 fn normalize_asset_type(value: String) -> Result<String, DomainError> {
@@ -307,11 +534,7 @@ async fn ensure_demand_asset_access(
         .await?;
     let photographer_id = photographer.map(|p| p.id);
 
-    let mut team_ids = teams_repo::list_owned_team_ids(&state.orm, user_id).await?;
-    let members = teams_repo::list_team_members_by_user(&state.orm, user_id).await?;
-    team_ids.extend(members.into_iter().map(|m| m.team_id));
-    team_ids.sort_unstable();
-    team_ids.dedup();
+    let team_ids = provider_access_service::list_team_ids(&state.orm, user_id).await?;
 
     let mut quote_cond = Condition::any();
     let mut has_quote_cond = false;
@@ -357,3 +580,31 @@ async fn ensure_demand_asset_access(
 
     Err(DomainError::Forbidden.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_changes_lists_changed_fields() {
+        let before = DemandSnapshot {
+            location: Some("西湖".to_string()),
+            schedule_start: Some("2026-03-01T09:00:00+00:00".to_string()),
+            schedule_end: Some("2026-03-01T12:00:00+00:00".to_string()),
+            budget_min: None,
+            budget_max: Some(Money::from(sea_orm::prelude::Decimal::new(1000, 0))),
+            people_count: Some(2),
+            style_tags: None,
+            attachments: Vec::new(),
+        };
+        let mut after = before.clone();
+        assert!(snapshot_changes(&before, &after).is_empty());
+
+        after.people_count = Some(4);
+        after.budget_min = Some(Money::from(sea_orm::prelude::Decimal::new(1200, 0)));
+        let changes = snapshot_changes(&before, &after);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes["people_count"], serde_json::json!({ "from": 2, "to": 4 }));
+        assert!(after.validate().is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use crate::errors::ServiceResult;
+use crate::repositories::{demands_repo, quotes_repo};
+use crate::services::{notifications_service, provider_access_service};
+
+/// 过期到期的待处理报价并通知报价方，返回过期数量。
+pub async fn expire_quotes(
+    orm: &DatabaseConnection,
+    now: chrono::DateTime<chrono::Utc>,
+) -> ServiceResult<usize> {
+    let txn = orm.begin().await?;
+    let expired = quotes_repo::expire_due_quotes(&txn, now).await?;
+    for quote in &expired {
+        let recipient =
+            provider_access_service::provider_user_id(&txn, quote.photographer_id, quote.team_id)
+                .await?;
+        let Some(recipient) = recipient else {
+            continue;
+        };
+        notifications_service::notify(
+            &txn,
+            recipient,
+            "quote_expired",
+            "报价已过期".to_string(),
+            Some(format!("你对需求 #{} 的报价 #{} 已超过有效期", quote.demand_id, quote.id)),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(expired.len())
+}
+
+/// 关闭拍摄时间已过的需求，同时过期其下待处理报价，并通知需求发布者与报价方。
+/// 返回关闭的需求数量。
+pub async fn close_stale_demands(
+    orm: &DatabaseConnection,
+    now: chrono::DateTime<chrono::Utc>,
+) -> ServiceResult<usize> {
+    let txn = orm.begin().await?;
+    let closed = demands_repo::close_stale_demands(&txn, now).await?;
+    let demand_ids = closed.iter().map(|d| d.id).collect();
+    let quotes = quotes_repo::expire_pending_quotes_by_demands(&txn, demand_ids, now).await?;
+
+    for demand in &closed {
+        notifications_service::notify(
+            &txn,
+            demand.user_id,
+            "demand_closed",
+            "需求已关闭".to_string(),
+            Some(format!("需求 #{} 的拍摄时间已过，已自动关闭", demand.id)),
+        )
+        .await?;
+    }
+    for quote in &quotes {
+        let recipient =
+            provider_access_service::provider_user_id(&txn, quote.photographer_id, quote.team_id)
+                .await?;
+        let Some(recipient) = recipient else {
+            continue;
+        };
+        notifications_service::notify(
+            &txn,
+            recipient,
+            "quote_expired",
+            "报价已过期".to_string(),
+            Some(format!("需求 #{} 已关闭，你的报价 #{} 已失效", quote.demand_id, quote.id)),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(closed.len())
+}
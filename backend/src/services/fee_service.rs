@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use sea_orm::prelude::Decimal;
+use sea_orm::ConnectionTrait;
+use serde::Deserialize;
+
+use crate::errors::{DomainError, ServiceResult};
+use crate::money::Money;
+use crate::repositories::configs_repo;
+
+/// 平台服务费规则（configs.key），未配置时不收取服务费。
+pub const FEE_CONFIG_KEY: &str = "service_fee_rules";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FeeRule {
+    /// 费率 0 到 1，接受数字或字符串。
+    Percentage {
+        rate: Decimal,
+        #[serde(default)]
+        min: Option<Money>,
+        #[serde(default)]
+        max: Option<Money>,
+    },
+    Fixed {
+        amount: Money,
+    },
+    /// 按订单金额所在档位的费率计算整单服务费，档位按 up_to 升序，最后一档 up_to 为空。
+    Tiered {
+        tiers: Vec<FeeTier>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FeeTier {
+    #[serde(default)]
+    pub up_to: Option<Money>,
+    pub rate: Decimal,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ScopedFeeRules {
+    #[serde(default)]
+    pub individual: Option<FeeRule>,
+    #[serde(default)]
+    pub merchant: Option<FeeRule>,
+}
+
+impl ScopedFeeRules {
+    fn get(&self, is_merchant: bool) -> Option<&FeeRule> {
+        if is_merchant {
+            self.merchant.as_ref()
+        } else {
+            self.individual.as_ref()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct FeeRuleSet {
+    #[serde(flatten)]
+    pub default: ScopedFeeRules,
+    /// 城市覆盖，key 为 city_id。
+    #[serde(default)]
+    pub cities: HashMap<String, ScopedFeeRules>,
+}
+
+impl FeeRuleSet {
+    pub fn parse(value: &serde_json::Value) -> Result<Self, DomainError> {
+        let rules: FeeRuleSet = serde_json::from_value(value.clone())
+            .map_err(|_| DomainError::BadRequest("invalid_fee_rules".to_string()))?;
+        let all = std::iter::once(&rules.default).chain(rules.cities.values());
+        for scoped in all {
+            for rule in [&scoped.individual, &scoped.merchant].into_iter().flatten() {
+                rule.validate()?;
+            }
+        }
+        Ok(rules)
+    }
+
+    /// 取规则顺序：城市+订单类型 → 全局+订单类型；商户订单未单独配置时沿用个人订单规则。
+    pub fn resolve(&self, is_merchant: bool, city_id: Option<i64>) -> Option<&FeeRule> {
+        let city = city_id.and_then(|id| self.cities.get(&id.to_string()));
+        let mut scopes = vec![is_merchant];
+        if is_merchant {
+            scopes.push(false);
+        }
+        scopes.into_iter().find_map(|scope| {
+            city.and_then(|c| c.get(scope))
+                .or_else(|| self.default.get(scope))
+        })
+    }
+}
+
+impl FeeRule {
+    fn validate(&self) -> Result<(), DomainError> {
+        let valid_rate = |rate: Decimal| rate >= Decimal::ZERO && rate <= Decimal::ONE;
+        let ok = match self {
+            FeeRule::Percentage { rate, min, max } => {
+                valid_rate(*rate)
+                    && min.is_none_or(|v| v >= Money::ZERO)
+                    && max.is_none_or(|v| v >= min.unwrap_or(Money::ZERO))
+            }
+            FeeRule::Fixed { amount } => *amount >= Money::ZERO,
+            FeeRule::Tiered { tiers } => {
+                !tiers.is_empty()
+                    && tiers.iter().all(|t| valid_rate(t.rate))
+                    && tiers.last().is_some_and(|t| t.up_to.is_none())
+                    && tiers[..tiers.len() - 1]
+                        .windows(2)
+                        .all(|w| w[0].up_to < w[1].up_to)
+                    && tiers[..tiers.len() - 1].iter().all(|t| t.up_to.is_some())
+            }
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(DomainError::BadRequest("invalid_fee_rules".to_string()))
+        }
+    }
+
+    /// 计算服务费，按 [`Money::mul_ratio`] 舍入到分且不超过订单金额。
+    pub fn compute(&self, total: Money) -> Money {
+        let fee = match self {
+            FeeRule::Percentage { rate, min, max } => {
+                let mut fee = total.mul_ratio(*rate);
+                if let Some(min) = min {
+                    fee = fee.max(*min);
+                }
+                if let Some(max) = max {
+                    fee = fee.min(*max);
+                }
+                fee
+            }
+            FeeRule::Fixed { amount } => *amount,
+            FeeRule::Tiered { tiers } => tiers
+                .iter()
+                .find(|t| t.up_to.is_none_or(|up_to| total <= up_to))
+                .or(tiers.last())
+                .map(|t| total.mul_ratio(t.rate))
+                .unwrap_or(Money::ZERO),
+        };
+        fee.max(Money::ZERO).min(total.max(Money::ZERO))
+    }
+}
+
+pub async fn load_rules<C: ConnectionTrait>(conn: &C) -> ServiceResult<FeeRuleSet> {
+    let Some(config) = configs_repo::find_config_by_key(conn, FEE_CONFIG_KEY).await? else {
+        return Ok(FeeRuleSet::default());
+    };
+    // 写入时已校验；历史脏数据不阻塞下单，按未配置处理。
+    Ok(FeeRuleSet::parse(&config.value).unwrap_or_else(|_| {
+        tracing::warn!("service_fee_rules_invalid");
+        FeeRuleSet::default()
+    }))
+}
+
+pub async fn compute_service_fee<C: ConnectionTrait>(
+    conn: &C,
+    total: Money,
+    is_merchant: bool,
+    city_id: Option<i64>,
+) -> ServiceResult<Money> {
+    let rules = load_rules(conn).await?;
+    Ok(rules
+        .resolve(is_merchant, city_id)
+        .map(|rule| rule.compute(total))
+        .unwrap_or(Money::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn money(v: &str) -> Money {
+        Money::from_str(v).unwrap()
+    }
+
+    #[test]
+    fn test_fee_rule_compute() {
+        let pct = FeeRule::Percentage {
+            rate: Decimal::new(5, 2),
+            min: Some(money("10")),
+            max: Some(money("100")),
+        };
+        assert_eq!(pct.compute(money("100")), money("10"));
+        assert_eq!(pct.compute(money("999.9")), money("50.00"));
+        assert_eq!(pct.compute(money("5000")), money("100"));
+
+        let fixed = FeeRule::Fixed { amount: money("30") };
+        assert_eq!(fixed.compute(money("20")), money("20"));
+
+        let tiered = FeeRule::Tiered {
+            tiers: vec![
+                FeeTier { up_to: Some(money("1000")), rate: Decimal::new(1, 1) },
+                FeeTier { up_to: None, rate: Decimal::new(6, 2) },
+            ],
+        };
+        assert_eq!(tiered.compute(money("1000")), money("100.0"));
+        assert_eq!(tiered.compute(money("2000")), money("120.00"));
+    }
+
+    #[test]
+    fn test_rule_set_resolves_city_and_scope() {
+        let rules = FeeRuleSet::parse(&serde_json::json!({
+            "individual": { "type": "percentage", "rate": 0.05 },
+            "merchant": { "type": "fixed", "amount": 50 },
+            "cities": {
+                "310100": { "individual": { "type": "percentage", "rate": "0.08" } }
+            }
+        }))
+        .unwrap();
+
+        let rate = |rule: Option<&FeeRule>| rule.map(|r| r.compute(money("100")));
+        assert_eq!(rate(rules.resolve(false, None)), Some(money("5.00")));
+        assert_eq!(rate(rules.resolve(false, Some(310100))), Some(money("8.00")));
+        assert_eq!(rate(rules.resolve(true, Some(310100))), Some(money("50")));
+        assert_eq!(FeeRuleSet::default().resolve(true, None), None);
+    }
+
+    #[test]
+    fn test_rule_set_rejects_invalid() {
+        assert!(FeeRuleSet::parse(&serde_json::json!({
+            "individual": { "type": "percentage", "rate": 1.5 }
+        }))
+        .is_err());
+        assert!(FeeRuleSet::parse(&serde_json::json!({
+            "merchant": { "type": "tiered", "tiers": [{ "up_to": 100, "rate": 0.1 }] }
+        }))
+        .is_err());
+        assert!(FeeRuleSet::parse(&serde_json::json!({
+            "individual": { "type": "bogus" }
+        }))
+        .is_err());
+    }
+}
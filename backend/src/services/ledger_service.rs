@@ -0,0 +1,438 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use sea_orm::{ActiveValue::Set, ConnectionTrait};
+
+use crate::dto::ledger::{
+    LedgerAccountQuery, LedgerAccountResp, LedgerReconciliationItem, LedgerReconciliationQuery,
+    LedgerStatementItem, LedgerStatementQuery, LedgerStatementResp,
+};
+use crate::dto::pagination::{normalize_pagination, Paged};
+use crate::entity::{ledger_accounts, ledger_entries, ledger_lines, orders, payments, refunds};
+use crate::errors::{DomainError, ServiceResult};
+use crate::money::Money;
+use crate::repositories::{ledger_repo, orders_repo};
+use crate::services::roles_service::ensure_permission;
+use crate::state::AppState;
+
+/// 平台账户只有一个，owner_id 固定为 0。
+pub const PLATFORM_OWNER_ID: i64 = 0;
+const RECONCILIATION_MAX_DAYS: i64 = 31;
+
+/// 账户归属。分录行金额为正表示流入该账户，为负表示流出，每笔分录各行之和为零：
+/// - 支付：用户 -A，承接方 +A
+/// - 服务费计提：承接方 -F，平台 +F；订单取消时冲销
+/// - 退款完成：承接方 -R，用户 +R
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountOwner {
+    User(i64),
+    Photographer(i64),
+    Team(i64),
+    Platform,
+}
+
+impl AccountOwner {
+    pub fn parse(owner_type: &str, owner_id: i64) -> Result<Self, DomainError> {
+        match owner_type {
+            "user" => Ok(AccountOwner::User(owner_id)),
+            "photographer" => Ok(AccountOwner::Photographer(owner_id)),
+            "team" => Ok(AccountOwner::Team(owner_id)),
+            "platform" => Ok(AccountOwner::Platform),
+            _ => Err(DomainError::BadRequest("invalid_owner_type".to_string())),
+        }
+    }
+
+    pub fn owner_type(self) -> &'static str {
+        match self {
+            AccountOwner::User(_) => "user",
+            AccountOwner::Photographer(_) => "photographer",
+            AccountOwner::Team(_) => "team",
+            AccountOwner::Platform => "platform",
+        }
+    }
+
+    pub fn owner_id(self) -> i64 {
+        match self {
+            AccountOwner::User(id) | AccountOwner::Photographer(id) | AccountOwner::Team(id) => id,
+            AccountOwner::Platform => PLATFORM_OWNER_ID,
+        }
+    }
+
+    /// 订单承接方：个人摄影师优先，其次团队。
+    pub fn provider_of(order: &orders::Model) -> Option<Self> {
+        order
+            .photographer_id
+            .map(AccountOwner::Photographer)
+            .or(order.team_id.map(AccountOwner::Team))
+    }
+}
+
+struct EntryMeta<'a> {
+    entry_type: &'a str,
+    order_id: Option<i64>,
+    reference_type: &'a str,
+    reference_id: i64,
+    memo: Option<String>,
+}
+
+/// 支付成功记账，需与支付状态更新在同一事务内调用。
+pub async fn record_payment<C: ConnectionTrait>(
+    conn: &C,
+    payment: &payments::Model,
+    order: &orders::Model,
+) -> ServiceResult<()> {
+    let Some(provider) = AccountOwner::provider_of(order) else {
+        tracing::warn!(payment_id = payment.id, order_id = order.id, "ledger_provider_missing");
+        return Ok(());
+    };
+    let amount = Money::from(payment.amount);
+    let meta = EntryMeta {
+        entry_type: "payment",
+        order_id: Some(order.id),
+        reference_type: "payment",
+        reference_id: payment.id,
+        memo: Some(payment.pay_channel.clone()),
+    };
+    post_entry(conn, meta, &[(AccountOwner::User(payment.payer_id), -amount), (provider, amount)])
+        .await
+}
+
+/// 订单付清时计提平台服务费，重复调用只记一次。
+pub async fn accrue_service_fee<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+) -> ServiceResult<()> {
+    let fee = Money::from(order.service_fee);
+    if !fee.is_positive() {
+        return Ok(());
+    }
+    let Some(provider) = AccountOwner::provider_of(order) else {
+        tracing::warn!(order_id = order.id, "ledger_provider_missing");
+        return Ok(());
+    };
+    let meta = EntryMeta {
+        entry_type: "service_fee",
+        order_id: Some(order.id),
+        reference_type: "order",
+        reference_id: order.id,
+        memo: None,
+    };
+    post_entry(conn, meta, &[(provider, -fee), (AccountOwner::Platform, fee)]).await
+}
+
+/// 订单取消时按原分录反向冲销已计提的服务费。
+pub async fn reverse_service_fee<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+) -> ServiceResult<()> {
+    let Some(accrual) =
+        ledger_repo::find_entry_by_reference(conn, "service_fee", "order", order.id).await?
+    else {
+        return Ok(());
+    };
+    let lines: Vec<(i64, Money)> = ledger_repo::list_lines_by_entries(conn, vec![accrual.id])
+        .await?
+        .into_iter()
+        .map(|l| (l.account_id, -Money::from(l.amount)))
+        .collect();
+    let meta = EntryMeta {
+        entry_type: "service_fee_reversal",
+        order_id: Some(order.id),
+        reference_type: "order",
+        reference_id: order.id,
+        memo: Some(format!("reverses entry {}", accrual.id)),
+    };
+    write_entry(conn, meta, lines).await
+}
+
+/// 退款完成（款项已退回）时记账。
+pub async fn record_refund<C: ConnectionTrait>(
+    conn: &C,
+    refund: &refunds::Model,
+    order: &orders::Model,
+) -> ServiceResult<()> {
+    let Some(provider) = AccountOwner::provider_of(order) else {
+        tracing::warn!(refund_id = refund.id, order_id = order.id, "ledger_provider_missing");
+        return Ok(());
+    };
+    let amount = Money::from(refund.approved_amount.unwrap_or(refund.amount));
+    let meta = EntryMeta {
+        entry_type: "refund",
+        order_id: Some(order.id),
+        reference_type: "refund",
+        reference_id: refund.id,
+        memo: None,
+    };
+    post_entry(conn, meta, &[(provider, -amount), (AccountOwner::User(order.user_id), amount)])
+        .await
+}
+
+/// 账户当前余额，账户不存在时为零。
+pub async fn account_balance<C: ConnectionTrait>(
+    conn: &C,
+    owner: AccountOwner,
+) -> ServiceResult<Money> {
+    let Some(account) =
+        ledger_repo::find_account(conn, owner.owner_type(), owner.owner_id()).await?
+    else {
+        return Ok(Money::ZERO);
+    };
+    Ok(Money::from(ledger_repo::sum_account_before(conn, account.id, None).await?))
+}
+
+pub async fn list_accounts(
+    state: &AppState,
+    operator_id: i64,
+    query: LedgerAccountQuery,
+) -> ServiceResult<Paged<LedgerAccountResp>> {
+    ensure_permission(&state.orm, operator_id, "ledger.read").await?;
+    if let Some(owner_type) = query.owner_type.as_deref() {
+        AccountOwner::parse(owner_type, 0)?;
+    }
+    let (page, page_size) = normalize_pagination(query.page, query.page_size);
+    let (rows, total) = ledger_repo::list_accounts(
+        &state.orm,
+        query.owner_type,
+        query.owner_id,
+        page,
+        page_size,
+    )
+    .await?;
+    let balances =
+        ledger_repo::sum_balances(&state.orm, rows.iter().map(|a| a.id).collect()).await?;
+    let items = rows
+        .into_iter()
+        .map(|a| {
+            let balance = balances.get(&a.id).copied().map(Money::from).unwrap_or_default();
+            to_account_resp(a, balance)
+        })
+        .collect();
+    Ok(Paged::new(items, total, page, page_size))
+}
+
+pub async fn get_statement(
+    state: &AppState,
+    operator_id: i64,
+    account_id: i64,
+    query: LedgerStatementQuery,
+) -> ServiceResult<LedgerStatementResp> {
+    ensure_permission(&state.orm, operator_id, "ledger.read").await?;
+    let account = ledger_repo::find_account_by_id(&state.orm, account_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let start = query.start_date.as_deref().map(parse_date).transpose()?;
+    let end = query
+        .end_date
+        .as_deref()
+        .map(parse_date)
+        .transpose()?
+        .map(|d| d + Duration::days(1));
+    if let (Some(start), Some(end)) = (start, end)
+        && start >= end
+    {
+        return Err(DomainError::BadRequest("invalid_date_range".to_string()).into());
+    }
+
+    let (page, page_size) = normalize_pagination(query.page, query.page_size);
+    let opening = match start {
+        Some(start) => {
+            Money::from(ledger_repo::sum_account_before(&state.orm, account.id, Some(start)).await?)
+        }
+        None => Money::ZERO,
+    };
+    let closing = Money::from(ledger_repo::sum_account_before(&state.orm, account.id, end).await?);
+    let (lines, total) =
+        ledger_repo::list_account_lines(&state.orm, account.id, start, end, page, page_size)
+            .await?;
+    let entry_ids = lines.iter().map(|l| l.entry_id).collect();
+    let entries: HashMap<i64, ledger_entries::Model> =
+        ledger_repo::list_entries_by_ids(&state.orm, entry_ids)
+            .await?
+            .into_iter()
+            .map(|e| (e.id, e))
+            .collect();
+    let items = lines
+        .into_iter()
+        .filter_map(|l| entries.get(&l.entry_id).map(|e| to_statement_item(l, e)))
+        .collect();
+    let balance = Money::from(ledger_repo::sum_account_before(&state.orm, account.id, None).await?);
+
+    Ok(LedgerStatementResp {
+        account: to_account_resp(account, balance),
+        opening_balance: opening,
+        closing_balance: closing,
+        lines: Paged::new(items, total, page, page_size),
+    })
+}
+
+/// 按支付渠道核对区间内成功支付与账本支付分录，列出未入账的支付。
+pub async fn reconcile_payments(
+    state: &AppState,
+    operator_id: i64,
+    query: LedgerReconciliationQuery,
+) -> ServiceResult<Vec<LedgerReconciliationItem>> {
+    ensure_permission(&state.orm, operator_id, "ledger.read").await?;
+    let start = parse_date(&query.start_date)?;
+    let end = parse_date(&query.end_date)? + Duration::days(1);
+    if start >= end || end - start > Duration::days(RECONCILIATION_MAX_DAYS) {
+        return Err(DomainError::BadRequest("invalid_date_range".to_string()).into());
+    }
+
+    let payments = orders_repo::list_success_payments_paid_between(&state.orm, start, end).await?;
+    let payment_ids = payments.iter().map(|p| p.id).collect();
+    let entries =
+        ledger_repo::list_entries_by_references(&state.orm, "payment", "payment", payment_ids)
+            .await?;
+    let entry_by_payment: HashMap<i64, i64> =
+        entries.iter().map(|e| (e.reference_id, e.id)).collect();
+    let lines =
+        ledger_repo::list_lines_by_entries(&state.orm, entries.iter().map(|e| e.id).collect())
+            .await?;
+    // 支付分录的入账金额取正向行之和。
+    let mut posted: HashMap<i64, Money> = HashMap::new();
+    for line in lines {
+        let amount = Money::from(line.amount);
+        if amount.is_positive() {
+            *posted.entry(line.entry_id).or_default() += amount;
+        }
+    }
+
+    let mut by_channel: BTreeMap<String, LedgerReconciliationItem> = BTreeMap::new();
+    for payment in payments {
+        let item = by_channel
+            .entry(payment.pay_channel.clone())
+            .or_insert_with(|| LedgerReconciliationItem {
+                pay_channel: payment.pay_channel.clone(),
+                payment_count: 0,
+                payment_total: Money::ZERO,
+                ledger_total: Money::ZERO,
+                missing_payment_ids: Vec::new(),
+            });
+        item.payment_count += 1;
+        item.payment_total += Money::from(payment.amount);
+        match entry_by_payment.get(&payment.id) {
+            Some(entry_id) => {
+                item.ledger_total += posted.get(entry_id).copied().unwrap_or_default();
+            }
+            None => item.missing_payment_ids.push(payment.id),
+        }
+    }
+    Ok(by_channel.into_values().collect())
+}
+
+async fn post_entry<C: ConnectionTrait>(
+    conn: &C,
+    meta: EntryMeta<'_>,
+    postings: &[(AccountOwner, Money)],
+) -> ServiceResult<()> {
+    let mut lines = Vec::with_capacity(postings.len());
+    for (owner, amount) in postings {
+        let account =
+            ledger_repo::ensure_account(conn, owner.owner_type(), owner.owner_id()).await?;
+        lines.push((account.id, *amount));
+    }
+    write_entry(conn, meta, lines).await
+}
+
+/// 写入分录及各行；同一业务引用已记账时跳过（回调重试、重复调用）。
+async fn write_entry<C: ConnectionTrait>(
+    conn: &C,
+    meta: EntryMeta<'_>,
+    lines: Vec<(i64, Money)>,
+) -> ServiceResult<()> {
+    let lines = balanced_lines(lines).ok_or_else(|| {
+        anyhow::anyhow!("unbalanced ledger entry {} {}", meta.entry_type, meta.reference_id)
+    })?;
+    let entry = ledger_entries::ActiveModel {
+        entry_type: Set(meta.entry_type.to_string()),
+        order_id: Set(meta.order_id),
+        reference_type: Set(meta.reference_type.to_string()),
+        reference_id: Set(meta.reference_id),
+        memo: Set(meta.memo),
+        ..Default::default()
+    };
+    let Some(entry_id) = ledger_repo::try_insert_entry(conn, entry).await? else {
+        return Ok(());
+    };
+    for (account_id, amount) in lines {
+        let line = ledger_lines::ActiveModel {
+            entry_id: Set(entry_id),
+            account_id: Set(account_id),
+            amount: Set(amount.amount()),
+            ..Default::default()
+        };
+        ledger_repo::create_line(conn, line).await?;
+    }
+    Ok(())
+}
+
+/// 去掉零金额行后至少两行且合计为零才可记账。
+fn balanced_lines(lines: Vec<(i64, Money)>) -> Option<Vec<(i64, Money)>> {
+    let lines: Vec<(i64, Money)> = lines.into_iter().filter(|(_, m)| !m.is_zero()).collect();
+    let total: Money = lines.iter().map(|(_, m)| *m).sum();
+    (lines.len() >= 2 && total.is_zero()).then_some(lines)
+}
+
+fn to_account_resp(account: ledger_accounts::Model, balance: Money) -> LedgerAccountResp {
+    LedgerAccountResp {
+        id: account.id,
+        owner_type: account.owner_type,
+        owner_id: account.owner_id,
+        balance,
+        created_at: account.created_at.to_rfc3339(),
+    }
+}
+
+fn to_statement_item(
+    line: ledger_lines::Model,
+    entry: &ledger_entries::Model,
+) -> LedgerStatementItem {
+    LedgerStatementItem {
+        line_id: line.id,
+        entry_id: entry.id,
+        entry_type: entry.entry_type.clone(),
+        order_id: entry.order_id,
+        reference_type: entry.reference_type.clone(),
+        reference_id: entry.reference_id,
+        memo: entry.memo.clone(),
+        amount: Money::from(line.amount),
+        created_at: line.created_at.to_rfc3339(),
+    }
+}
+
+fn parse_date(value: &str) -> Result<chrono::DateTime<Utc>, DomainError> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| DomainError::BadRequest("invalid_date".to_string()))?;
+    Ok(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn money(v: &str) -> Money {
+        Money::from_str(v).unwrap()
+    }
+
+    #[test]
+    fn test_balanced_lines() {
+        let ok = balanced_lines(vec![(1, -money("800")), (2, money("800")), (3, Money::ZERO)]);
+        assert_eq!(ok, Some(vec![(1, -money("800")), (2, money("800"))]));
+        assert_eq!(balanced_lines(vec![(1, -money("800")), (2, money("799.99"))]), None);
+        assert_eq!(balanced_lines(vec![(1, Money::ZERO), (2, Money::ZERO)]), None);
+    }
+
+    #[test]
+    fn test_account_owner_roundtrip() {
+        for owner in [
+            AccountOwner::User(7),
+            AccountOwner::Photographer(3),
+            AccountOwner::Team(2),
+            AccountOwner::Platform,
+        ] {
+            assert_eq!(AccountOwner::parse(owner.owner_type(), owner.owner_id()).ok(), Some(owner));
+        }
+        assert!(AccountOwner::parse("bank", 1).is_err());
+    }
+}
@@ -1,5 +1,4 @@
 use std::collections::{HashMap, HashSet};
-use std::str::FromStr;
 
 use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 use sea_orm::{
@@ -21,6 +20,7 @@ use crate::dto::merchants::{
 use crate::dto::pagination::{normalize_pagination, Paged};
 use crate::entity::{orders, payments, refunds};
 use crate::errors::{DomainError, ServiceResult};
+use crate::money::Money;
 use crate::repositories::merchants_repo;
 use crate::state::AppState;
 
@@ -288,7 +288,7 @@ pub async fn create_template(
             inserted.id,
             item.name,
             item.quantity,
-            decimal_from_f64(item.price),
+            item.price.amount(),
         )
         .await?;
     }
@@ -337,7 +337,7 @@ pub async fn list_templates(
             .push(TemplateItemResp {
                 name: item.name,
                 quantity: item.quantity,
-                price: decimal_to_f64(item.price),
+                price: Money::from(item.price),
             });
     }
 
@@ -471,7 +471,7 @@ pub async fn create_invoice(
     state: &AppState,
     req: CreateInvoiceReq,
 ) -> ServiceResult<InvoiceResp> {
-    if req.amount <= 0.0 {
+    if !req.amount.is_positive() {
         return Err(DomainError::InvalidAmount.into());
     }
 
@@ -481,7 +481,7 @@ pub async fn create_invoice(
         req.order_id,
         req.title,
         req.tax_no,
-        decimal_from_f64(req.amount),
+        req.amount.amount(),
         "pending".to_string(),
     )
     .await?;
@@ -522,7 +522,7 @@ pub async fn list_invoices(
             order_id: row.order_id,
             title: row.title,
             tax_no: row.tax_no,
-            amount: decimal_to_f64(row.amount),
+            amount: Money::from(row.amount),
             status: row.status,
             created_at: row.created_at.to_rfc3339(),
         })
@@ -728,7 +728,7 @@ pub async fn list_merchant_orders(
         .map(|row| MerchantOrderListItem {
             id: row.id,
             status: row.status,
-            total_amount: decimal_to_f64(row.total_amount),
+            total_amount: Money::from(row.total_amount),
             demand_id: row.demand_id,
             created_at: row.created_at.to_rfc3339(),
         })
@@ -758,7 +758,7 @@ pub async fn get_merchant_order(
         .into_iter()
         .map(|it| MerchantOrderItem {
             name: it.name,
-            price: decimal_to_f64(it.price),
+            price: Money::from(it.price),
             quantity: it.quantity,
         })
         .collect();
@@ -771,8 +771,8 @@ pub async fn get_merchant_order(
         id: order.id,
         status: order.status,
         pay_type: order.pay_type,
-        total_amount: decimal_to_f64(order.total_amount),
-        service_fee: decimal_to_f64(order.service_fee),
+        total_amount: Money::from(order.total_amount),
+        service_fee: Money::from(order.service_fee),
         schedule_start: order.schedule_start.map(|t| t.to_rfc3339()),
         schedule_end: order.schedule_end.map(|t| t.to_rfc3339()),
         user_id: order.user_id,
@@ -849,10 +849,10 @@ pub async fn export_merchant_orders_report(
         .filter(payments::Column::Status.eq("success"))
         .all(&state.orm)
         .await?;
-    let mut paid_map: HashMap<i64, f64> = HashMap::new();
+    let mut paid_map: HashMap<i64, Money> = HashMap::new();
     for row in payment_rows {
-        let entry = paid_map.entry(row.order_id).or_insert(0.0);
-        *entry += decimal_to_f64(row.amount);
+        let entry = paid_map.entry(row.order_id).or_insert(Money::ZERO);
+        *entry += Money::from(row.amount);
     }
 
     let refund_rows = refunds::Entity::find()
@@ -860,10 +860,10 @@ pub async fn export_merchant_orders_report(
         .filter(refunds::Column::Status.eq("paid"))
         .all(&state.orm)
         .await?;
-    let mut refund_map: HashMap<i64, f64> = HashMap::new();
+    let mut refund_map: HashMap<i64, Money> = HashMap::new();
     for row in refund_rows {
-        let entry = refund_map.entry(row.order_id).or_insert(0.0);
-        *entry += decimal_to_f64(row.amount);
+        let entry = refund_map.entry(row.order_id).or_insert(Money::ZERO);
+        *entry += Money::from(row.amount);
     }
 
     let items: Vec<MerchantOrderReportItem> = rows
@@ -872,9 +872,9 @@ pub async fn export_merchant_orders_report(
             id: row.id,
             demand_id: row.demand_id,
             status: row.status,
-            total_amount: decimal_to_f64(row.total_amount),
-            paid_amount: paid_map.get(&row.id).cloned().unwrap_or(0.0),
-            refund_amount: refund_map.get(&row.id).cloned().unwrap_or(0.0),
+            total_amount: Money::from(row.total_amount),
+            paid_amount: paid_map.get(&row.id).copied().unwrap_or_default(),
+            refund_amount: refund_map.get(&row.id).copied().unwrap_or_default(),
             created_at: row.created_at.to_rfc3339(),
         })
         .collect();
@@ -1056,15 +1056,6 @@ fn normalize_asset_status(value: String) -> Result<String, DomainError> {
     }
 }
 
-fn decimal_from_f64(v: f64) -> sea_orm::prelude::Decimal {
-    sea_orm::prelude::Decimal::from_str(&v.to_string())
-        .unwrap_or(sea_orm::prelude::Decimal::ZERO)
-}
-
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
-}
-
 fn parse_date(value: &str) -> ServiceResult<DateTime<Utc>> {
     let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
         .map_err(|_| DomainError::BadRequest("invalid_date".to_string()))?;
@@ -2,6 +2,7 @@ use crate::dto::messages::{CreateMessageReq, ListMessagesQuery, MessageItem, Mes
 use crate::dto::pagination::{normalize_pagination, Paged};
 use crate::errors::{DomainError, ServiceResult};
 use crate::repositories::{conversations_repo, messages_repo};
+use crate::services::provider_access_service::{self, ProviderAction};
 use crate::state::AppState;
 
 pub async fn send_message(
@@ -72,18 +73,12 @@ async fn ensure_conversation_participant(
     let order = conversations_repo::find_order_by_id(&state.orm, order_id)
         .await?
         .ok_or(DomainError::NotFound)?;
-    if order.user_id == user_id {
-        return Ok(());
-    }
-    let photographer_user = match order.photographer_id {
-        Some(pid) => conversations_repo::find_photographer_by_id(&state.orm, pid)
-            .await?
-            .map(|p| p.user_id),
-        None => None,
-    };
-    if photographer_user == Some(user_id) {
-        Ok(())
-    } else {
-        Err(DomainError::Forbidden.into())
-    }
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::Chat,
+    )
+    .await?;
+    Ok(())
 }
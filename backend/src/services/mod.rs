@@ -1,13 +1,23 @@
 pub mod configs_service;
 pub mod admin_service;
 pub mod demands_service;
+pub mod expiry_service;
+pub mod fee_service;
+pub mod ledger_service;
 pub mod conversations_service;
 pub mod merchants_service;
 pub mod messages_service;
 pub mod notifications_service;
+pub mod order_state_service;
 pub mod orders_service;
+pub mod payment_plan_service;
 pub mod payments_service;
 pub mod quotes_service;
+pub mod refund_policy_service;
 pub mod refunds_service;
+pub mod roles_service;
+pub mod sessions_service;
 pub mod teams_service;
+pub mod users_service;
 pub mod photographers_service;
+pub mod provider_access_service;
@@ -6,6 +6,18 @@ use crate::errors::{DomainError, ServiceResult};
 use crate::repositories::notifications_repo;
 use crate::state::AppState;
 
+/// 写入一条站内通知，可在业务事务内调用。
+pub async fn notify<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    user_id: i64,
+    r#type: &str,
+    title: String,
+    content: Option<String>,
+) -> ServiceResult<()> {
+    notifications_repo::create_notification(conn, user_id, r#type, title, content).await?;
+    Ok(())
+}
+
 pub async fn list_notifications(
     state: &AppState,
     user_id: i64,
@@ -0,0 +1,301 @@
+use sea_orm::ConnectionTrait;
+
+use crate::entity::{order_status_history, orders};
+use crate::errors::{DomainError, ServiceResult};
+use crate::repositories::orders_repo;
+use crate::services::payment_plan_service::{self, StageBalance};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderStatus {
+    Confirmed,
+    Paid,
+    Ongoing,
+    Completed,
+    Reviewed,
+    Cancelled,
+    Frozen,
+}
+
+impl OrderStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OrderStatus::Confirmed => "confirmed",
+            OrderStatus::Paid => "paid",
+            OrderStatus::Ongoing => "ongoing",
+            OrderStatus::Completed => "completed",
+            OrderStatus::Reviewed => "reviewed",
+            OrderStatus::Cancelled => "cancelled",
+            OrderStatus::Frozen => "frozen",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "confirmed" => Some(OrderStatus::Confirmed),
+            "paid" => Some(OrderStatus::Paid),
+            "ongoing" => Some(OrderStatus::Ongoing),
+            "completed" => Some(OrderStatus::Completed),
+            "reviewed" => Some(OrderStatus::Reviewed),
+            "cancelled" => Some(OrderStatus::Cancelled),
+            "frozen" => Some(OrderStatus::Frozen),
+            _ => None,
+        }
+    }
+
+    pub fn of(order: &orders::Model) -> ServiceResult<Self> {
+        Self::parse(&order.status).ok_or_else(|| DomainError::InvalidStatus.into())
+    }
+
+    /// 状态机允许的目标状态。confirmed → ongoing 另需首阶段款项已付清，
+    /// 由 [`transition_order`] 校验。
+    pub fn allowed_transitions(self) -> &'static [OrderStatus] {
+        use OrderStatus::*;
+        match self {
+            Confirmed => &[Paid, Ongoing, Cancelled, Frozen],
+            Paid => &[Ongoing, Cancelled, Frozen],
+            Ongoing => &[Completed, Cancelled, Frozen],
+            Completed => &[Reviewed, Frozen],
+            Reviewed => &[Frozen],
+            Cancelled => &[Frozen],
+            Frozen => &[],
+        }
+    }
+
+    pub fn can_transition_to(self, to: OrderStatus) -> bool {
+        self.allowed_transitions().contains(&to)
+    }
+
+    pub fn accepts_payment(self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Confirmed | OrderStatus::Paid | OrderStatus::Ongoing | OrderStatus::Completed
+        )
+    }
+}
+
+pub fn ensure_not_frozen(order: &orders::Model) -> ServiceResult<()> {
+    if OrderStatus::of(order)? == OrderStatus::Frozen {
+        return Err(DomainError::OrderFrozen.into());
+    }
+    Ok(())
+}
+
+pub async fn transition_order<C: ConnectionTrait>(
+    conn: &C,
+    order: orders::Model,
+    to: OrderStatus,
+    actor_id: Option<i64>,
+    reason: Option<String>,
+) -> ServiceResult<orders::Model> {
+    let from = OrderStatus::of(&order)?;
+    if from == OrderStatus::Frozen {
+        return Err(DomainError::OrderFrozen.into());
+    }
+    if !from.can_transition_to(to) {
+        return Err(DomainError::BadRequest("invalid_order_transition".to_string()).into());
+    }
+    // 定金/分期订单付清首阶段后即可开工，未付款的订单不能直接进入 ongoing。
+    if from == OrderStatus::Confirmed && to == OrderStatus::Ongoing {
+        let now = chrono::Utc::now();
+        let plan = payment_plan_service::load_or_create_plan(conn, &order).await?;
+        let payments = orders_repo::list_payments(conn, order.id).await?;
+        ensure_first_stage_paid(&payment_plan_service::summarize(&plan, &payments, now))?;
+    }
+
+    apply_transition(conn, order, from, to, actor_id, reason).await
+}
+
+fn ensure_first_stage_paid(balances: &[StageBalance]) -> Result<(), DomainError> {
+    match balances.first() {
+        Some(first) if first.outstanding().is_zero() => Ok(()),
+        _ => Err(DomainError::BadRequest("deposit_unpaid".to_string())),
+    }
+}
+
+/// 解冻订单。冻结前状态取自状态历史；早期版本冻结时未写历史，
+/// 此时需由管理员通过 `requested` 指定恢复的状态。
+pub async fn unfreeze_order<C: ConnectionTrait>(
+    conn: &C,
+    order: orders::Model,
+    requested: Option<OrderStatus>,
+    actor_id: Option<i64>,
+    reason: Option<String>,
+) -> ServiceResult<orders::Model> {
+    if OrderStatus::of(&order)? != OrderStatus::Frozen {
+        return Err(DomainError::BadRequest("order_not_frozen".to_string()).into());
+    }
+
+    let frozen = OrderStatus::Frozen.as_str();
+    let frozen_from = orders_repo::find_last_status_change_to(conn, order.id, frozen)
+        .await?
+        .and_then(|h| h.from_status)
+        .and_then(|s| OrderStatus::parse(&s))
+        .filter(|s| *s != OrderStatus::Frozen);
+    let restore_to = restore_status(frozen_from, requested)?;
+
+    apply_transition(conn, order, OrderStatus::Frozen, restore_to, actor_id, reason).await
+}
+
+fn restore_status(
+    frozen_from: Option<OrderStatus>,
+    requested: Option<OrderStatus>,
+) -> Result<OrderStatus, DomainError> {
+    match (frozen_from, requested) {
+        (Some(from), Some(to)) if from != to => {
+            Err(DomainError::BadRequest("restore_status_mismatch".to_string()))
+        }
+        (Some(from), _) => Ok(from),
+        // 只能恢复到本可被冻结的状态。
+        (None, Some(to)) if to.can_transition_to(OrderStatus::Frozen) => Ok(to),
+        (None, Some(_)) => Err(DomainError::BadRequest("invalid_restore_status".to_string())),
+        (None, None) => Err(DomainError::BadRequest("restore_status_required".to_string())),
+    }
+}
+
+async fn apply_transition<C: ConnectionTrait>(
+    conn: &C,
+    order: orders::Model,
+    from: OrderStatus,
+    to: OrderStatus,
+    actor_id: Option<i64>,
+    reason: Option<String>,
+) -> ServiceResult<orders::Model> {
+    let order_id = order.id;
+    let updated = if to == OrderStatus::Cancelled && from != OrderStatus::Frozen {
+        orders_repo::update_order_status_cancelled(conn, order, chrono::Utc::now()).await?
+    } else {
+        orders_repo::update_order_status(conn, order, to.as_str().to_string()).await?
+    };
+
+    orders_repo::create_status_history(
+        conn,
+        order_id,
+        Some(from.as_str().to_string()),
+        to.as_str().to_string(),
+        actor_id,
+        reason,
+    )
+    .await?;
+
+    Ok(updated)
+}
+
+pub async fn record_initial_status<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+    actor_id: Option<i64>,
+    reason: Option<String>,
+) -> ServiceResult<order_status_history::Model> {
+    let status = OrderStatus::of(order)?;
+    Ok(orders_repo::create_status_history(
+        conn,
+        order.id,
+        None,
+        status.as_str().to_string(),
+        actor_id,
+        reason,
+    )
+    .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::*;
+    use crate::money::Money;
+
+    #[test]
+    fn test_happy_path_transitions() {
+        assert!(OrderStatus::Confirmed.can_transition_to(OrderStatus::Paid));
+        assert!(OrderStatus::Paid.can_transition_to(OrderStatus::Ongoing));
+        assert!(OrderStatus::Ongoing.can_transition_to(OrderStatus::Completed));
+        assert!(OrderStatus::Completed.can_transition_to(OrderStatus::Reviewed));
+    }
+
+    #[test]
+    fn test_illegal_transitions_rejected() {
+        assert!(!OrderStatus::Confirmed.can_transition_to(OrderStatus::Reviewed));
+        assert!(!OrderStatus::Paid.can_transition_to(OrderStatus::Completed));
+        assert!(!OrderStatus::Completed.can_transition_to(OrderStatus::Cancelled));
+        assert!(!OrderStatus::Cancelled.can_transition_to(OrderStatus::Paid));
+        assert!(!OrderStatus::Frozen.can_transition_to(OrderStatus::Ongoing));
+    }
+
+    #[test]
+    fn test_ongoing_requires_first_stage_paid() {
+        let balance = |amount: i64, paid: i64| StageBalance {
+            stage: "deposit".to_string(),
+            amount: Money::from(Decimal::from(amount)),
+            due_at: None,
+            paid: Money::from(Decimal::from(paid)),
+            pending: Money::ZERO,
+        };
+        assert!(ensure_first_stage_paid(&[balance(300, 300), balance(700, 0)]).is_ok());
+        assert!(ensure_first_stage_paid(&[balance(300, 299), balance(700, 0)]).is_err());
+        assert!(ensure_first_stage_paid(&[]).is_err());
+    }
+
+    fn order_with_status(status: &str) -> orders::Model {
+        let now = chrono::Utc::now().into();
+        orders::Model {
+            id: 1,
+            user_id: 1,
+            photographer_id: None,
+            team_id: None,
+            demand_id: None,
+            quote_id: None,
+            status: status.to_string(),
+            pay_type: "deposit".to_string(),
+            deposit_amount: sea_orm::prelude::Decimal::ZERO,
+            total_amount: sea_orm::prelude::Decimal::ZERO,
+            service_fee: sea_orm::prelude::Decimal::ZERO,
+            schedule_start: None,
+            schedule_end: None,
+            cancelled_at: None,
+            created_at: now,
+            updated_at: now,
+            refund_policy: None,
+            quote_version: None,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_ensure_not_frozen() {
+        assert!(ensure_not_frozen(&order_with_status("paid")).is_ok());
+        assert!(matches!(
+            ensure_not_frozen(&order_with_status("frozen")),
+            Err(crate::errors::ServiceError::Domain(DomainError::OrderFrozen))
+        ));
+    }
+
+    #[test]
+    fn test_restore_status_for_legacy_freeze() {
+        use OrderStatus::*;
+        assert_eq!(restore_status(Some(Paid), None).unwrap(), Paid);
+        assert_eq!(restore_status(Some(Paid), Some(Paid)).unwrap(), Paid);
+        assert!(restore_status(Some(Paid), Some(Completed)).is_err());
+
+        // 早期冻结没有状态历史：必须显式指定，且不能指定为 frozen。
+        assert!(restore_status(None, None).is_err());
+        assert_eq!(restore_status(None, Some(Ongoing)).unwrap(), Ongoing);
+        assert!(restore_status(None, Some(Frozen)).is_err());
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for status in [
+            OrderStatus::Confirmed,
+            OrderStatus::Paid,
+            OrderStatus::Ongoing,
+            OrderStatus::Completed,
+            OrderStatus::Reviewed,
+            OrderStatus::Cancelled,
+            OrderStatus::Frozen,
+        ] {
+            assert_eq!(OrderStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(OrderStatus::parse("unknown"), None);
+    }
+}
@@ -1,15 +1,20 @@
-use std::str::FromStr;
-
+use sea_orm::prelude::Decimal;
 use sea_orm::TransactionTrait;
 
 use crate::dto::orders::{
-    CancelOrderReq, CancelOrderResp, OrderItemResp, OrderListItem, OrderListQuery, OrderResp,
-    RefundPreviewResp,
+    CancelOrderReq, CancelOrderResp, OrderContractResp, OrderItemResp, OrderListItem,
+    OrderListQuery, OrderResp, OrderTimelineItem, PaymentPlanResp, PaymentPlanStageItem,
+    RefundPolicyTierResp, RefundPreviewResp,
 };
 use crate::dto::pagination::{normalize_pagination, Paged};
 use crate::entity::orders;
 use crate::errors::{DomainError, ServiceResult};
+use crate::money::Money;
 use crate::repositories::orders_repo;
+use crate::services::order_state_service::{self, OrderStatus};
+use crate::services::provider_access_service::{self, ProviderAction};
+use crate::services::refund_policy_service::{AppliedRefundTier, RefundPolicySnapshot};
+use crate::services::{ledger_service, payment_plan_service, refunds_service};
 use crate::state::AppState;
 
 pub async fn list_orders(
@@ -34,8 +39,8 @@ pub async fn list_orders(
         return Err(DomainError::BadRequest("invalid_amount_range".to_string()).into());
     }
 
-    let min_amount = query.min_amount.map(decimal_from_f64);
-    let max_amount = query.max_amount.map(decimal_from_f64);
+    let min_amount = query.min_amount.map(Money::amount);
+    let max_amount = query.max_amount.map(Money::amount);
 
     let start_time = match query.start_time.as_deref() {
         Some(value) => Some(parse_datetime(value)?),
@@ -64,7 +69,7 @@ pub async fn list_orders(
         .map(|r| OrderListItem {
             id: r.id,
             status: r.status,
-            total_amount: decimal_to_f64(r.total_amount),
+            total_amount: Money::from(r.total_amount),
         })
         .collect();
 
@@ -80,16 +85,20 @@ pub async fn get_order(
         .await?
         .ok_or(DomainError::NotFound)?;
 
-    if order.user_id != user_id {
-        return Err(DomainError::Forbidden.into());
-    }
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::View,
+    )
+    .await?;
 
     let items = orders_repo::list_order_items(&state.orm, order_id)
         .await?
         .into_iter()
         .map(|it| OrderItemResp {
             name: it.name,
-            price: decimal_to_f64(it.price),
+            price: Money::from(it.price),
             quantity: it.quantity,
         })
         .collect();
@@ -99,12 +108,48 @@ pub async fn get_order(
         user_id: order.user_id,
         status: order.status,
         pay_type: order.pay_type,
-        total_amount: decimal_to_f64(order.total_amount),
-        service_fee: decimal_to_f64(order.service_fee),
+        total_amount: Money::from(order.total_amount),
+        service_fee: Money::from(order.service_fee),
+        quote_id: order.quote_id,
+        quote_version: order.quote_version,
+        schedule_start: order.schedule_start.map(|t| t.to_rfc3339()),
+        schedule_end: order.schedule_end.map(|t| t.to_rfc3339()),
+        location: order.location,
         items,
     })
 }
 
+/// 订单合同：接受报价时生成的条款快照，下单用户或承接的服务方可查看。
+pub async fn get_order_contract(
+    state: &AppState,
+    user_id: i64,
+    order_id: i64,
+) -> ServiceResult<OrderContractResp> {
+    let order = orders_repo::find_order_by_id(&state.orm, order_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::View,
+    )
+    .await?;
+
+    let contract = orders_repo::find_contract_by_order(&state.orm, order_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    Ok(OrderContractResp {
+        id: contract.id,
+        order_id: contract.order_id,
+        version: contract.version,
+        terms: contract.terms,
+        created_at: contract.created_at.to_rfc3339(),
+    })
+}
+
 pub async fn refund_preview(
     state: &AppState,
     user_id: i64,
@@ -115,19 +160,31 @@ pub async fn refund_preview(
         .ok_or(DomainError::NotFound)?;
 
     let cancel_by = resolve_cancel_role(&state.orm, &order, user_id).await?;
+    order_state_service::ensure_not_frozen(&order)?;
     ensure_cancellable(&order)?;
 
     let paid_amount = calc_paid_amount(&state.orm, order_id).await?;
-    let (ratio, rule) = compute_refund_ratio(&order, &cancel_by, paid_amount);
-    let refund_amount = (paid_amount * ratio).max(0.0);
+    let policy = RefundPolicySnapshot::from_order(order.refund_policy.as_ref())?;
+    let decision = compute_refund_ratio(&order, &policy, &cancel_by, paid_amount);
+    let refundable = refunds_service::refundable_balance(&state.orm, order_id, None).await?;
+    let refund_amount = paid_amount.mul_ratio(decision.ratio).min(refundable);
 
     Ok(RefundPreviewResp {
         order_id,
         paid_amount,
-        refund_ratio: ratio,
+        refund_ratio: decision.ratio,
         refund_amount,
         responsible_party: cancel_by,
-        rule,
+        rule: decision.rule,
+        policy_name: policy.name,
+        policy_source: policy.source,
+        days_before_start: decision.days_before_start,
+        applied_tier: decision.applied_tier.map(|applied| RefundPolicyTierResp {
+            index: applied.index,
+            pay_type: applied.tier.pay_type,
+            min_days_before: applied.tier.min_days_before,
+            ratio: applied.tier.ratio,
+        }),
     })
 }
 
@@ -139,28 +196,32 @@ pub async fn cancel_order(
 ) -> ServiceResult<CancelOrderResp> {
     let txn = state.orm.begin().await?;
 
-    let order = orders_repo::find_order_by_id(&txn, order_id)
+    let order = orders_repo::find_order_for_update(&txn, order_id)
         .await?
         .ok_or(DomainError::NotFound)?;
 
     let cancel_by = resolve_cancel_role(&txn, &order, user_id).await?;
+    order_state_service::ensure_not_frozen(&order)?;
     ensure_cancellable(&order)?;
     if order.status == "cancelled" {
         return Err(DomainError::BadRequest("already_cancelled".to_string()).into());
     }
 
     let paid_amount = calc_paid_amount(&txn, order_id).await?;
-    let (ratio, _rule) = compute_refund_ratio(&order, &cancel_by, paid_amount);
-    let refund_amount = (paid_amount * ratio).max(0.0);
+    let policy = RefundPolicySnapshot::from_order(order.refund_policy.as_ref())?;
+    let decision = compute_refund_ratio(&order, &policy, &cancel_by, paid_amount);
+    // 扣除已申请的退款，避免取消退款与手动退款合计超过实付金额。
+    let refundable = refunds_service::refundable_balance(&txn, order_id, None).await?;
+    let refund_amount = paid_amount.mul_ratio(decision.ratio).min(refundable);
 
-    let refund_id = if refund_amount > 0.0 {
+    let refund_id = if refund_amount.is_positive() {
         let refund = crate::entity::refunds::ActiveModel {
             order_id: sea_orm::ActiveValue::Set(order_id),
             applicant_id: sea_orm::ActiveValue::Set(user_id),
-            amount: sea_orm::ActiveValue::Set(decimal_from_f64(refund_amount)),
+            amount: sea_orm::ActiveValue::Set(refund_amount.amount()),
             status: sea_orm::ActiveValue::Set("pending".to_string()),
             responsible_party: sea_orm::ActiveValue::Set(Some(cancel_by.clone())),
-            reason: sea_orm::ActiveValue::Set(req.reason),
+            reason: sea_orm::ActiveValue::Set(req.reason.clone()),
             proof_url: sea_orm::ActiveValue::Set(None),
             ..Default::default()
         };
@@ -169,7 +230,15 @@ pub async fn cancel_order(
         None
     };
 
-    orders_repo::update_order_status_cancelled(&txn, order, chrono::Utc::now()).await?;
+    ledger_service::reverse_service_fee(&txn, &order).await?;
+    order_state_service::transition_order(
+        &txn,
+        order,
+        OrderStatus::Cancelled,
+        Some(user_id),
+        req.reason,
+    )
+    .await?;
 
     txn.commit().await?;
 
@@ -181,78 +250,155 @@ pub async fn cancel_order(
     })
 }
 
+pub async fn get_order_timeline(
+    state: &AppState,
+    user_id: i64,
+    order_id: i64,
+) -> ServiceResult<Vec<OrderTimelineItem>> {
+    let order = orders_repo::find_order_by_id(&state.orm, order_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::View,
+    )
+    .await?;
+
+    let items = orders_repo::list_status_history(&state.orm, order_id)
+        .await?
+        .into_iter()
+        .map(|h| OrderTimelineItem {
+            id: h.id,
+            from_status: h.from_status,
+            to_status: h.to_status,
+            actor_id: h.actor_id,
+            reason: h.reason,
+            created_at: h.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(items)
+}
+
+pub async fn get_payment_plan(
+    state: &AppState,
+    user_id: i64,
+    order_id: i64,
+) -> ServiceResult<PaymentPlanResp> {
+    let order = orders_repo::find_order_by_id(&state.orm, order_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    provider_access_service::ensure_order_participant(
+        &state.orm,
+        &order,
+        user_id,
+        ProviderAction::View,
+    )
+    .await?;
+
+    let plan = payment_plan_service::load_or_create_plan(&state.orm, &order).await?;
+    let payments = orders_repo::list_payments(&state.orm, order_id).await?;
+    let balances = payment_plan_service::summarize(&plan, &payments, chrono::Utc::now());
+
+    let paid: Money = balances.iter().map(|b| b.paid).sum();
+    let outstanding: Money = balances.iter().map(|b| b.outstanding()).sum();
+    let stages = balances
+        .into_iter()
+        .map(|b| PaymentPlanStageItem {
+            amount: b.amount,
+            due_at: b.due_at.map(|t| t.to_rfc3339()),
+            paid_amount: b.paid,
+            pending_amount: b.pending,
+            outstanding_amount: b.outstanding(),
+            status: b.status().to_string(),
+            stage: b.stage,
+        })
+        .collect();
+
+    Ok(PaymentPlanResp {
+        order_id,
+        pay_type: order.pay_type,
+        total_amount: Money::from(order.total_amount),
+        paid_amount: paid,
+        outstanding_amount: outstanding,
+        stages,
+    })
+}
+
+/// 取消方：下单用户或有订单管理权限的服务方（摄影师本人 / 团队管理员）。
 async fn resolve_cancel_role<C: sea_orm::ConnectionTrait>(
     conn: &C,
     order: &orders::Model,
     user_id: i64,
 ) -> ServiceResult<String> {
-    if order.user_id == user_id {
-        return Ok("user".to_string());
-    }
-
-    let photographer_user = match order.photographer_id {
-        Some(pid) => orders_repo::find_photographer_user_id(conn, pid).await?,
-        None => None,
-    };
-
-    if photographer_user == Some(user_id) {
-        return Ok("photographer".to_string());
-    }
-
-    Err(DomainError::Forbidden.into())
+    let party = provider_access_service::ensure_order_participant(
+        conn,
+        order,
+        user_id,
+        ProviderAction::ManageOrder,
+    )
+    .await?;
+    Ok(party.as_str().to_string())
 }
 
 fn ensure_cancellable(order: &orders::Model) -> ServiceResult<()> {
-    if matches!(order.status.as_str(), "completed" | "reviewed") {
+    let status = OrderStatus::of(order)?;
+    if status == OrderStatus::Cancelled {
+        return Ok(());
+    }
+    if !status.can_transition_to(OrderStatus::Cancelled) {
         return Err(DomainError::BadRequest("order_not_cancellable".to_string()).into());
     }
     Ok(())
 }
 
-fn compute_refund_ratio(order: &orders::Model, cancel_by: &str, paid: f64) -> (f64, String) {
-    if cancel_by == "photographer" {
-        return (1.0, "photographer_full_refund".to_string());
-    }
-    if paid <= 0.0 {
-        return (0.0, "unpaid_no_refund".to_string());
-    }
+struct RefundDecision {
+    ratio: Decimal,
+    rule: String,
+    days_before_start: i64,
+    applied_tier: Option<AppliedRefundTier>,
+}
+
+/// 摄影师取消全额退；否则按订单快照的退款策略取距开拍天数命中的档位。
+fn compute_refund_ratio(
+    order: &orders::Model,
+    policy: &RefundPolicySnapshot,
+    cancel_by: &str,
+    paid: Money,
+) -> RefundDecision {
     let start_at = order
         .schedule_start
         .map(|t| t.with_timezone(&chrono::Utc))
         .unwrap_or_else(|| order.created_at.with_timezone(&chrono::Utc));
-    let now = chrono::Utc::now();
-    let diff_days = (start_at - now).num_days();
-    let is_deposit = order.pay_type == "deposit";
-    let ratio = if diff_days >= 7 {
-        if is_deposit { 0.8 } else { 0.9 }
-    } else if diff_days >= 3 {
-        if is_deposit { 0.5 } else { 0.7 }
-    } else if is_deposit {
-        0.0
-    } else {
-        0.5
+    let days_before_start = (start_at - chrono::Utc::now()).num_days();
+    let decision = |ratio, rule: &str, applied_tier| RefundDecision {
+        ratio,
+        rule: rule.to_string(),
+        days_before_start,
+        applied_tier,
     };
-    (ratio, "time_based_default".to_string())
+    if cancel_by == "photographer" {
+        return decision(Decimal::ONE, "photographer_full_refund", None);
+    }
+    if !paid.is_positive() {
+        return decision(Decimal::ZERO, "unpaid_no_refund", None);
+    }
+    match policy.apply(&order.pay_type, days_before_start) {
+        (ratio, Some(tier)) => decision(ratio, "policy_tier", Some(tier)),
+        (ratio, None) => decision(ratio, "policy_no_matching_tier", None),
+    }
 }
 
 async fn calc_paid_amount<C: sea_orm::ConnectionTrait>(
     conn: &C,
     order_id: i64,
-) -> ServiceResult<f64> {
+) -> ServiceResult<Money> {
     let rows = orders_repo::list_success_payments(conn, order_id).await?;
-    let total = rows
-        .into_iter()
-        .fold(0.0, |acc, p| acc + decimal_to_f64(p.amount));
-    Ok(total)
-}
-
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
-}
-
-fn decimal_from_f64(v: f64) -> sea_orm::prelude::Decimal {
-    sea_orm::prelude::Decimal::from_str(&v.to_string())
-        .unwrap_or(sea_orm::prelude::Decimal::ZERO)
+    Ok(rows.into_iter().map(|p| Money::from(p.amount)).sum())
 }
 
 fn parse_datetime(input: &str) -> Result<chrono::DateTime<chrono::Utc>, DomainError> {
@@ -0,0 +1,444 @@
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::prelude::Decimal;
+use sea_orm::{ActiveValue::Set, ConnectionTrait};
+use serde::Deserialize;
+
+use crate::entity::{order_payment_plans, orders, payments};
+use crate::errors::{DomainError, ServiceResult};
+use crate::money::Money;
+use crate::repositories::{configs_repo, orders_repo};
+
+/// 平台配置中的分期规则（configs.key），缺省或字段非法时使用默认值。
+pub const PLAN_CONFIG_KEY: &str = "payment_plan";
+/// 未回调的支付意图在此时间内占用阶段额度，超时视为放弃。
+pub const PENDING_PAYMENT_TTL_MINUTES: i64 = 30;
+
+const STAGES: [&str; 3] = ["deposit", "mid", "final"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanSettings {
+    pub pay_type: String,
+    pub deposit_ratio: Decimal,
+    pub mid_ratio: Decimal,
+    pub deposit_due_hours: i64,
+}
+
+impl Default for PlanSettings {
+    fn default() -> Self {
+        Self {
+            pay_type: "deposit".to_string(),
+            deposit_ratio: Decimal::new(3, 1),
+            mid_ratio: Decimal::new(4, 1),
+            deposit_due_hours: 24,
+        }
+    }
+}
+
+/// 写入配置时的严格格式：字段均可省略（沿用默认值），出现的字段必须合法。
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlanConfig {
+    pay_type: Option<String>,
+    deposit_ratio: Option<Decimal>,
+    mid_ratio: Option<Decimal>,
+    deposit_due_hours: Option<i64>,
+}
+
+impl PlanSettings {
+    /// 校验管理端写入的分期配置，非法时拒绝而不是静默回退默认值。
+    pub fn parse(value: &serde_json::Value) -> Result<Self, DomainError> {
+        let invalid = || DomainError::BadRequest("invalid_payment_plan".to_string());
+        let config: PlanConfig = serde_json::from_value(value.clone()).map_err(|_| invalid())?;
+        let defaults = Self::default();
+        let settings = Self {
+            pay_type: config.pay_type.unwrap_or(defaults.pay_type),
+            deposit_ratio: config.deposit_ratio.unwrap_or(defaults.deposit_ratio),
+            mid_ratio: config.mid_ratio.unwrap_or(defaults.mid_ratio),
+            deposit_due_hours: config.deposit_due_hours.unwrap_or(defaults.deposit_due_hours),
+        };
+        let valid_ratio = |v: Decimal| v >= Decimal::ZERO && v <= Decimal::ONE;
+        let valid = matches!(settings.pay_type.as_str(), "deposit" | "full" | "phase")
+            && valid_ratio(settings.deposit_ratio)
+            && valid_ratio(settings.mid_ratio)
+            && settings.deposit_ratio + settings.mid_ratio <= Decimal::ONE
+            && settings.deposit_due_hours > 0;
+        if !valid {
+            return Err(invalid());
+        }
+        Ok(settings)
+    }
+
+    pub fn from_config(value: &serde_json::Value) -> Self {
+        let mut settings = Self::default();
+        if let Some(pay_type) = value.get("pay_type").and_then(|v| v.as_str())
+            && matches!(pay_type, "deposit" | "full" | "phase")
+        {
+            settings.pay_type = pay_type.to_string();
+        }
+        let ratio = |key: &str| {
+            value
+                .get(key)
+                .and_then(|v| serde_json::from_value::<Decimal>(v.clone()).ok())
+                .filter(|v| *v >= Decimal::ZERO && *v <= Decimal::ONE)
+        };
+        if let Some(v) = ratio("deposit_ratio") {
+            settings.deposit_ratio = v;
+        }
+        if let Some(v) = ratio("mid_ratio") {
+            settings.mid_ratio = v;
+        }
+        if settings.deposit_ratio + settings.mid_ratio > Decimal::ONE {
+            settings.mid_ratio = Decimal::ONE - settings.deposit_ratio;
+        }
+        if let Some(hours) = value.get("deposit_due_hours").and_then(|v| v.as_i64())
+            && hours > 0
+        {
+            settings.deposit_due_hours = hours;
+        }
+        settings
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedStage {
+    pub stage: String,
+    pub amount: Money,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageBalance {
+    pub stage: String,
+    pub amount: Money,
+    pub due_at: Option<DateTime<Utc>>,
+    pub paid: Money,
+    pub pending: Money,
+}
+
+impl StageBalance {
+    pub fn outstanding(&self) -> Money {
+        self.amount.saturating_sub(self.paid)
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.outstanding().is_zero() {
+            "paid"
+        } else if self.paid.is_positive() {
+            "partial"
+        } else {
+            "unpaid"
+        }
+    }
+}
+
+pub async fn load_settings<C: ConnectionTrait>(conn: &C) -> ServiceResult<PlanSettings> {
+    Ok(configs_repo::find_config_by_key(conn, PLAN_CONFIG_KEY)
+        .await?
+        .map(|c| PlanSettings::from_config(&c.value))
+        .unwrap_or_default())
+}
+
+/// 按支付类型拆分阶段：full 一次付清，deposit 定金+尾款，phase 定金+中期+尾款；
+/// 尾款取差额，保证各阶段之和等于订单总额。
+pub fn build_plan(
+    pay_type: &str,
+    total: Money,
+    settings: &PlanSettings,
+    created_at: DateTime<Utc>,
+    schedule_start: Option<DateTime<Utc>>,
+    schedule_end: Option<DateTime<Utc>>,
+) -> Vec<PlannedStage> {
+    let deposit = total.mul_ratio(settings.deposit_ratio);
+    let mid = total.mul_ratio(settings.mid_ratio);
+    let deposit_due = Some(created_at + Duration::hours(settings.deposit_due_hours));
+    let final_due = schedule_end.or(schedule_start);
+
+    let mut stages = Vec::new();
+    match pay_type {
+        "deposit" => {
+            stages.push(("deposit", deposit, deposit_due));
+        }
+        "phase" => {
+            stages.push(("deposit", deposit, deposit_due));
+            stages.push(("mid", mid, schedule_start));
+        }
+        _ => {}
+    }
+    let allocated: Money = stages.iter().map(|(_, amount, _)| *amount).sum();
+    stages.push(("final", total - allocated, final_due));
+
+    stages
+        .into_iter()
+        .filter(|(stage, amount, _)| *stage == "final" || amount.is_positive())
+        .map(|(stage, amount, due_at)| PlannedStage {
+            stage: stage.to_string(),
+            amount,
+            due_at,
+        })
+        .collect()
+}
+
+pub async fn save_plan<C: ConnectionTrait>(
+    conn: &C,
+    order_id: i64,
+    plan: &[PlannedStage],
+) -> ServiceResult<Vec<order_payment_plans::Model>> {
+    let items = plan
+        .iter()
+        .map(|p| order_payment_plans::ActiveModel {
+            order_id: Set(order_id),
+            stage: Set(p.stage.clone()),
+            amount: Set(p.amount.amount()),
+            due_at: Set(p.due_at.map(Into::into)),
+            ..Default::default()
+        })
+        .collect();
+    Ok(orders_repo::create_payment_plan(conn, items).await?)
+}
+
+/// 读取订单分期计划；历史订单没有计划时按当前配置补建。
+pub async fn load_or_create_plan<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+) -> ServiceResult<Vec<order_payment_plans::Model>> {
+    let rows = orders_repo::list_payment_plan(conn, order.id).await?;
+    if !rows.is_empty() {
+        return Ok(rows);
+    }
+    let settings = load_settings(conn).await?;
+    let plan = build_plan(
+        &order.pay_type,
+        Money::from(order.total_amount),
+        &settings,
+        order.created_at.with_timezone(&Utc),
+        order.schedule_start.map(|t| t.with_timezone(&Utc)),
+        order.schedule_end.map(|t| t.with_timezone(&Utc)),
+    );
+    save_plan(conn, order.id, &plan).await
+}
+
+/// 汇总各阶段已付/占用金额；未标注阶段的历史支付按阶段顺序依次冲抵。
+pub fn summarize(
+    plan: &[order_payment_plans::Model],
+    payments: &[payments::Model],
+    now: DateTime<Utc>,
+) -> Vec<StageBalance> {
+    let mut balances: Vec<StageBalance> = STAGES
+        .iter()
+        .filter_map(|stage| plan.iter().find(|p| p.stage == *stage))
+        .map(|p| StageBalance {
+            stage: p.stage.clone(),
+            amount: Money::from(p.amount),
+            due_at: p.due_at.map(|t| t.with_timezone(&Utc)),
+            paid: Money::ZERO,
+            pending: Money::ZERO,
+        })
+        .collect();
+
+    let pending_since = now - Duration::minutes(PENDING_PAYMENT_TTL_MINUTES);
+    let mut unstaged = Money::ZERO;
+    for payment in payments {
+        let reserved = match payment.status.as_str() {
+            "success" => false,
+            "pending" if payment.created_at.with_timezone(&Utc) > pending_since => true,
+            _ => continue,
+        };
+        let target = payment
+            .stage
+            .as_deref()
+            .and_then(|stage| balances.iter_mut().find(|b| b.stage == stage));
+        let amount = Money::from(payment.amount);
+        match (target, reserved) {
+            (Some(balance), false) => balance.paid += amount,
+            (Some(balance), true) => balance.pending += amount,
+            (None, false) => unstaged += amount,
+            (None, true) => {}
+        }
+    }
+
+    for balance in balances.iter_mut() {
+        if unstaged.is_zero() {
+            break;
+        }
+        let applied = balance.outstanding().min(unstaged);
+        balance.paid += applied;
+        unstaged -= applied;
+    }
+    if let Some(last) = balances.last_mut() {
+        last.paid += unstaged;
+    }
+
+    balances
+}
+
+pub fn is_fully_paid(balances: &[StageBalance]) -> bool {
+    balances.iter().all(|b| b.outstanding().is_zero())
+}
+
+/// 各阶段已付超出应付的合计。
+pub fn overpaid(balances: &[StageBalance]) -> Money {
+    balances.iter().map(|b| b.paid.saturating_sub(b.amount)).sum()
+}
+
+/// 校验本次支付不超过阶段未付余额（含未过期的待回调支付），返回实际归属阶段。
+/// 未指定阶段时归入第一个未付清的阶段；分期（phase）订单必须显式指定。
+pub fn ensure_payable(
+    pay_type: &str,
+    balances: &[StageBalance],
+    stage: Option<&str>,
+    amount: Money,
+) -> Result<String, DomainError> {
+    let balance = match stage {
+        Some(stage) => balances
+            .iter()
+            .find(|b| b.stage == stage)
+            .ok_or_else(|| DomainError::BadRequest("stage_not_in_plan".to_string()))?,
+        None if pay_type == "phase" => {
+            return Err(DomainError::BadRequest("stage_required".to_string()));
+        }
+        None => balances
+            .iter()
+            .find(|b| !b.outstanding().is_zero())
+            .ok_or_else(|| DomainError::Conflict("order_fully_paid".to_string()))?,
+    };
+
+    let available = balance.outstanding().saturating_sub(balance.pending);
+    if balance.outstanding().is_zero() {
+        return Err(DomainError::Conflict("stage_already_paid".to_string()));
+    }
+    if amount > available {
+        return Err(DomainError::BadRequest("amount_exceeds_outstanding".to_string()));
+    }
+    Ok(balance.stage.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn money(v: &str) -> Money {
+        Money::from_str(v).unwrap()
+    }
+
+    fn plan_row(stage: &str, amount: &str) -> order_payment_plans::Model {
+        order_payment_plans::Model {
+            id: 0,
+            order_id: 1,
+            stage: stage.to_string(),
+            amount: Decimal::from_str(amount).unwrap(),
+            due_at: None,
+            created_at: Utc::now().into(),
+        }
+    }
+
+    fn payment(stage: Option<&str>, amount: &str, status: &str) -> payments::Model {
+        payments::Model {
+            id: 0,
+            order_id: 1,
+            payer_id: 1,
+            payee_id: 2,
+            amount: Decimal::from_str(amount).unwrap(),
+            status: status.to_string(),
+            pay_channel: "wx".to_string(),
+            stage: stage.map(str::to_string),
+            paid_at: None,
+            proof_url: None,
+            created_at: Utc::now().into(),
+            provider_ref: None,
+            pay_params: None,
+            callback_at: None,
+        }
+    }
+
+    #[test]
+    fn test_build_plan_sums_to_total() {
+        let settings = PlanSettings::default();
+        let now = Utc::now();
+        let plan = build_plan("phase", money("999.99"), &settings, now, None, None);
+        let stages: Vec<&str> = plan.iter().map(|p| p.stage.as_str()).collect();
+        assert_eq!(stages, vec!["deposit", "mid", "final"]);
+        assert_eq!(plan[0].amount, money("300.00"));
+        assert_eq!(plan.iter().map(|p| p.amount).sum::<Money>(), money("999.99"));
+
+        let full = build_plan("full", money("100"), &settings, now, None, None);
+        assert_eq!(full.len(), 1);
+        assert_eq!(full[0].amount, money("100"));
+    }
+
+    #[test]
+    fn test_settings_from_config_clamps_ratios() {
+        let settings = PlanSettings::from_config(&serde_json::json!({
+            "pay_type": "phase",
+            "deposit_ratio": "0.7",
+            "mid_ratio": 0.6,
+            "deposit_due_hours": -1
+        }));
+        assert_eq!(settings.pay_type, "phase");
+        assert_eq!(settings.deposit_ratio, Decimal::new(7, 1));
+        assert_eq!(settings.mid_ratio, Decimal::new(3, 1));
+        assert_eq!(settings.deposit_due_hours, 24);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_settings() {
+        let parsed = PlanSettings::parse(&serde_json::json!({
+            "pay_type": "phase",
+            "deposit_ratio": "0.2",
+            "mid_ratio": 0.5
+        }))
+        .unwrap();
+        assert_eq!(parsed.deposit_ratio, Decimal::new(2, 1));
+        assert_eq!(parsed.deposit_due_hours, 24);
+        assert!(PlanSettings::parse(&serde_json::json!({})).is_ok());
+
+        for value in [
+            serde_json::json!({ "pay_type": "weekly" }),
+            serde_json::json!({ "deposit_ratio": "1.5" }),
+            serde_json::json!({ "deposit_ratio": 0.7, "mid_ratio": 0.6 }),
+            serde_json::json!({ "deposit_due_hours": 0 }),
+            serde_json::json!({ "deposit_ratio": "abc" }),
+            serde_json::json!({ "deposit_ration": 0.3 }),
+            serde_json::json!([]),
+        ] {
+            assert!(PlanSettings::parse(&value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_ensure_payable_rejects_overpayment() {
+        let plan = vec![plan_row("deposit", "300"), plan_row("final", "700")];
+        let payments = vec![
+            payment(Some("deposit"), "200", "success"),
+            payment(Some("deposit"), "50", "pending"),
+            payment(Some("final"), "700", "failed"),
+        ];
+        let balances = summarize(&plan, &payments, Utc::now());
+
+        assert_eq!(
+            ensure_payable("deposit", &balances, Some("deposit"), money("50")).unwrap(),
+            "deposit"
+        );
+        assert!(ensure_payable("deposit", &balances, Some("deposit"), money("50.01")).is_err());
+        assert!(ensure_payable("deposit", &balances, Some("mid"), money("1")).is_err());
+        assert!(ensure_payable("phase", &balances, None, money("1")).is_err());
+        assert_eq!(ensure_payable("deposit", &balances, None, money("50")).unwrap(), "deposit");
+        assert!(!is_fully_paid(&balances));
+    }
+
+    #[test]
+    fn test_summarize_allocates_unstaged_payments_in_order() {
+        let plan = vec![plan_row("deposit", "300"), plan_row("final", "700")];
+        let payments = vec![payment(None, "1000", "success")];
+        let balances = summarize(&plan, &payments, Utc::now());
+        assert_eq!(balances[0].paid, money("300"));
+        assert_eq!(balances[1].paid, money("700"));
+        assert!(is_fully_paid(&balances));
+        assert_eq!(overpaid(&balances), Money::ZERO);
+
+        let payments = vec![payment(None, "1000", "success"), payment(None, "80", "success")];
+        let balances = summarize(&plan, &payments, Utc::now());
+        assert_eq!(overpaid(&balances), money("80"));
+    }
+}
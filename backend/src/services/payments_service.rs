@@ -1,10 +1,17 @@
-use std::str::FromStr;
+use axum::http::HeaderMap;
+use sea_orm::{ActiveValue::Set, ConnectionTrait, TransactionTrait};
 
-use sea_orm::TransactionTrait;
-
-use crate::dto::payments::{CreatePaymentReq, PaymentResp};
+use crate::dto::payments::{
+    ConfirmPaymentReq, CreatePaymentReq, PaymentCallbackResp, PaymentResp,
+};
+use crate::entity::{orders, payments};
 use crate::errors::{DomainError, ServiceResult};
+use crate::money::Money;
+use crate::payment_provider::{CallbackStatus, IntentRequest, CHANNELS};
 use crate::repositories::orders_repo;
+use crate::services::order_state_service::{self, OrderStatus};
+use crate::services::provider_access_service::{self, ProviderAction};
+use crate::services::{ledger_service, payment_plan_service, refunds_service};
 use crate::state::AppState;
 
 pub async fn create_payment(
@@ -12,81 +19,291 @@ pub async fn create_payment(
     user_id: i64,
     req: CreatePaymentReq,
 ) -> ServiceResult<PaymentResp> {
-    if req.amount <= 0.0 {
+    if !req.amount.is_positive() {
         return Err(DomainError::InvalidAmount.into());
     }
-    if !matches!(req.pay_channel.as_str(), "wx" | "alipay" | "bank") {
+    if !CHANNELS.contains(&req.pay_channel.as_str()) {
         return Err(DomainError::BadRequest("invalid_channel".to_string()).into());
     }
-    if req.proof_url.is_empty() {
-        return Err(DomainError::BadRequest("proof_required".to_string()).into());
-    }
     if let Some(stage) = req.stage.as_deref()
         && !matches!(stage, "deposit" | "mid" | "final")
     {
         return Err(DomainError::BadRequest("invalid_stage".to_string()).into());
     }
+    let provider = state
+        .payments
+        .provider(&req.pay_channel)
+        .ok_or_else(|| DomainError::BadRequest("channel_unavailable".to_string()))?;
+    let proof_url = req.proof_url.filter(|u| !u.trim().is_empty());
+    // 线下转账没有渠道回调，收款方只能凭转账凭证确认到账。
+    if provider.settles_manually() && proof_url.is_none() {
+        return Err(DomainError::BadRequest("proof_required".to_string()).into());
+    }
 
     let txn = state.orm.begin().await?;
-    let order = orders_repo::find_order_by_id(&txn, req.order_id)
+    // 锁定订单行，避免并发支付同时通过余额校验。
+    let order = orders_repo::find_order_for_update(&txn, req.order_id)
         .await?
         .ok_or(DomainError::NotFound)?;
 
     if order.user_id != user_id {
         return Err(DomainError::Forbidden.into());
     }
+    order_state_service::ensure_not_frozen(&order)?;
+    let status = OrderStatus::of(&order)?;
+    if !status.accepts_payment() {
+        return Err(DomainError::BadRequest("order_not_payable".to_string()).into());
+    }
 
-    let payee_id = match order.photographer_id {
-        Some(pid) => orders_repo::find_photographer_user_id(&txn, pid)
+    let payee_id =
+        provider_access_service::provider_user_id(&txn, order.photographer_id, order.team_id)
             .await?
-            .unwrap_or(user_id),
-        None => user_id,
-    };
+            .unwrap_or(user_id);
 
-    if order.pay_type == "phase" && req.stage.is_none() {
-        return Err(DomainError::BadRequest("stage_required".to_string()).into());
-    }
+    let amount = req.amount;
+    let plan = payment_plan_service::load_or_create_plan(&txn, &order).await?;
+    let existing = orders_repo::list_payments(&txn, order.id).await?;
+    let balances = payment_plan_service::summarize(&plan, &existing, chrono::Utc::now());
+    let stage = payment_plan_service::ensure_payable(
+        &order.pay_type,
+        &balances,
+        req.stage.as_deref(),
+        amount,
+    )?;
 
-    let payment = crate::entity::payments::ActiveModel {
-        order_id: sea_orm::ActiveValue::Set(req.order_id),
-        payer_id: sea_orm::ActiveValue::Set(user_id),
-        payee_id: sea_orm::ActiveValue::Set(payee_id),
-        amount: sea_orm::ActiveValue::Set(decimal_from_f64(req.amount)),
-        status: sea_orm::ActiveValue::Set("success".to_string()),
-        pay_channel: sea_orm::ActiveValue::Set(req.pay_channel),
-        stage: sea_orm::ActiveValue::Set(req.stage.clone()),
-        proof_url: sea_orm::ActiveValue::Set(Some(req.proof_url)),
-        paid_at: sea_orm::ActiveValue::Set(Some(chrono::Utc::now().into())),
+    let payment = payments::ActiveModel {
+        order_id: Set(req.order_id),
+        payer_id: Set(user_id),
+        payee_id: Set(payee_id),
+        amount: Set(amount.amount()),
+        status: Set("pending".to_string()),
+        pay_channel: Set(req.pay_channel),
+        stage: Set(Some(stage)),
+        proof_url: Set(proof_url),
+        paid_at: Set(None),
         ..Default::default()
     };
-
     let inserted = orders_repo::create_payment(&txn, payment).await?;
+    txn.commit().await?;
+
+    // 调用渠道下单期间不持有订单锁，避免渠道响应慢时阻塞该订单的其他写操作；
+    // 已提交的 pending 支付占用阶段额度，并发支付仍会被余额校验拦下。
+    let intent = provider
+        .create_intent(&IntentRequest {
+            payment_id: inserted.id,
+            order_id: inserted.order_id,
+            amount: inserted.amount,
+            description: format!("order {}", inserted.order_id),
+        })
+        .await;
 
-    let should_mark_paid = match req.stage.as_deref() {
-        Some("final") => true,
-        Some(_) => false,
-        None => true,
+    let txn = state.orm.begin().await?;
+    let payment = orders_repo::find_payment_for_update(&txn, inserted.id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let mut model: payments::ActiveModel = payment.into();
+    let intent = match intent {
+        Ok(intent) => intent,
+        Err(err) => {
+            // 下单失败的支付置为 failed，释放占用的阶段额度。
+            model.status = Set("failed".to_string());
+            orders_repo::update_payment(&txn, model).await?;
+            txn.commit().await?;
+            return Err(err.into());
+        }
     };
-    if should_mark_paid {
-        orders_repo::update_order_status(&txn, order, "paid".to_string()).await?;
+    model.provider_ref = Set(Some(intent.provider_ref));
+    model.pay_params = Set(Some(intent.pay_params));
+    let updated = orders_repo::update_payment(&txn, model).await?;
+    txn.commit().await?;
+
+    Ok(to_payment_resp(updated))
+}
+
+pub async fn handle_callback(
+    state: &AppState,
+    channel: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> ServiceResult<PaymentCallbackResp> {
+    let provider = state.payments.provider(channel).ok_or(DomainError::NotFound)?;
+    let event = provider.verify_callback(headers, body).map_err(|err| {
+        tracing::warn!(channel, error = ?err, "payment_callback_rejected");
+        DomainError::BadRequest("invalid_signature".to_string())
+    })?;
+
+    let txn = state.orm.begin().await?;
+    // 锁定支付行：并发的重复回调在此排队，后到者读到已落定的状态后直接返回。
+    let payment = orders_repo::find_payment_by_provider_ref_for_update(&txn, &event.provider_ref)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if payment.pay_channel != channel {
+        return Err(DomainError::BadRequest("channel_mismatch".to_string()).into());
+    }
+
+    // 渠道会重复推送通知，已落定的支付按当前状态应答。
+    if payment.status != "pending" {
+        return Ok(PaymentCallbackResp {
+            payment_id: payment.id,
+            status: payment.status,
+        });
+    }
+    if event.amount != payment.amount {
+        tracing::warn!(payment_id = payment.id, "payment_callback_amount_mismatch");
+        return Err(DomainError::BadRequest("amount_mismatch".to_string()).into());
     }
 
+    // 锁序与创建支付、人工确认一致：支付 → 订单，串行化同一订单的并发落定。
+    let order = orders_repo::find_order_for_update(&txn, payment.order_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let updated =
+        settle_payment(&txn, payment, order, event.status == CallbackStatus::Success).await?;
+
     txn.commit().await?;
 
-    Ok(PaymentResp {
-        id: inserted.id,
-        order_id: inserted.order_id,
-        amount: decimal_to_f64(inserted.amount),
-        status: inserted.status,
-        stage: inserted.stage,
+    Ok(PaymentCallbackResp {
+        payment_id: updated.id,
+        status: updated.status,
     })
 }
 
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
+/// 线下转账渠道由收款方确认是否到账。
+pub async fn confirm_payment(
+    state: &AppState,
+    user_id: i64,
+    payment_id: i64,
+    req: ConfirmPaymentReq,
+) -> ServiceResult<PaymentResp> {
+    let txn = state.orm.begin().await?;
+    let payment = orders_repo::find_payment_for_update(&txn, payment_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let order = orders_repo::find_order_for_update(&txn, payment.order_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    provider_access_service::ensure_provider_action(
+        &txn,
+        order.photographer_id,
+        order.team_id,
+        user_id,
+        ProviderAction::ManageOrder,
+    )
+    .await?;
+
+    let manual = state
+        .payments
+        .provider(&payment.pay_channel)
+        .is_some_and(|p| p.settles_manually());
+    if !manual {
+        return Err(DomainError::BadRequest("channel_not_manual".to_string()).into());
+    }
+    if payment.status != "pending" {
+        return Err(DomainError::InvalidStatus.into());
+    }
+
+    let updated = settle_payment(&txn, payment, order, req.received).await?;
+    txn.commit().await?;
+
+    Ok(to_payment_resp(updated))
+}
+
+/// 将待处理支付落定为成功或失败；成功时记账，付清后推进订单状态。
+/// 调用方须已锁定支付行及其订单行。
+async fn settle_payment<C: ConnectionTrait>(
+    conn: &C,
+    payment: payments::Model,
+    order: orders::Model,
+    success: bool,
+) -> ServiceResult<payments::Model> {
+    let now = chrono::Utc::now();
+    let order_id = payment.order_id;
+    let mut model: payments::ActiveModel = payment.into();
+    model.status = Set(if success { "success" } else { "failed" }.to_string());
+    model.callback_at = Set(Some(now.into()));
+    if success {
+        model.paid_at = Set(Some(now.into()));
+    }
+    let updated = orders_repo::update_payment(conn, model).await?;
+    if !success {
+        return Ok(updated);
+    }
+
+    ledger_service::record_payment(conn, &updated, &order).await?;
+    // 订单已取消时资金仍已到账，整笔生成待审核退款，不再计入分期与服务费。
+    if OrderStatus::of(&order)? == OrderStatus::Cancelled {
+        let amount = Money::from(updated.amount);
+        let refund = refunds_service::create_payment_refund(
+            conn,
+            &order,
+            &updated,
+            amount,
+            "order_cancelled",
+        )
+        .await?;
+        tracing::warn!(
+            payment_id = updated.id,
+            order_id,
+            refund_id = refund.id,
+            "payment_on_cancelled_order"
+        );
+        return Ok(updated);
+    }
+    let plan = payment_plan_service::load_or_create_plan(conn, &order).await?;
+    let payments = orders_repo::list_payments(conn, order_id).await?;
+    let balances = payment_plan_service::summarize(&plan, &payments, now);
+    // 资金已到账无法拒收，本笔支付新增的超付部分生成待审核退款。
+    let earlier: Vec<payments::Model> =
+        payments.iter().filter(|p| p.id != updated.id).cloned().collect();
+    let earlier_overpaid =
+        payment_plan_service::overpaid(&payment_plan_service::summarize(&plan, &earlier, now));
+    let excess = payment_plan_service::overpaid(&balances)
+        .saturating_sub(earlier_overpaid)
+        .min(Money::from(updated.amount));
+    if excess.is_positive() {
+        let refund = refunds_service::create_payment_refund(
+            conn,
+            &order,
+            &updated,
+            excess,
+            "payment_overpaid",
+        )
+        .await?;
+        tracing::warn!(
+            payment_id = updated.id,
+            order_id,
+            refund_id = refund.id,
+            %excess,
+            "payment_plan_overpaid"
+        );
+    }
+    let fully_paid = payment_plan_service::is_fully_paid(&balances);
+    if fully_paid {
+        ledger_service::accrue_service_fee(conn, &order).await?;
+    }
+    // 已冻结或已推进的订单保持原状态，支付照常入账。
+    if fully_paid && OrderStatus::of(&order)?.can_transition_to(OrderStatus::Paid) {
+        order_state_service::transition_order(
+            conn,
+            order,
+            OrderStatus::Paid,
+            None,
+            Some("payment_received".to_string()),
+        )
+        .await?;
+    }
+    Ok(updated)
 }
 
-fn decimal_from_f64(v: f64) -> sea_orm::prelude::Decimal {
-    sea_orm::prelude::Decimal::from_str(&v.to_string())
-        .unwrap_or(sea_orm::prelude::Decimal::ZERO)
+fn to_payment_resp(p: payments::Model) -> PaymentResp {
+    PaymentResp {
+        id: p.id,
+        order_id: p.order_id,
+        amount: Money::from(p.amount),
+        status: p.status,
+        stage: p.stage,
+        pay_channel: p.pay_channel,
+        provider_ref: p.provider_ref,
+        pay_params: p.pay_params,
+    }
 }
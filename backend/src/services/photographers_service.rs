@@ -1,11 +1,19 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, Datelike, Months, NaiveDate, TimeZone, Utc};
 use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect};
 
+use crate::common::render_csv;
 use crate::dto::pagination::{normalize_pagination, Paged};
-use crate::dto::photographers::{PhotographerListItem, PhotographerListQuery};
-use crate::entity::{photographers, user_profiles, users};
-use crate::errors::ServiceResult;
+use crate::dto::photographers::{
+    PhotographerEarningsItem, PhotographerEarningsQuery, PhotographerEarningsResp,
+    PhotographerListItem, PhotographerListQuery,
+};
+use crate::entity::{ledger_entries, photographers, user_profiles, users};
+use crate::errors::{DomainError, ServiceResult};
+use crate::money::Money;
+use crate::repositories::{ledger_repo, orders_repo, quotes_repo, refunds_repo, teams_repo};
+use crate::services::ledger_service::AccountOwner;
 use crate::state::AppState;
 
 pub async fn list_photographers(
@@ -114,7 +122,176 @@ pub async fn list_photographers(
     Ok(Paged::new(items, total, page, page_size))
 }
 
+/// 摄影师月度收支：按收款人汇总到账支付，按承接订单汇总已完成退款，服务费取自账本。
+pub async fn get_my_earnings(
+    state: &AppState,
+    user_id: i64,
+    query: PhotographerEarningsQuery,
+) -> ServiceResult<PhotographerEarningsResp> {
+    // 个人承接与本人拥有的团队承接的订单一并计入；团队订单的收款人为团队所有者。
+    let photographer_id = quotes_repo::find_photographer_by_user(&state.orm, user_id)
+        .await?
+        .map(|p| p.id);
+    let team_ids = teams_repo::list_owned_team_ids(&state.orm, user_id).await?;
+    if photographer_id.is_none() && team_ids.is_empty() {
+        return Err(DomainError::NotFound.into());
+    }
+    let (month, start, end) = parse_month(query.month.as_deref(), Utc::now())?;
+
+    let mut items = Vec::new();
+    let mut received = Money::ZERO;
+    let payments = orders_repo::list_success_payments_by_provider(
+        &state.orm,
+        photographer_id,
+        &team_ids,
+        start,
+        end,
+    )
+    .await?;
+    for payment in payments {
+        let amount = Money::from(payment.amount);
+        received += amount;
+        items.push(PhotographerEarningsItem {
+            kind: "payment".to_string(),
+            order_id: Some(payment.order_id),
+            reference_id: payment.id,
+            amount,
+            occurred_at: payment.paid_at.unwrap_or(payment.created_at).to_rfc3339(),
+        });
+    }
+
+    let mut refunded = Money::ZERO;
+    let refunds = refunds_repo::list_completed_refunds_by_provider(
+        &state.orm,
+        photographer_id,
+        &team_ids,
+        start,
+        end,
+    )
+    .await?;
+    for refund in refunds {
+        let amount = Money::from(refund.approved_amount.unwrap_or(refund.amount));
+        refunded += amount;
+        items.push(PhotographerEarningsItem {
+            kind: "refund".to_string(),
+            order_id: Some(refund.order_id),
+            reference_id: refund.id,
+            amount: -amount,
+            occurred_at: refund.completed_at.unwrap_or(refund.updated_at).to_rfc3339(),
+        });
+    }
+
+    // 服务费在订单付清时计提、取消时冲回，记在承接方（摄影师或团队）账户上，金额为负即扣费。
+    let mut service_fee = Money::ZERO;
+    let owners = photographer_id
+        .map(AccountOwner::Photographer)
+        .into_iter()
+        .chain(team_ids.iter().copied().map(AccountOwner::Team));
+    for owner in owners {
+        let Some(account) =
+            ledger_repo::find_account(&state.orm, owner.owner_type(), owner.owner_id()).await?
+        else {
+            continue;
+        };
+        let lines =
+            ledger_repo::list_account_lines_between(&state.orm, account.id, start, end).await?;
+        let entry_ids = lines.iter().map(|l| l.entry_id).collect();
+        let entries: HashMap<i64, ledger_entries::Model> =
+            ledger_repo::list_entries_by_ids(&state.orm, entry_ids)
+                .await?
+                .into_iter()
+                .map(|e| (e.id, e))
+                .collect();
+        for line in lines {
+            let Some(entry) = entries.get(&line.entry_id) else {
+                continue;
+            };
+            if !matches!(entry.entry_type.as_str(), "service_fee" | "service_fee_reversal") {
+                continue;
+            }
+            let amount = Money::from(line.amount);
+            service_fee -= amount;
+            items.push(PhotographerEarningsItem {
+                kind: entry.entry_type.clone(),
+                order_id: entry.order_id,
+                reference_id: entry.id,
+                amount,
+                occurred_at: line.created_at.to_rfc3339(),
+            });
+        }
+    }
+
+    items.sort_by(|a, b| a.occurred_at.cmp(&b.occurred_at));
+    Ok(PhotographerEarningsResp {
+        month,
+        received_amount: received,
+        refunded_amount: refunded,
+        service_fee_amount: service_fee,
+        net_amount: received - refunded - service_fee,
+        items,
+    })
+}
+
+/// 导出月度收支明细 CSV，返回（月份, CSV 文本）。
+pub async fn export_my_earnings_csv(
+    state: &AppState,
+    user_id: i64,
+    query: PhotographerEarningsQuery,
+) -> ServiceResult<(String, String)> {
+    let earnings = get_my_earnings(state, user_id, query).await?;
+    let header = ["occurred_at", "kind", "order_id", "reference_id", "amount"];
+    let rows = earnings.items.into_iter().map(|item| {
+        vec![
+            item.occurred_at,
+            item.kind,
+            item.order_id.map(|v| v.to_string()).unwrap_or_default(),
+            item.reference_id.to_string(),
+            item.amount.to_string(),
+        ]
+    });
+    Ok((earnings.month, render_csv(&header, rows)))
+}
+
+/// 解析 `YYYY-MM`，缺省为 `now` 所在月份，返回（月份, 月初, 次月月初）。
+fn parse_month(
+    value: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<(String, DateTime<Utc>, DateTime<Utc>), DomainError> {
+    let first = match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(value) => NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d")
+            .map_err(|_| DomainError::BadRequest("invalid_month".to_string()))?,
+        None => NaiveDate::from_ymd_opt(now.year(), now.month(), 1).unwrap(),
+    };
+    let next = first
+        .checked_add_months(Months::new(1))
+        .ok_or_else(|| DomainError::BadRequest("invalid_month".to_string()))?;
+    Ok((
+        first.format("%Y-%m").to_string(),
+        Utc.from_utc_datetime(&first.and_hms_opt(0, 0, 0).unwrap()),
+        Utc.from_utc_datetime(&next.and_hms_opt(0, 0, 0).unwrap()),
+    ))
+}
+
 fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
     use std::str::FromStr;
     f64::from_str(&v.to_string()).unwrap_or(0.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_month_covers_whole_month() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 15, 8, 0, 0).unwrap();
+        let (month, start, end) = parse_month(Some("2025-12"), now).unwrap();
+        assert_eq!(month, "2025-12");
+        assert_eq!(start, Utc.with_ymd_and_hms(2025, 12, 1, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+
+        let (month, _, _) = parse_month(None, now).unwrap();
+        assert_eq!(month, "2026-03");
+        assert!(parse_month(Some("2026-13"), now).is_err());
+        assert!(parse_month(Some("2026-03-01"), now).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use sea_orm::ConnectionTrait;
+
+use crate::entity::{orders, teams};
+use crate::errors::{DomainError, ServiceResult};
+use crate::repositories::{orders_repo, teams_repo};
+
+/// 团队成员角色（team_members.role），按权限从低到高排序。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TeamRole {
+    Member,
+    Admin,
+    Owner,
+}
+
+impl TeamRole {
+    pub fn parse(role: &str) -> Option<Self> {
+        match role {
+            "member" => Some(TeamRole::Member),
+            "admin" => Some(TeamRole::Admin),
+            "owner" => Some(TeamRole::Owner),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TeamRole::Member => "member",
+            TeamRole::Admin => "admin",
+            TeamRole::Owner => "owner",
+        }
+    }
+}
+
+/// 代表服务方（个人摄影师或团队）执行的操作。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderAction {
+    /// 查看报价、订单及其时间线、交付等。
+    View,
+    /// 订单会话内沟通。
+    Chat,
+    /// 提交交付。
+    Deliver,
+    /// 提交、修改、撤回报价。
+    Quote,
+    /// 取消订单、处理退款、发起纠纷。
+    ManageOrder,
+    /// 增删成员。
+    ManageMembers,
+}
+
+impl ProviderAction {
+    /// 团队内执行该操作所需的最低角色。
+    pub fn min_team_role(self) -> TeamRole {
+        match self {
+            ProviderAction::View | ProviderAction::Chat | ProviderAction::Deliver => {
+                TeamRole::Member
+            }
+            ProviderAction::Quote | ProviderAction::ManageOrder | ProviderAction::ManageMembers => {
+                TeamRole::Admin
+            }
+        }
+    }
+}
+
+/// 订单中的一方：下单用户或服务方。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderParty {
+    Customer,
+    Provider,
+}
+
+impl OrderParty {
+    /// 与退款 responsible_party 等既有字段一致的取值。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OrderParty::Customer => "user",
+            OrderParty::Provider => "photographer",
+        }
+    }
+}
+
+/// 用户在团队中的角色；团队所有者以 teams.owner_user_id 为准。
+pub async fn team_role<C: ConnectionTrait>(
+    conn: &C,
+    team_id: i64,
+    user_id: i64,
+) -> ServiceResult<Option<TeamRole>> {
+    let team = teams_repo::find_team_by_id(conn, team_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    member_role(conn, &team, user_id).await
+}
+
+/// 校验用户可代表团队执行操作，返回其角色。
+pub async fn ensure_team_action<C: ConnectionTrait>(
+    conn: &C,
+    team_id: i64,
+    user_id: i64,
+    action: ProviderAction,
+) -> ServiceResult<TeamRole> {
+    let team = teams_repo::find_team_by_id(conn, team_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let role = member_role(conn, &team, user_id).await?;
+    match role {
+        Some(role) if team_allows(&team, role, action) => Ok(role),
+        _ => Err(DomainError::Forbidden.into()),
+    }
+}
+
+/// 用户能否代表服务方（个人摄影师本人或团队成员）执行操作。
+pub async fn can_act_for_provider<C: ConnectionTrait>(
+    conn: &C,
+    photographer_id: Option<i64>,
+    team_id: Option<i64>,
+    user_id: i64,
+    action: ProviderAction,
+) -> ServiceResult<bool> {
+    if let Some(pid) = photographer_id
+        && orders_repo::find_photographer_user_id(conn, pid).await? == Some(user_id)
+    {
+        return Ok(true);
+    }
+    let Some(team_id) = team_id else {
+        return Ok(false);
+    };
+    let Some(team) = teams_repo::find_team_by_id(conn, team_id).await? else {
+        return Ok(false);
+    };
+    let role = member_role(conn, &team, user_id).await?;
+    Ok(role.is_some_and(|role| team_allows(&team, role, action)))
+}
+
+pub async fn ensure_provider_action<C: ConnectionTrait>(
+    conn: &C,
+    photographer_id: Option<i64>,
+    team_id: Option<i64>,
+    user_id: i64,
+    action: ProviderAction,
+) -> ServiceResult<()> {
+    if can_act_for_provider(conn, photographer_id, team_id, user_id, action).await? {
+        Ok(())
+    } else {
+        Err(DomainError::Forbidden.into())
+    }
+}
+
+/// 用户在订单中的身份；服务方按 `action` 校验团队角色，均不满足时为 None。
+pub async fn order_party<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+    user_id: i64,
+    action: ProviderAction,
+) -> ServiceResult<Option<OrderParty>> {
+    if order.user_id == user_id {
+        return Ok(Some(OrderParty::Customer));
+    }
+    let is_provider =
+        can_act_for_provider(conn, order.photographer_id, order.team_id, user_id, action).await?;
+    Ok(is_provider.then_some(OrderParty::Provider))
+}
+
+pub async fn ensure_order_participant<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+    user_id: i64,
+    action: ProviderAction,
+) -> ServiceResult<OrderParty> {
+    order_party(conn, order, user_id, action)
+        .await?
+        .ok_or_else(|| DomainError::Forbidden.into())
+}
+
+/// 代表服务方收款、接收通知的用户：个人摄影师本人，团队为所有者。
+pub async fn provider_user_id<C: ConnectionTrait>(
+    conn: &C,
+    photographer_id: Option<i64>,
+    team_id: Option<i64>,
+) -> ServiceResult<Option<i64>> {
+    if let Some(pid) = photographer_id {
+        return Ok(orders_repo::find_photographer_user_id(conn, pid).await?);
+    }
+    if let Some(team_id) = team_id {
+        let team = teams_repo::find_team_by_id(conn, team_id).await?;
+        return Ok(team.map(|t| t.owner_user_id));
+    }
+    Ok(None)
+}
+
+async fn member_role<C: ConnectionTrait>(
+    conn: &C,
+    team: &teams::Model,
+    user_id: i64,
+) -> ServiceResult<Option<TeamRole>> {
+    if team.owner_user_id == user_id {
+        return Ok(Some(TeamRole::Owner));
+    }
+    let member = teams_repo::find_team_member(conn, team.id, user_id).await?;
+    Ok(member.and_then(|m| TeamRole::parse(&m.role)))
+}
+
+/// 非 active 团队仅可查看。
+fn team_allows(team: &teams::Model, role: TeamRole, action: ProviderAction) -> bool {
+    (team.status == "active" || action == ProviderAction::View) && role >= action.min_team_role()
+}
+
+/// 用户所属（拥有或加入）的团队 ID。
+pub async fn list_team_ids<C: ConnectionTrait>(conn: &C, user_id: i64) -> ServiceResult<Vec<i64>> {
+    let mut team_ids = teams_repo::list_owned_team_ids(conn, user_id).await?;
+    let members = teams_repo::list_team_members_by_user(conn, user_id).await?;
+    team_ids.extend(members.into_iter().map(|m| m.team_id));
+    team_ids.sort_unstable();
+    team_ids.dedup();
+    Ok(team_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_team_role_covers_actions() {
+        let allowed = |role: TeamRole, action: ProviderAction| role >= action.min_team_role();
+        assert!(allowed(TeamRole::Member, ProviderAction::Deliver));
+        assert!(allowed(TeamRole::Member, ProviderAction::Chat));
+        assert!(!allowed(TeamRole::Member, ProviderAction::Quote));
+        assert!(allowed(TeamRole::Admin, ProviderAction::Quote));
+        assert!(allowed(TeamRole::Owner, ProviderAction::ManageMembers));
+        assert_eq!(TeamRole::parse("owner"), Some(TeamRole::Owner));
+        assert_eq!(TeamRole::parse("guest"), None);
+    }
+}
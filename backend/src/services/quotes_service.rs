@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::str::FromStr;
 
 use chrono::{Duration, Utc};
 use sea_orm::TransactionTrait;
@@ -7,11 +6,18 @@ use serde_json::json;
 
 use crate::dto::quotes::{
     AcceptQuoteResp, CreateQuoteReq, MyQuoteListItem, MyQuoteListQuery, QuoteDetailResp,
-    QuoteItemResp, QuoteListItem, QuoteListQuery, QuoteResp, QuoteVersionItem, UpdateQuoteReq,
+    QuoteItemChange, QuoteItemReq, QuoteItemResp, QuoteListItem, QuoteListQuery, QuoteResp,
+    QuoteVersionDiffQuery, QuoteVersionDiffResp, QuoteVersionItem, UpdateQuoteReq,
 };
 use crate::dto::pagination::{normalize_pagination, Paged};
 use crate::errors::{DomainError, ServiceResult};
-use crate::repositories::quotes_repo;
+use crate::money::Money;
+use crate::repositories::{demands_repo, orders_repo, quotes_repo};
+use crate::services::provider_access_service::{self, ProviderAction};
+use crate::services::{
+    fee_service, notifications_service, order_state_service, payment_plan_service,
+    refund_policy_service,
+};
 use crate::state::AppState;
 
 const QUOTE_EXPIRE_DAYS: i64 = 7;
@@ -32,8 +38,6 @@ pub async fn list_quotes(
         return Err(DomainError::Forbidden.into());
     }
 
-    quotes_repo::expire_quotes_for_demand(&state.orm, demand_id).await?;
-
     let (page, page_size) = normalize_pagination(query.page, query.page_size);
     let (rows, total) =
         quotes_repo::list_quotes_by_demand(&state.orm, demand_id, page, page_size).await?;
@@ -48,7 +52,7 @@ pub async fn list_quotes(
     for item in items {
         items_map.entry(item.quote_id).or_default().push(QuoteItemResp {
             name: item.name,
-            price: decimal_to_f64(item.price),
+            price: Money::from(item.price),
             quantity: item.quantity,
         });
     }
@@ -59,7 +63,7 @@ pub async fn list_quotes(
             id: q.id,
             demand_id: q.demand_id,
             status: q.status,
-            total_price: decimal_to_f64(q.total_price),
+            total_price: Money::from(q.total_price),
             photographer_id: q.photographer_id,
             team_id: q.team_id,
             version: q.version,
@@ -76,33 +80,18 @@ pub async fn get_quote(
     user_id: i64,
     quote_id: i64,
 ) -> ServiceResult<QuoteDetailResp> {
-    quotes_repo::expire_quote_by_id(&state.orm, quote_id).await?;
-
     let quote = quotes_repo::find_quote_by_id(&state.orm, quote_id)
         .await?
         .ok_or(DomainError::NotFound)?;
 
-    let demand = quotes_repo::find_demand_by_id(&state.orm, quote.demand_id)
-        .await?
-        .ok_or(DomainError::NotFound)?;
-    let is_owner = demand.user_id == user_id;
-    let is_photographer = match quote.photographer_id {
-        Some(pid) => {
-            let owner = quotes_repo::find_photographer_by_id(&state.orm, pid).await?;
-            owner.map(|o| o.user_id == user_id).unwrap_or(false)
-        }
-        None => false,
-    };
-    if !is_owner && !is_photographer {
-        return Err(DomainError::Forbidden.into());
-    }
+    ensure_quote_viewer(state, &quote, user_id).await?;
 
     let items = quotes_repo::list_quote_items_by_quote_id(&state.orm, quote_id)
         .await?
         .into_iter()
         .map(|it| QuoteItemResp {
             name: it.name,
-            price: decimal_to_f64(it.price),
+            price: Money::from(it.price),
             quantity: it.quantity,
         })
         .collect();
@@ -117,7 +106,7 @@ pub async fn get_quote(
         id: quote.id,
         demand_id: quote.demand_id,
         status: quote.status,
-        total_price: decimal_to_f64(quote.total_price),
+        total_price: Money::from(quote.total_price),
         photographer_id: quote.photographer_id,
         team_id: quote.team_id,
         version: quote.version,
@@ -133,17 +122,21 @@ pub async fn list_my_quotes(
     user_id: i64,
     query: MyQuoteListQuery,
 ) -> ServiceResult<Paged<MyQuoteListItem>> {
-    let photographer = quotes_repo::find_photographer_by_user(&state.orm, user_id)
+    // 本人报价及所属团队的报价（团队成员均可查看）。
+    let photographer_id = quotes_repo::find_photographer_by_user(&state.orm, user_id)
         .await?
-        .ok_or(DomainError::NotFound)?;
-
-    quotes_repo::expire_quotes_for_photographer(&state.orm, photographer.id).await?;
+        .map(|p| p.id);
+    let team_ids = provider_access_service::list_team_ids(&state.orm, user_id).await?;
+    if photographer_id.is_none() && team_ids.is_empty() {
+        return Err(DomainError::NotFound.into());
+    }
 
     let (page, page_size) = normalize_pagination(query.page, query.page_size);
 
-    let (rows, total) = quotes_repo::list_quotes_by_photographer(
+    let (rows, total) = quotes_repo::list_quotes_by_provider(
         &state.orm,
-        photographer.id,
+        photographer_id,
+        team_ids,
         query.status,
         query.demand_id,
         page,
@@ -162,7 +155,7 @@ pub async fn list_my_quotes(
     for item in items {
         items_map.entry(item.quote_id).or_default().push(QuoteItemResp {
             name: item.name,
-            price: decimal_to_f64(item.price),
+            price: Money::from(item.price),
             quantity: item.quantity,
         });
     }
@@ -180,8 +173,9 @@ pub async fn list_my_quotes(
         .map(|q| MyQuoteListItem {
             id: q.id,
             demand_id: q.demand_id,
+            team_id: q.team_id,
             status: q.status,
-            total_price: decimal_to_f64(q.total_price),
+            total_price: Money::from(q.total_price),
             created_at: q.created_at.to_rfc3339(),
             version: q.version,
             expires_at: q.expires_at.map(|v| v.to_rfc3339()),
@@ -203,17 +197,18 @@ pub async fn withdraw_quote(
         .await?
         .ok_or(DomainError::NotFound)?;
 
-    if quote.status != "pending" {
+    if !matches!(quote.status.as_str(), "pending" | "stale") {
         return Err(DomainError::InvalidStatus.into());
     }
 
-    let photographer_id = quote.photographer_id.ok_or(DomainError::Forbidden)?;
-    let photographer = quotes_repo::find_photographer_by_id(&state.orm, photographer_id)
-        .await?
-        .ok_or(DomainError::NotFound)?;
-    if photographer.user_id != user_id {
-        return Err(DomainError::Forbidden.into());
-    }
+    provider_access_service::ensure_provider_action(
+        &state.orm,
+        quote.photographer_id,
+        quote.team_id,
+        user_id,
+        ProviderAction::Quote,
+    )
+    .await?;
 
     let updated = quotes_repo::update_quote_status(&state.orm, quote, "expired".to_string()).await?;
 
@@ -236,30 +231,31 @@ pub async fn create_quote(
         return Err(DomainError::BadRequest("photographer_or_team_required".to_string()).into());
     }
 
-    if let Some(pid) = req.photographer_id {
-        let owner = quotes_repo::find_photographer_by_id(&state.orm, pid).await?;
-        if owner.map(|o| o.user_id) != Some(user_id) {
-            return Err(DomainError::Forbidden.into());
-        }
+    provider_access_service::ensure_provider_action(
+        &state.orm,
+        req.photographer_id,
+        req.team_id,
+        user_id,
+        ProviderAction::Quote,
+    )
+    .await?;
+
+    let demand = quotes_repo::find_demand_by_id(&state.orm, req.demand_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if demand.status != "open" {
+        return Err(DomainError::BadRequest("demand_not_open".to_string()).into());
     }
 
     let expires_at = Utc::now() + Duration::days(QUOTE_EXPIRE_DAYS);
-    let items_json = json!(req
-        .items
-        .iter()
-        .map(|item| json!({
-            "name": item.name,
-            "price": item.price,
-            "quantity": item.quantity
-        }))
-        .collect::<Vec<_>>());
+    let items_json = quote_items_json(&req.items);
 
     let txn = state.orm.begin().await?;
     let q = crate::entity::quotes::ActiveModel {
         demand_id: sea_orm::ActiveValue::Set(req.demand_id),
         photographer_id: sea_orm::ActiveValue::Set(req.photographer_id),
         team_id: sea_orm::ActiveValue::Set(req.team_id),
-        total_price: sea_orm::ActiveValue::Set(decimal_from_f64(req.total_price)),
+        total_price: sea_orm::ActiveValue::Set(req.total_price.amount()),
         status: sea_orm::ActiveValue::Set("pending".to_string()),
         version: sea_orm::ActiveValue::Set(1),
         expires_at: sea_orm::ActiveValue::Set(Some(expires_at.into())),
@@ -272,7 +268,7 @@ pub async fn create_quote(
         let qi = crate::entity::quote_items::ActiveModel {
             quote_id: sea_orm::ActiveValue::Set(quote.id),
             name: sea_orm::ActiveValue::Set(item.name),
-            price: sea_orm::ActiveValue::Set(decimal_from_f64(item.price)),
+            price: sea_orm::ActiveValue::Set(item.price.amount()),
             quantity: sea_orm::ActiveValue::Set(item.quantity),
             ..Default::default()
         };
@@ -282,10 +278,12 @@ pub async fn create_quote(
     let version = crate::entity::quote_versions::ActiveModel {
         quote_id: sea_orm::ActiveValue::Set(quote.id),
         version: sea_orm::ActiveValue::Set(1),
-        total_price: sea_orm::ActiveValue::Set(decimal_from_f64(req.total_price)),
+        total_price: sea_orm::ActiveValue::Set(req.total_price.amount()),
         items: sea_orm::ActiveValue::Set(items_json),
         note: sea_orm::ActiveValue::Set(req.note),
         created_by: sea_orm::ActiveValue::Set(user_id),
+        proposer_role: sea_orm::ActiveValue::Set("photographer".to_string()),
+        status: sea_orm::ActiveValue::Set("adopted".to_string()),
         ..Default::default()
     };
     quotes_repo::create_quote_version(&txn, version).await?;
@@ -309,65 +307,63 @@ pub async fn update_quote(
         return Err(DomainError::ItemsRequired.into());
     }
 
-    let items_json = json!(req
-        .items
-        .iter()
-        .map(|item| json!({
-            "name": item.name,
-            "price": item.price,
-            "quantity": item.quantity
-        }))
-        .collect::<Vec<_>>());
+    let items_json = quote_items_json(&req.items);
 
     let txn = state.orm.begin().await?;
-    let quote = quotes_repo::find_quote_by_id(&txn, quote_id)
+    // 与接受报价相同，按 需求 → 报价 的顺序加锁。
+    let demand_id = quotes_repo::find_quote_by_id(&txn, quote_id)
+        .await?
+        .ok_or(DomainError::NotFound)?
+        .demand_id;
+    let demand = quotes_repo::find_demand_for_update(&txn, demand_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let quote = quotes_repo::find_quote_for_update(&txn, quote_id)
         .await?
         .ok_or(DomainError::NotFound)?;
 
-    if quote.status != "pending" {
+    // 需求变更后的 stale 报价经修改重新生效；需求已成交或关闭时不可再修改。
+    if !matches!(quote.status.as_str(), "pending" | "stale") {
         return Err(DomainError::InvalidStatus.into());
     }
-
-    let photographer_id = quote.photographer_id.ok_or(DomainError::Forbidden)?;
-    let photographer = quotes_repo::find_photographer_by_id(&txn, photographer_id)
-        .await?
-        .ok_or(DomainError::NotFound)?;
-    if photographer.user_id != user_id {
-        return Err(DomainError::Forbidden.into());
+    if demand.status != "open" {
+        return Err(DomainError::BadRequest("demand_not_open".to_string()).into());
     }
 
-    let new_version = quote.version + 1;
+    provider_access_service::ensure_provider_action(
+        &txn,
+        quote.photographer_id,
+        quote.team_id,
+        user_id,
+        ProviderAction::Quote,
+    )
+    .await?;
+
+    // 摄影师重新报价即视为对未回应还价的答复。
+    quotes_repo::supersede_proposed_versions(&txn, quote_id).await?;
+    let new_version = quotes_repo::next_quote_version(&txn, quote_id).await?;
     let expires_at = Utc::now() + Duration::days(QUOTE_EXPIRE_DAYS);
 
     let updated = quotes_repo::update_quote(
         &txn,
         quote,
-        decimal_from_f64(req.total_price),
+        req.total_price.amount(),
         new_version,
         expires_at,
     )
     .await?;
 
-    quotes_repo::delete_quote_items(&txn, quote_id).await?;
-
-    for item in req.items {
-        let qi = crate::entity::quote_items::ActiveModel {
-            quote_id: sea_orm::ActiveValue::Set(updated.id),
-            name: sea_orm::ActiveValue::Set(item.name),
-            price: sea_orm::ActiveValue::Set(decimal_from_f64(item.price)),
-            quantity: sea_orm::ActiveValue::Set(item.quantity),
-            ..Default::default()
-        };
-        quotes_repo::create_quote_item(&txn, qi).await?;
-    }
+    replace_quote_items(&txn, updated.id, req.items).await?;
 
     let version = crate::entity::quote_versions::ActiveModel {
         quote_id: sea_orm::ActiveValue::Set(updated.id),
         version: sea_orm::ActiveValue::Set(new_version),
-        total_price: sea_orm::ActiveValue::Set(decimal_from_f64(req.total_price)),
+        total_price: sea_orm::ActiveValue::Set(req.total_price.amount()),
         items: sea_orm::ActiveValue::Set(items_json),
         note: sea_orm::ActiveValue::Set(req.note),
         created_by: sea_orm::ActiveValue::Set(user_id),
+        proposer_role: sea_orm::ActiveValue::Set("photographer".to_string()),
+        status: sea_orm::ActiveValue::Set("adopted".to_string()),
         ..Default::default()
     };
     quotes_repo::create_quote_version(&txn, version).await?;
@@ -389,37 +385,154 @@ pub async fn list_quote_versions(
     let quote = quotes_repo::find_quote_by_id(&state.orm, quote_id)
         .await?
         .ok_or(DomainError::NotFound)?;
-    let demand = quotes_repo::find_demand_by_id(&state.orm, quote.demand_id)
-        .await?
-        .ok_or(DomainError::NotFound)?;
-    let is_owner = demand.user_id == user_id;
-    let is_photographer = match quote.photographer_id {
-        Some(pid) => {
-            let owner = quotes_repo::find_photographer_by_id(&state.orm, pid).await?;
-            owner.map(|o| o.user_id == user_id).unwrap_or(false)
-        }
-        None => false,
-    };
-    if !is_owner && !is_photographer {
-        return Err(DomainError::Forbidden.into());
-    }
+    ensure_quote_viewer(state, &quote, user_id).await?;
 
     let rows = quotes_repo::list_quote_versions(&state.orm, quote_id).await?;
 
     Ok(rows
         .into_iter()
-        .map(|v| QuoteVersionItem {
-            id: v.id,
-            version: v.version,
-            total_price: decimal_to_f64(v.total_price),
-            items: v.items,
-            note: v.note,
-            created_by: v.created_by,
-            created_at: v.created_at.to_rfc3339(),
-        })
+        .map(|v| to_version_item(v, quote.version))
         .collect())
 }
 
+/// 两个版本间的条目差异：按条目名称配对（同名多条按出现顺序配对），价格或数量变化计为 modified。
+pub async fn diff_quote_versions(
+    state: &AppState,
+    user_id: i64,
+    quote_id: i64,
+    query: QuoteVersionDiffQuery,
+) -> ServiceResult<QuoteVersionDiffResp> {
+    let quote = quotes_repo::find_quote_by_id(&state.orm, quote_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    ensure_quote_viewer(state, &quote, user_id).await?;
+
+    let from = quotes_repo::find_quote_version(&state.orm, quote_id, query.from)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let to = quotes_repo::find_quote_version(&state.orm, quote_id, query.to)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let from_items = parse_version_items(&from)?;
+    let to_items = parse_version_items(&to)?;
+    let (added, removed, modified) = diff_items(&from_items, &to_items);
+
+    let from_total = Money::from(from.total_price);
+    let to_total = Money::from(to.total_price);
+    Ok(QuoteVersionDiffResp {
+        quote_id,
+        from_version: from.version,
+        to_version: to.version,
+        from_total,
+        to_total,
+        total_change: to_total - from_total,
+        added,
+        removed,
+        modified,
+    })
+}
+
+/// 需求发布者对 pending 报价提出还价，生成待摄影师回应的新版本；当前生效版本不变。
+pub async fn create_counter_offer(
+    state: &AppState,
+    user_id: i64,
+    quote_id: i64,
+    req: UpdateQuoteReq,
+) -> ServiceResult<QuoteVersionItem> {
+    validate_counter_offer(&req.items, req.total_price)?;
+
+    let txn = state.orm.begin().await?;
+    let quote = quotes_repo::find_quote_for_update(&txn, quote_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let demand = quotes_repo::find_demand_by_id(&txn, quote.demand_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if demand.user_id != user_id {
+        return Err(DomainError::Forbidden.into());
+    }
+    if quote.status != "pending" {
+        return Err(DomainError::InvalidStatus.into());
+    }
+    if quote.expires_at.is_some_and(|t| t <= Utc::now()) {
+        return Err(DomainError::BadRequest("quote_expired".to_string()).into());
+    }
+
+    // 同一报价仅保留一份待回应的还价。
+    quotes_repo::supersede_proposed_versions(&txn, quote_id).await?;
+    let version = quotes_repo::next_quote_version(&txn, quote_id).await?;
+    let model = crate::entity::quote_versions::ActiveModel {
+        quote_id: sea_orm::ActiveValue::Set(quote_id),
+        version: sea_orm::ActiveValue::Set(version),
+        total_price: sea_orm::ActiveValue::Set(req.total_price.amount()),
+        items: sea_orm::ActiveValue::Set(quote_items_json(&req.items)),
+        note: sea_orm::ActiveValue::Set(req.note),
+        created_by: sea_orm::ActiveValue::Set(user_id),
+        proposer_role: sea_orm::ActiveValue::Set("customer".to_string()),
+        status: sea_orm::ActiveValue::Set("proposed".to_string()),
+        ..Default::default()
+    };
+    let created = quotes_repo::create_quote_version(&txn, model).await?;
+
+    let recipient =
+        provider_access_service::provider_user_id(&txn, quote.photographer_id, quote.team_id)
+            .await?;
+    if let Some(recipient) = recipient {
+        notifications_service::notify(
+            &txn,
+            recipient,
+            "quote_counter_offer",
+            "收到还价".to_string(),
+            Some(format!("需求 #{} 的发布者对你的报价 #{} 提出了还价", demand.id, quote_id)),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+
+    Ok(to_version_item(created, quote.version))
+}
+
+/// 摄影师接受还价：还价版本成为当前生效版本，报价金额与条目随之更新并刷新有效期。
+pub async fn accept_counter_offer(
+    state: &AppState,
+    user_id: i64,
+    quote_id: i64,
+    version: i32,
+) -> ServiceResult<QuoteVersionItem> {
+    let txn = state.orm.begin().await?;
+    let (quote, offer) = load_pending_counter_offer(&txn, user_id, quote_id, version).await?;
+    let items = parse_version_items(&offer)?;
+
+    let expires_at = Utc::now() + Duration::days(QUOTE_EXPIRE_DAYS);
+    let updated =
+        quotes_repo::update_quote(&txn, quote, offer.total_price, offer.version, expires_at)
+            .await?;
+    replace_quote_items(&txn, quote_id, items).await?;
+    let offer = quotes_repo::update_quote_version_status(&txn, offer, "adopted").await?;
+
+    notify_counter_offer_result(&txn, &updated, &offer, true).await?;
+    txn.commit().await?;
+
+    Ok(to_version_item(offer, updated.version))
+}
+
+/// 摄影师拒绝还价：当前生效版本不变。
+pub async fn decline_counter_offer(
+    state: &AppState,
+    user_id: i64,
+    quote_id: i64,
+    version: i32,
+) -> ServiceResult<QuoteVersionItem> {
+    let txn = state.orm.begin().await?;
+    let (quote, offer) = load_pending_counter_offer(&txn, user_id, quote_id, version).await?;
+    let offer = quotes_repo::update_quote_version_status(&txn, offer, "declined").await?;
+
+    notify_counter_offer_result(&txn, &quote, &offer, false).await?;
+    txn.commit().await?;
+
+    Ok(to_version_item(offer, quote.version))
+}
+
 pub async fn accept_quote(
     state: &AppState,
     user_id: i64,
@@ -427,56 +540,426 @@ pub async fn accept_quote(
 ) -> ServiceResult<AcceptQuoteResp> {
     let txn = state.orm.begin().await?;
 
-    let quote = quotes_repo::find_quote_by_id(&txn, quote_id)
+    // 报价所属需求不会变更，先取 demand_id 再按 需求 → 报价 的顺序加锁，
+    // 串行化同一需求下的并发接受，且报价状态以加锁后的读取为准。
+    let demand_id = quotes_repo::find_quote_by_id(&txn, quote_id)
+        .await?
+        .ok_or(DomainError::NotFound)?
+        .demand_id;
+    let demand = quotes_repo::find_demand_for_update(&txn, demand_id)
         .await?
         .ok_or(DomainError::NotFound)?;
-
-    let demand = quotes_repo::find_demand_by_id(&state.orm, quote.demand_id)
+    let quote = quotes_repo::find_quote_for_update(&txn, quote_id)
         .await?
         .ok_or(DomainError::NotFound)?;
 
     if demand.user_id != user_id {
         return Err(DomainError::Forbidden.into());
     }
+    if quote.status == "stale" {
+        return Err(DomainError::BadRequest("quote_stale".to_string()).into());
+    }
     if quote.status != "pending" {
         return Err(DomainError::BadRequest("quote_not_pending".to_string()).into());
     }
+    // 过期由后台任务批量处理，任务执行前到期的报价同样不可接受。
+    if quote.expires_at.is_some_and(|t| t <= chrono::Utc::now()) {
+        return Err(DomainError::BadRequest("quote_expired".to_string()).into());
+    }
+    // 成交时其余报价已全部置为 rejected，订单取消后需求保持 matched，不再接受报价。
+    if demand.status != "open" {
+        return Err(DomainError::BadRequest("demand_not_open".to_string()).into());
+    }
 
     let existing = quotes_repo::find_order_by_quote_id(&txn, quote_id).await?;
     if existing.is_some() {
         return Err(DomainError::Conflict("order_exists".to_string()).into());
     }
+    if quotes_repo::find_active_order_by_demand(&txn, demand.id).await?.is_some() {
+        return Err(DomainError::Conflict("demand_has_active_order".to_string()).into());
+    }
 
-    let total_price = quote.total_price;
+    let quote_items = quotes_repo::list_quote_items_by_quote_id(&txn, quote_id).await?;
+    let total_price = Money::from(quote.total_price);
+    let quote_version = quote.version;
     let photographer_id = quote.photographer_id;
     let team_id = quote.team_id;
     quotes_repo::update_quote_status(&txn, quote, "accepted".to_string()).await?;
+    // 按当前生效版本成交，未回应的还价随之失效。
+    quotes_repo::supersede_proposed_versions(&txn, quote_id).await?;
+
+    let now = chrono::Utc::now();
+    let schedule_start = demand.schedule_start.map(|t| t.with_timezone(&chrono::Utc));
+    let schedule_end = demand.schedule_end.map(|t| t.with_timezone(&chrono::Utc));
+    let settings = payment_plan_service::load_settings(&txn).await?;
+    let plan = payment_plan_service::build_plan(
+        &settings.pay_type,
+        total_price,
+        &settings,
+        now,
+        schedule_start,
+        schedule_end,
+    );
+    let service_fee =
+        fee_service::compute_service_fee(&txn, total_price, demand.is_merchant, demand.city_id)
+            .await?;
+    let deposit_amount = plan
+        .iter()
+        .find(|p| p.stage == "deposit")
+        .map(|p| p.amount)
+        .unwrap_or(Money::ZERO);
+    let refund_policy = refund_policy_service::snapshot_for_order(
+        &txn,
+        photographer_id,
+        demand.merchant_id,
+        Some(&demand.r#type),
+    )
+    .await?;
 
     let order = crate::entity::orders::ActiveModel {
         user_id: sea_orm::ActiveValue::Set(user_id),
         demand_id: sea_orm::ActiveValue::Set(Some(demand.id)),
         quote_id: sea_orm::ActiveValue::Set(Some(quote_id)),
+        quote_version: sea_orm::ActiveValue::Set(Some(quote_version)),
         photographer_id: sea_orm::ActiveValue::Set(photographer_id),
         team_id: sea_orm::ActiveValue::Set(team_id),
         status: sea_orm::ActiveValue::Set("confirmed".to_string()),
-        pay_type: sea_orm::ActiveValue::Set("deposit".to_string()),
-        deposit_amount: sea_orm::ActiveValue::Set(sea_orm::prelude::Decimal::ZERO),
-        total_amount: sea_orm::ActiveValue::Set(total_price),
-        service_fee: sea_orm::ActiveValue::Set(sea_orm::prelude::Decimal::ZERO),
+        pay_type: sea_orm::ActiveValue::Set(settings.pay_type.clone()),
+        deposit_amount: sea_orm::ActiveValue::Set(deposit_amount.amount()),
+        total_amount: sea_orm::ActiveValue::Set(total_price.amount()),
+        service_fee: sea_orm::ActiveValue::Set(service_fee.amount()),
+        schedule_start: sea_orm::ActiveValue::Set(demand.schedule_start),
+        schedule_end: sea_orm::ActiveValue::Set(demand.schedule_end),
+        location: sea_orm::ActiveValue::Set(demand.location.clone()),
+        refund_policy: sea_orm::ActiveValue::Set(Some(refund_policy.clone())),
         ..Default::default()
     };
 
     let created = quotes_repo::create_order(&txn, order).await?;
+    for item in &quote_items {
+        let oi = crate::entity::order_items::ActiveModel {
+            order_id: sea_orm::ActiveValue::Set(created.id),
+            name: sea_orm::ActiveValue::Set(item.name.clone()),
+            price: sea_orm::ActiveValue::Set(item.price),
+            quantity: sea_orm::ActiveValue::Set(item.quantity),
+            ..Default::default()
+        };
+        orders_repo::create_order_item(&txn, oi).await?;
+    }
+    payment_plan_service::save_plan(&txn, created.id, &plan).await?;
+
+    // 合同条款为接受时的完整快照，报价后续修改不影响已成交内容。
+    let terms = json!({
+        "quote_id": quote_id,
+        "quote_version": quote_version,
+        "demand_id": demand.id,
+        "user_id": user_id,
+        "photographer_id": photographer_id,
+        "team_id": team_id,
+        "items": quote_items
+            .iter()
+            .map(|item| json!({
+                "name": item.name,
+                "price": Money::from(item.price),
+                "quantity": item.quantity
+            }))
+            .collect::<Vec<_>>(),
+        "total_amount": total_price,
+        "service_fee": service_fee,
+        "pay_type": settings.pay_type,
+        "deposit_amount": deposit_amount,
+        "payment_plan": plan
+            .iter()
+            .map(|p| json!({
+                "stage": p.stage,
+                "amount": p.amount,
+                "due_at": p.due_at.map(|t| t.to_rfc3339())
+            }))
+            .collect::<Vec<_>>(),
+        "schedule_start": schedule_start.map(|t| t.to_rfc3339()),
+        "schedule_end": schedule_end.map(|t| t.to_rfc3339()),
+        "location": demand.location,
+        "refund_policy": refund_policy,
+        "accepted_at": now.to_rfc3339(),
+    });
+    orders_repo::create_contract(&txn, created.id, quote_version, terms).await?;
+
+    order_state_service::record_initial_status(
+        &txn,
+        &created,
+        Some(user_id),
+        Some("quote_accepted".to_string()),
+    )
+    .await?;
+
+    let siblings = quotes_repo::list_pending_quotes_by_demand(&txn, demand.id).await?;
+    quotes_repo::reject_quotes(&txn, siblings.iter().map(|q| q.id).collect()).await?;
+    for sibling in &siblings {
+        let recipient = provider_access_service::provider_user_id(
+            &txn,
+            sibling.photographer_id,
+            sibling.team_id,
+        )
+        .await?;
+        let Some(recipient) = recipient else {
+            continue;
+        };
+        notifications_service::notify(
+            &txn,
+            recipient,
+            "quote_rejected",
+            "报价未被选中".to_string(),
+            Some(format!("需求 #{} 已选定其他报价，你的报价 #{} 已关闭", demand.id, sibling.id)),
+        )
+        .await?;
+    }
+    demands_repo::update_demand_status(&txn, demand, "matched".to_string()).await?;
     txn.commit().await?;
 
     Ok(AcceptQuoteResp { order_id: created.id })
 }
 
-fn decimal_from_f64(v: f64) -> sea_orm::prelude::Decimal {
-    sea_orm::prelude::Decimal::from_str(&v.to_string())
-        .unwrap_or(sea_orm::prelude::Decimal::ZERO)
+/// 报价查看权限：需求发布者或报价方（含团队成员）。
+async fn ensure_quote_viewer(
+    state: &AppState,
+    quote: &crate::entity::quotes::Model,
+    user_id: i64,
+) -> ServiceResult<()> {
+    let demand = quotes_repo::find_demand_by_id(&state.orm, quote.demand_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let is_owner = demand.user_id == user_id;
+    let is_provider = provider_access_service::can_act_for_provider(
+        &state.orm,
+        quote.photographer_id,
+        quote.team_id,
+        user_id,
+        ProviderAction::View,
+    )
+    .await?;
+    if !is_owner && !is_provider {
+        return Err(DomainError::Forbidden.into());
+    }
+    Ok(())
 }
 
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
+async fn load_pending_counter_offer<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    user_id: i64,
+    quote_id: i64,
+    version: i32,
+) -> ServiceResult<(crate::entity::quotes::Model, crate::entity::quote_versions::Model)> {
+    let quote = quotes_repo::find_quote_for_update(conn, quote_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    provider_access_service::ensure_provider_action(
+        conn,
+        quote.photographer_id,
+        quote.team_id,
+        user_id,
+        ProviderAction::Quote,
+    )
+    .await?;
+    if quote.status != "pending" {
+        return Err(DomainError::InvalidStatus.into());
+    }
+    let offer = quotes_repo::find_quote_version(conn, quote_id, version)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if offer.proposer_role != "customer" || offer.status != "proposed" {
+        return Err(DomainError::BadRequest("counter_offer_not_pending".to_string()).into());
+    }
+    Ok((quote, offer))
+}
+
+async fn notify_counter_offer_result<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    quote: &crate::entity::quotes::Model,
+    offer: &crate::entity::quote_versions::Model,
+    accepted: bool,
+) -> ServiceResult<()> {
+    let Some(demand) = quotes_repo::find_demand_by_id(conn, quote.demand_id).await? else {
+        return Ok(());
+    };
+    let (r#type, title, outcome) = if accepted {
+        ("counter_offer_accepted", "还价已接受", "已接受，报价已按还价更新")
+    } else {
+        ("counter_offer_declined", "还价被拒绝", "被拒绝，报价保持不变")
+    };
+    notifications_service::notify(
+        conn,
+        demand.user_id,
+        r#type,
+        title.to_string(),
+        Some(format!("你对报价 #{} 的还价（版本 {}）{}", quote.id, offer.version, outcome)),
+    )
+    .await
+}
+
+async fn replace_quote_items<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    quote_id: i64,
+    items: Vec<QuoteItemReq>,
+) -> ServiceResult<()> {
+    quotes_repo::delete_quote_items(conn, quote_id).await?;
+    for item in items {
+        let qi = crate::entity::quote_items::ActiveModel {
+            quote_id: sea_orm::ActiveValue::Set(quote_id),
+            name: sea_orm::ActiveValue::Set(item.name),
+            price: sea_orm::ActiveValue::Set(item.price.amount()),
+            quantity: sea_orm::ActiveValue::Set(item.quantity),
+            ..Default::default()
+        };
+        quotes_repo::create_quote_item(conn, qi).await?;
+    }
+    Ok(())
+}
+
+fn quote_items_json(items: &[QuoteItemReq]) -> serde_json::Value {
+    json!(items
+        .iter()
+        .map(|item| json!({
+            "name": item.name,
+            "price": item.price,
+            "quantity": item.quantity
+        }))
+        .collect::<Vec<_>>())
+}
+
+fn parse_version_items(
+    version: &crate::entity::quote_versions::Model,
+) -> ServiceResult<Vec<QuoteItemReq>> {
+    serde_json::from_value(version.items.clone())
+        .map_err(|_| DomainError::BadRequest("invalid_version_items".to_string()).into())
+}
+
+type ItemsDiff = (Vec<QuoteItemResp>, Vec<QuoteItemResp>, Vec<QuoteItemChange>);
+
+fn diff_items(from: &[QuoteItemReq], to: &[QuoteItemReq]) -> ItemsDiff {
+    let mut remaining: Vec<Option<&QuoteItemReq>> = from.iter().map(Some).collect();
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    for new in to {
+        let matched = remaining
+            .iter_mut()
+            .find(|old| old.is_some_and(|old| old.name == new.name))
+            .and_then(Option::take);
+        let Some(old) = matched else {
+            added.push(to_item_resp(new));
+            continue;
+        };
+        if old.price == new.price && old.quantity == new.quantity {
+            continue;
+        }
+        modified.push(QuoteItemChange {
+            name: new.name.clone(),
+            from_price: old.price,
+            to_price: new.price,
+            price_delta: new.price - old.price,
+            from_quantity: old.quantity,
+            to_quantity: new.quantity,
+            subtotal_delta: subtotal(new) - subtotal(old),
+        });
+    }
+    let removed = remaining.into_iter().flatten().map(to_item_resp).collect();
+    (added, removed, modified)
+}
+
+/// 还价被接受后直接成为生效版本，条目单价、数量须为正且总价等于各条目小计之和。
+fn validate_counter_offer(items: &[QuoteItemReq], total_price: Money) -> Result<(), DomainError> {
+    if items.is_empty() {
+        return Err(DomainError::ItemsRequired);
+    }
+    if !total_price.is_positive() || items.iter().any(|item| !item.price.is_positive()) {
+        return Err(DomainError::InvalidAmount);
+    }
+    if items.iter().any(|item| item.quantity <= 0) {
+        return Err(DomainError::BadRequest("invalid_quantity".to_string()));
+    }
+    if items.iter().map(subtotal).sum::<Money>() != total_price {
+        return Err(DomainError::BadRequest("total_price_mismatch".to_string()));
+    }
+    Ok(())
+}
+
+fn subtotal(item: &QuoteItemReq) -> Money {
+    Money::from(item.price.amount() * sea_orm::prelude::Decimal::from(item.quantity))
+}
+
+fn to_item_resp(item: &QuoteItemReq) -> QuoteItemResp {
+    QuoteItemResp {
+        name: item.name.clone(),
+        price: item.price,
+        quantity: item.quantity,
+    }
+}
+
+fn to_version_item(
+    v: crate::entity::quote_versions::Model,
+    binding_version: i32,
+) -> QuoteVersionItem {
+    QuoteVersionItem {
+        id: v.id,
+        version: v.version,
+        total_price: Money::from(v.total_price),
+        is_binding: v.version == binding_version,
+        items: v.items,
+        note: v.note,
+        created_by: v.created_by,
+        proposer_role: v.proposer_role,
+        status: v.status,
+        created_at: v.created_at.to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, cents: i64, quantity: i32) -> QuoteItemReq {
+        QuoteItemReq {
+            name: name.to_string(),
+            price: Money::from(sea_orm::prelude::Decimal::new(cents, 2)),
+            quantity,
+        }
+    }
+
+    #[test]
+    fn test_diff_items() {
+        let from = vec![item("拍摄", 50000, 1), item("精修", 2000, 10), item("化妆", 30000, 1)];
+        let to = vec![item("拍摄", 60000, 1), item("精修", 2000, 10), item("航拍", 40000, 1)];
+        let (added, removed, modified) = diff_items(&from, &to);
+
+        assert_eq!(added.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), ["航拍"]);
+        assert_eq!(removed.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), ["化妆"]);
+        assert_eq!(modified.len(), 1);
+        assert_eq!(modified[0].name, "拍摄");
+        assert_eq!(modified[0].price_delta, Money::from(sea_orm::prelude::Decimal::new(10000, 2)));
+
+        let (_, _, modified) = diff_items(&[item("精修", 2000, 10)], &[item("精修", 2500, 8)]);
+        assert_eq!(modified[0].subtotal_delta, Money::ZERO);
+    }
+
+    #[test]
+    fn test_validate_counter_offer() {
+        let total = |cents| Money::from(sea_orm::prelude::Decimal::new(cents, 2));
+        let items = vec![item("拍摄", 50000, 1), item("精修", 2000, 10)];
+        assert!(validate_counter_offer(&items, total(70000)).is_ok());
+        assert!(validate_counter_offer(&items, total(69999)).is_err());
+        assert!(validate_counter_offer(&[], total(0)).is_err());
+        assert!(validate_counter_offer(&[item("拍摄", -100, 1)], total(-100)).is_err());
+        assert!(validate_counter_offer(&[item("拍摄", 100, 0)], total(0)).is_err());
+        assert!(validate_counter_offer(&[item("拍摄", 0, 1)], total(0)).is_err());
+    }
+
+    #[test]
+    fn test_version_items_round_trip() {
+        let items = vec![item("拍摄", 45050, 2)];
+        let parsed: Vec<QuoteItemReq> =
+            serde_json::from_value(quote_items_json(&items)).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name, "拍摄");
+        assert_eq!(parsed[0].price, items[0].price);
+        assert_eq!(parsed[0].quantity, 2);
+    }
 }
@@ -0,0 +1,289 @@
+use std::collections::{BTreeMap, HashMap};
+
+use sea_orm::prelude::Decimal;
+use sea_orm::ConnectionTrait;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{DomainError, ServiceResult};
+use crate::repositories::configs_repo;
+
+/// 取消退款策略（configs.key），未配置时使用内置策略。
+pub const REFUND_POLICY_CONFIG_KEY: &str = "refund_policy_rules";
+
+/// 内置策略名，与改造前硬编码的比例一致。
+pub const BUILTIN_POLICY_NAME: &str = "builtin_default";
+
+/// 退款档位：按顺序取第一个支付类型匹配且距开拍天数不少于 `min_days_before` 的档位。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundTier {
+    /// 为空时匹配任意支付类型。
+    #[serde(default)]
+    pub pay_type: Option<String>,
+    /// 为空时不限天数（兜底档）。
+    #[serde(default)]
+    pub min_days_before: Option<i64>,
+    /// 退款比例，0 到 1；接受数字或字符串，序列化为字符串。
+    pub ratio: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundPolicy {
+    pub tiers: Vec<RefundTier>,
+}
+
+/// 下单时快照到订单上的策略，后续修改配置不影响已有订单。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundPolicySnapshot {
+    pub name: String,
+    /// 选中来源：photographer / merchant / demand_type / default / builtin。
+    pub source: String,
+    pub tiers: Vec<RefundTier>,
+}
+
+/// 命中的档位，`index` 为其在策略 `tiers` 中的下标。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppliedRefundTier {
+    pub index: usize,
+    pub tier: RefundTier,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct RefundPolicySet {
+    #[serde(default)]
+    pub policies: BTreeMap<String, RefundPolicy>,
+    #[serde(default)]
+    pub default: Option<String>,
+    /// key 为 photographer_id。
+    #[serde(default)]
+    pub photographers: HashMap<String, String>,
+    /// key 为 merchant_id。
+    #[serde(default)]
+    pub merchants: HashMap<String, String>,
+    /// key 为需求类型（demands.type）。
+    #[serde(default)]
+    pub demand_types: HashMap<String, String>,
+}
+
+impl RefundPolicySet {
+    pub fn parse(value: &serde_json::Value) -> Result<Self, DomainError> {
+        let set: RefundPolicySet = serde_json::from_value(value.clone()).map_err(invalid)?;
+        for policy in set.policies.values() {
+            policy.validate()?;
+        }
+        let referenced = set
+            .default
+            .iter()
+            .chain(set.photographers.values())
+            .chain(set.merchants.values())
+            .chain(set.demand_types.values());
+        for name in referenced {
+            if !set.policies.contains_key(name) {
+                return Err(invalid(()));
+            }
+        }
+        Ok(set)
+    }
+
+    /// 选取顺序：摄影师 → 商户 → 需求类型 → 默认策略 → 内置策略。
+    pub fn resolve(
+        &self,
+        photographer_id: Option<i64>,
+        merchant_id: Option<i64>,
+        demand_type: Option<&str>,
+    ) -> RefundPolicySnapshot {
+        let lookup = |map: &HashMap<String, String>, key: Option<String>| {
+            key.and_then(|k| map.get(&k).cloned())
+        };
+        let candidates = [
+            ("photographer", lookup(&self.photographers, photographer_id.map(|v| v.to_string()))),
+            ("merchant", lookup(&self.merchants, merchant_id.map(|v| v.to_string()))),
+            ("demand_type", lookup(&self.demand_types, demand_type.map(str::to_string))),
+            ("default", self.default.clone()),
+        ];
+        candidates
+            .into_iter()
+            .find_map(|(source, name)| {
+                let name = name?;
+                let policy = self.policies.get(&name)?;
+                Some(RefundPolicySnapshot {
+                    name,
+                    source: source.to_string(),
+                    tiers: policy.tiers.clone(),
+                })
+            })
+            .unwrap_or_else(builtin_policy)
+    }
+}
+
+impl RefundPolicy {
+    fn validate(&self) -> Result<(), DomainError> {
+        if valid_tiers(&self.tiers) { Ok(()) } else { Err(invalid(())) }
+    }
+}
+
+impl RefundPolicySnapshot {
+    /// 从订单快照读取。仅历史订单（列为 NULL）按内置策略处理；
+    /// 快照无法解析或档位非法时返回错误，不静默套用内置比例。
+    pub fn from_order(value: Option<&serde_json::Value>) -> ServiceResult<Self> {
+        let Some(value) = value else {
+            return Ok(builtin_policy());
+        };
+        let snapshot: RefundPolicySnapshot = serde_json::from_value(value.clone())
+            .map_err(|err| anyhow::anyhow!("invalid refund policy snapshot: {err}"))?;
+        if !valid_tiers(&snapshot.tiers) {
+            return Err(anyhow::anyhow!("invalid refund policy snapshot tiers").into());
+        }
+        Ok(snapshot)
+    }
+
+    /// 按距开拍天数和支付类型取命中档位，未命中时不退款。
+    pub fn apply(&self, pay_type: &str, days_before: i64) -> (Decimal, Option<AppliedRefundTier>) {
+        let hit = self.tiers.iter().enumerate().find(|(_, t)| {
+            t.pay_type.as_deref().is_none_or(|p| p == pay_type)
+                && t.min_days_before.is_none_or(|d| days_before >= d)
+        });
+        match hit {
+            Some((index, tier)) => {
+                (tier.ratio, Some(AppliedRefundTier { index, tier: tier.clone() }))
+            }
+            None => (Decimal::ZERO, None),
+        }
+    }
+}
+
+/// 定金订单：≥7 天 80%，≥3 天 50%，否则不退；其他订单：≥7 天 90%，≥3 天 70%，否则 50%。
+pub fn builtin_policy() -> RefundPolicySnapshot {
+    let tier = |pay_type: Option<&str>, min_days_before: Option<i64>, tenths: i64| RefundTier {
+        pay_type: pay_type.map(str::to_string),
+        min_days_before,
+        ratio: Decimal::new(tenths, 1),
+    };
+    RefundPolicySnapshot {
+        name: BUILTIN_POLICY_NAME.to_string(),
+        source: "builtin".to_string(),
+        tiers: vec![
+            tier(Some("deposit"), Some(7), 8),
+            tier(Some("deposit"), Some(3), 5),
+            tier(Some("deposit"), None, 0),
+            tier(None, Some(7), 9),
+            tier(None, Some(3), 7),
+            tier(None, None, 5),
+        ],
+    }
+}
+
+pub async fn load_rules<C: ConnectionTrait>(conn: &C) -> ServiceResult<RefundPolicySet> {
+    let Some(config) = configs_repo::find_config_by_key(conn, REFUND_POLICY_CONFIG_KEY).await?
+    else {
+        return Ok(RefundPolicySet::default());
+    };
+    // 写入时已校验；历史脏数据不阻塞下单，按未配置处理。
+    Ok(RefundPolicySet::parse(&config.value).unwrap_or_else(|_| {
+        tracing::warn!("refund_policy_rules_invalid");
+        RefundPolicySet::default()
+    }))
+}
+
+/// 下单时解析适用策略并序列化为订单快照。
+pub async fn snapshot_for_order<C: ConnectionTrait>(
+    conn: &C,
+    photographer_id: Option<i64>,
+    merchant_id: Option<i64>,
+    demand_type: Option<&str>,
+) -> ServiceResult<serde_json::Value> {
+    let rules = load_rules(conn).await?;
+    let snapshot = rules.resolve(photographer_id, merchant_id, demand_type);
+    Ok(serde_json::to_value(snapshot).map_err(anyhow::Error::from)?)
+}
+
+fn valid_tiers(tiers: &[RefundTier]) -> bool {
+    !tiers.is_empty()
+        && tiers.iter().all(|t| t.ratio >= Decimal::ZERO && t.ratio <= Decimal::ONE)
+}
+
+fn invalid<E>(_: E) -> DomainError {
+    DomainError::BadRequest("invalid_refund_policy_rules".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_policy_matches_legacy_ratios() {
+        let policy = builtin_policy();
+        let ratio = |pay_type: &str, days: i64| policy.apply(pay_type, days).0;
+        assert_eq!(ratio("deposit", 10), Decimal::new(8, 1));
+        assert_eq!(ratio("deposit", 3), Decimal::new(5, 1));
+        assert_eq!(ratio("deposit", 1), Decimal::ZERO);
+        assert_eq!(ratio("full", 7), Decimal::new(9, 1));
+        assert_eq!(ratio("phase", 5), Decimal::new(7, 1));
+        assert_eq!(ratio("full", -2), Decimal::new(5, 1));
+        assert_eq!(policy.apply("full", -2).1.map(|t| t.index), Some(5));
+    }
+
+    #[test]
+    fn test_snapshot_from_order() {
+        assert_eq!(RefundPolicySnapshot::from_order(None).unwrap(), builtin_policy());
+
+        // 早期快照以 JSON 数字保存比例。
+        let legacy = serde_json::json!({
+            "name": "strict",
+            "source": "default",
+            "tiers": [{ "min_days_before": 14, "ratio": 0.35 }]
+        });
+        let snapshot = RefundPolicySnapshot::from_order(Some(&legacy)).unwrap();
+        assert_eq!(snapshot.tiers[0].ratio, Decimal::new(35, 2));
+        let round_trip = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(round_trip["tiers"][0]["ratio"], "0.35");
+
+        let corrupt = serde_json::json!({ "name": "strict", "tiers": "oops" });
+        assert!(RefundPolicySnapshot::from_order(Some(&corrupt)).is_err());
+        let out_of_range = serde_json::json!({
+            "name": "strict",
+            "source": "default",
+            "tiers": [{ "ratio": "1.5" }]
+        });
+        assert!(RefundPolicySnapshot::from_order(Some(&out_of_range)).is_err());
+    }
+
+    #[test]
+    fn test_rule_set_resolves_by_priority() {
+        let rules = RefundPolicySet::parse(&serde_json::json!({
+            "policies": {
+                "strict": { "tiers": [{ "min_days_before": 14, "ratio": 0.5 }] },
+                "flexible": { "tiers": [{ "ratio": 1 }] }
+            },
+            "default": "strict",
+            "photographers": { "3": "flexible" },
+            "demand_types": { "wedding": "strict" }
+        }))
+        .unwrap();
+
+        let picked = rules.resolve(Some(3), None, Some("wedding"));
+        assert_eq!((picked.name.as_str(), picked.source.as_str()), ("flexible", "photographer"));
+        let picked = rules.resolve(Some(4), Some(9), Some("wedding"));
+        assert_eq!(picked.source, "demand_type");
+        let picked = rules.resolve(None, None, Some("portrait"));
+        assert_eq!(picked.source, "default");
+        assert_eq!(picked.apply("full", 3), (Decimal::ZERO, None));
+        assert_eq!(RefundPolicySet::default().resolve(None, None, None), builtin_policy());
+    }
+
+    #[test]
+    fn test_rule_set_rejects_invalid() {
+        assert!(RefundPolicySet::parse(&serde_json::json!({
+            "policies": { "a": { "tiers": [] } }
+        }))
+        .is_err());
+        assert!(RefundPolicySet::parse(&serde_json::json!({
+            "policies": { "a": { "tiers": [{ "ratio": 1.2 }] } }
+        }))
+        .is_err());
+        assert!(RefundPolicySet::parse(&serde_json::json!({
+            "policies": { "a": { "tiers": [{ "ratio": 0.5 }] } },
+            "merchants": { "1": "missing" }
+        }))
+        .is_err());
+    }
+}
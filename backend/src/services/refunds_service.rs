@@ -1,8 +1,18 @@
-use std::str::FromStr;
+use sea_orm::{ActiveValue::Set, ConnectionTrait, TransactionTrait};
+use serde_json::json;
 
-use crate::dto::refunds::{CreateRefundReq, RefundResp};
+use crate::dto::pagination::{normalize_pagination, Paged};
+use crate::dto::refunds::{
+    ApproveRefundReq, CompleteRefundReq, CreateRefundReq, RefundListQuery, RefundResp,
+    RejectRefundReq, ReviewRefundReq,
+};
+use crate::entity::{orders, payments, refunds};
 use crate::errors::{DomainError, ServiceResult};
-use crate::repositories::orders_repo;
+use crate::money::Money;
+use crate::repositories::{audit_repo, orders_repo, refunds_repo, users_repo};
+use crate::services::provider_access_service::{self, OrderParty, ProviderAction};
+use crate::services::{ledger_service, order_state_service};
+use crate::services::roles_service::ensure_permission;
 use crate::state::AppState;
 
 pub async fn create_refund(
@@ -10,7 +20,7 @@ pub async fn create_refund(
     user_id: i64,
     req: CreateRefundReq,
 ) -> ServiceResult<RefundResp> {
-    if req.amount <= 0.0 {
+    if !req.amount.is_positive() {
         return Err(DomainError::InvalidAmount.into());
     }
     if let Some(party) = req.responsible_party.as_deref()
@@ -19,46 +29,429 @@ pub async fn create_refund(
         return Err(DomainError::BadRequest("invalid_responsible_party".to_string()).into());
     }
 
-    let order = orders_repo::find_order_by_id(&state.orm, req.order_id)
+    let txn = state.orm.begin().await?;
+    let order = orders_repo::find_order_for_update(&txn, req.order_id)
         .await?
         .ok_or(DomainError::NotFound)?;
 
-    let photographer_user = match order.photographer_id {
-        Some(pid) => orders_repo::find_photographer_user_id(&state.orm, pid).await?,
-        None => None,
+    provider_access_service::ensure_order_participant(
+        &txn,
+        &order,
+        user_id,
+        ProviderAction::ManageOrder,
+    )
+    .await?;
+    order_state_service::ensure_not_frozen(&order)?;
+
+    let amount = req.amount;
+    if amount > refundable_balance(&txn, order.id, None).await? {
+        return Err(DomainError::BadRequest("refund_exceeds_paid".to_string()).into());
+    }
+
+    let refund = refunds::ActiveModel {
+        order_id: Set(req.order_id),
+        applicant_id: Set(user_id),
+        amount: Set(amount.amount()),
+        status: Set("pending".to_string()),
+        responsible_party: Set(req.responsible_party),
+        reason: Set(req.reason),
+        proof_url: Set(req.proof_url),
+        ..Default::default()
+    };
+
+    let inserted = orders_repo::create_refund(&txn, refund).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        user_id,
+        "refund_create",
+        "refund",
+        inserted.id,
+        json!({ "order_id": inserted.order_id, "amount": Money::from(inserted.amount) }),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(to_refund_resp(inserted))
+}
+
+/// 支付落定后须退回的款项（超付部分、已取消订单的整笔支付）生成待审核退款，申请人为付款方。
+pub async fn create_payment_refund<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+    payment: &payments::Model,
+    amount: Money,
+    reason: &str,
+) -> ServiceResult<refunds::Model> {
+    let refund = refunds::ActiveModel {
+        order_id: Set(order.id),
+        applicant_id: Set(payment.payer_id),
+        amount: Set(amount.amount()),
+        status: Set("pending".to_string()),
+        responsible_party: Set(None),
+        reason: Set(Some(reason.to_string())),
+        proof_url: Set(None),
+        ..Default::default()
+    };
+
+    let inserted = orders_repo::create_refund(conn, refund).await?;
+    audit_repo::create_audit_log(
+        conn,
+        payment.payer_id,
+        "refund_auto_create",
+        "refund",
+        inserted.id,
+        json!({
+            "order_id": order.id,
+            "payment_id": payment.id,
+            "amount": amount,
+            "reason": reason
+        }),
+    )
+    .await?;
+    Ok(inserted)
+}
+
+pub async fn list_refunds(
+    state: &AppState,
+    user_id: i64,
+    query: RefundListQuery,
+) -> ServiceResult<Paged<RefundResp>> {
+    let (page, page_size) = normalize_pagination(query.page, query.page_size);
+    if let Some(status) = query.status.as_deref()
+        && !matches!(status, "pending" | "approved" | "rejected" | "completed")
+    {
+        return Err(DomainError::InvalidStatus.into());
+    }
+
+    let photographer_ids = users_repo::list_photographer_ids(&state.orm, user_id).await?;
+    let team_ids = provider_access_service::list_team_ids(&state.orm, user_id).await?;
+    let (rows, total) = refunds_repo::list_refunds_for_participant(
+        &state.orm,
+        user_id,
+        photographer_ids,
+        team_ids,
+        query.status,
+        page,
+        page_size,
+    )
+    .await?;
+
+    let items = rows.into_iter().map(to_refund_resp).collect();
+    Ok(Paged::new(items, total, page, page_size))
+}
+
+pub async fn approve_refund(
+    state: &AppState,
+    user_id: i64,
+    refund_id: i64,
+    req: ApproveRefundReq,
+) -> ServiceResult<RefundResp> {
+    let txn = state.orm.begin().await?;
+    let (refund, order) = load_refund_with_order(&txn, refund_id).await?;
+    ensure_counterparty(&txn, &refund, &order, user_id).await?;
+    order_state_service::ensure_not_frozen(&order)?;
+    if refund.status != "pending" {
+        return Err(DomainError::BadRequest("refund_not_pending".to_string()).into());
+    }
+
+    let approved = resolve_approved_amount(&txn, &refund, req.amount).await?;
+    let updated = apply_review(&txn, refund, "approved", Some(approved), user_id, req.note).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        user_id,
+        "refund_approve",
+        "refund",
+        updated.id,
+        json!({
+            "order_id": updated.order_id,
+            "approved_amount": approved,
+            "note": updated.review_note,
+        }),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(to_refund_resp(updated))
+}
+
+pub async fn reject_refund(
+    state: &AppState,
+    user_id: i64,
+    refund_id: i64,
+    req: RejectRefundReq,
+) -> ServiceResult<RefundResp> {
+    let txn = state.orm.begin().await?;
+    let (refund, order) = load_refund_with_order(&txn, refund_id).await?;
+    ensure_counterparty(&txn, &refund, &order, user_id).await?;
+    order_state_service::ensure_not_frozen(&order)?;
+    if refund.status != "pending" {
+        return Err(DomainError::BadRequest("refund_not_pending".to_string()).into());
+    }
+
+    let updated = apply_review(&txn, refund, "rejected", None, user_id, req.note).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        user_id,
+        "refund_reject",
+        "refund",
+        updated.id,
+        json!({ "order_id": updated.order_id, "note": updated.review_note }),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(to_refund_resp(updated))
+}
+
+/// 平台仲裁：可处理待审核或已被对方拒绝的退款，结论覆盖双方意见。
+pub async fn review_refund(
+    state: &AppState,
+    operator_id: i64,
+    refund_id: i64,
+    req: ReviewRefundReq,
+) -> ServiceResult<RefundResp> {
+    ensure_permission(&state.orm, operator_id, "refunds.review").await?;
+    if !matches!(req.status.as_str(), "approved" | "rejected") {
+        return Err(DomainError::InvalidStatus.into());
+    }
+    if req.note.trim().is_empty() {
+        return Err(DomainError::BadRequest("review_note_required".to_string()).into());
+    }
+
+    let txn = state.orm.begin().await?;
+    let (refund, _order) = load_refund_with_order(&txn, refund_id).await?;
+    if !matches!(refund.status.as_str(), "pending" | "rejected") {
+        return Err(DomainError::BadRequest("refund_not_reviewable".to_string()).into());
+    }
+
+    let previous_status = refund.status.clone();
+    let approved = if req.status == "approved" {
+        Some(resolve_approved_amount(&txn, &refund, req.amount).await?)
+    } else {
+        None
     };
+    let updated =
+        apply_review(&txn, refund, &req.status, approved, operator_id, Some(req.note)).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        operator_id,
+        "refund_review",
+        "refund",
+        updated.id,
+        json!({
+            "order_id": updated.order_id,
+            "from_status": previous_status,
+            "status": updated.status,
+            "approved_amount": approved,
+            "note": updated.review_note,
+        }),
+    )
+    .await?;
+    txn.commit().await?;
 
-    if order.user_id != user_id && photographer_user != Some(user_id) {
+    Ok(to_refund_resp(updated))
+}
+
+/// 直付模式下由收款方线下退回款项后上传凭证完成退款。
+pub async fn complete_refund(
+    state: &AppState,
+    user_id: i64,
+    refund_id: i64,
+    req: CompleteRefundReq,
+) -> ServiceResult<RefundResp> {
+    if req.proof_url.trim().is_empty() {
+        return Err(DomainError::BadRequest("proof_required".to_string()).into());
+    }
+
+    let txn = state.orm.begin().await?;
+    let (refund, order) = load_refund_with_order(&txn, refund_id).await?;
+    let payees: Vec<i64> = orders_repo::list_success_payments(&txn, order.id)
+        .await?
+        .into_iter()
+        .map(|p| p.payee_id)
+        .collect();
+    if !payees.contains(&user_id) {
         return Err(DomainError::Forbidden.into());
     }
+    if refund.status != "approved" {
+        return Err(DomainError::BadRequest("refund_not_approved".to_string()).into());
+    }
 
-    let refund = crate::entity::refunds::ActiveModel {
-        order_id: sea_orm::ActiveValue::Set(req.order_id),
-        applicant_id: sea_orm::ActiveValue::Set(user_id),
-        amount: sea_orm::ActiveValue::Set(decimal_from_f64(req.amount)),
-        status: sea_orm::ActiveValue::Set("pending".to_string()),
-        responsible_party: sea_orm::ActiveValue::Set(req.responsible_party),
-        reason: sea_orm::ActiveValue::Set(req.reason),
-        proof_url: sea_orm::ActiveValue::Set(req.proof_url),
-        ..Default::default()
+    let now = chrono::Utc::now();
+    let mut model: refunds::ActiveModel = refund.into();
+    model.status = Set("completed".to_string());
+    model.completion_proof_url = Set(Some(req.proof_url.trim().to_string()));
+    model.completed_at = Set(Some(now.into()));
+    model.updated_at = Set(now.into());
+    let updated = refunds_repo::update_refund(&txn, model).await?;
+    ledger_service::record_refund(&txn, &updated, &order).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        user_id,
+        "refund_complete",
+        "refund",
+        updated.id,
+        json!({ "order_id": updated.order_id, "proof_url": updated.completion_proof_url }),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(to_refund_resp(updated))
+}
+
+/// 订单仍可退款的余额：成功支付合计减去未被拒绝的退款（已批准按批准金额计）。
+pub async fn refundable_balance<C: ConnectionTrait>(
+    conn: &C,
+    order_id: i64,
+    exclude_refund_id: Option<i64>,
+) -> ServiceResult<Money> {
+    let paid: Money = orders_repo::list_success_payments(conn, order_id)
+        .await?
+        .into_iter()
+        .map(|p| Money::from(p.amount))
+        .sum();
+    let committed: Money = refunds_repo::list_refunds_by_order(conn, order_id)
+        .await?
+        .iter()
+        .filter(|r| Some(r.id) != exclude_refund_id)
+        .map(committed_amount)
+        .sum();
+    Ok(paid.saturating_sub(committed))
+}
+
+fn committed_amount(refund: &refunds::Model) -> Money {
+    match refund.status.as_str() {
+        "rejected" => Money::ZERO,
+        "approved" | "completed" => Money::from(refund.approved_amount.unwrap_or(refund.amount)),
+        _ => Money::from(refund.amount),
+    }
+}
+
+async fn load_refund_with_order<C: ConnectionTrait>(
+    conn: &C,
+    refund_id: i64,
+) -> ServiceResult<(refunds::Model, orders::Model)> {
+    let refund = refunds_repo::find_refund_by_id(conn, refund_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    // 锁定订单行，保证同一订单的退款额度校验串行执行。
+    let order = orders_repo::find_order_for_update(conn, refund.order_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    Ok((refund, order))
+}
+
+/// 退款由订单另一方处理：用户申请由服务方处理，服务方申请由用户处理。
+async fn ensure_counterparty<C: ConnectionTrait>(
+    conn: &C,
+    refund: &refunds::Model,
+    order: &orders::Model,
+    user_id: i64,
+) -> ServiceResult<()> {
+    let party = provider_access_service::ensure_order_participant(
+        conn,
+        order,
+        user_id,
+        ProviderAction::ManageOrder,
+    )
+    .await?;
+    let applicant_party = if refund.applicant_id == order.user_id {
+        OrderParty::Customer
+    } else {
+        OrderParty::Provider
     };
+    if party == applicant_party {
+        return Err(DomainError::Forbidden.into());
+    }
+    Ok(())
+}
 
-    let inserted = orders_repo::create_refund(&state.orm, refund).await?;
+async fn resolve_approved_amount<C: ConnectionTrait>(
+    conn: &C,
+    refund: &refunds::Model,
+    amount: Option<Money>,
+) -> ServiceResult<Money> {
+    let requested = Money::from(refund.amount);
+    let approved = amount.unwrap_or(requested);
+    if !approved.is_positive() || approved > requested {
+        return Err(DomainError::InvalidAmount.into());
+    }
+    if approved > refundable_balance(conn, refund.order_id, Some(refund.id)).await? {
+        return Err(DomainError::BadRequest("refund_exceeds_paid".to_string()).into());
+    }
+    Ok(approved)
+}
 
-    Ok(RefundResp {
-        id: inserted.id,
-        order_id: inserted.order_id,
-        amount: decimal_to_f64(inserted.amount),
-        status: inserted.status,
-        responsible_party: inserted.responsible_party,
-    })
+async fn apply_review<C: ConnectionTrait>(
+    conn: &C,
+    refund: refunds::Model,
+    status: &str,
+    approved_amount: Option<Money>,
+    reviewer_id: i64,
+    note: Option<String>,
+) -> ServiceResult<refunds::Model> {
+    let now = chrono::Utc::now();
+    let mut model: refunds::ActiveModel = refund.into();
+    model.status = Set(status.to_string());
+    model.approved_amount = Set(approved_amount.map(Money::amount));
+    model.reviewer_id = Set(Some(reviewer_id));
+    model.review_note = Set(note.filter(|n| !n.trim().is_empty()));
+    model.reviewed_at = Set(Some(now.into()));
+    model.updated_at = Set(now.into());
+    Ok(refunds_repo::update_refund(conn, model).await?)
 }
 
-fn decimal_to_f64(v: sea_orm::prelude::Decimal) -> f64 {
-    v.to_string().parse::<f64>().unwrap_or(0.0)
+fn to_refund_resp(r: refunds::Model) -> RefundResp {
+    RefundResp {
+        id: r.id,
+        order_id: r.order_id,
+        applicant_id: r.applicant_id,
+        amount: Money::from(r.amount),
+        approved_amount: r.approved_amount.map(Money::from),
+        status: r.status,
+        responsible_party: r.responsible_party,
+        reason: r.reason,
+        proof_url: r.proof_url,
+        review_note: r.review_note,
+        completion_proof_url: r.completion_proof_url,
+        created_at: r.created_at.to_rfc3339(),
+        updated_at: r.updated_at.to_rfc3339(),
+    }
 }
 
-fn decimal_from_f64(v: f64) -> sea_orm::prelude::Decimal {
-    sea_orm::prelude::Decimal::from_str(&v.to_string())
-        .unwrap_or(sea_orm::prelude::Decimal::ZERO)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::prelude::Decimal;
+
+    fn refund(status: &str, amount: i64, approved: Option<i64>) -> refunds::Model {
+        let now = chrono::Utc::now().into();
+        refunds::Model {
+            id: 1,
+            order_id: 1,
+            applicant_id: 1,
+            amount: Decimal::from(amount),
+            status: status.to_string(),
+            responsible_party: None,
+            reason: None,
+            proof_url: None,
+            created_at: now,
+            updated_at: now,
+            approved_amount: approved.map(Decimal::from),
+            reviewer_id: None,
+            review_note: None,
+            reviewed_at: None,
+            completion_proof_url: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_committed_amount_uses_approved_amount() {
+        let money = |v: i64| Money::from(Decimal::from(v));
+        assert_eq!(committed_amount(&refund("pending", 100, None)), money(100));
+        assert_eq!(committed_amount(&refund("approved", 100, Some(60))), money(60));
+        assert_eq!(committed_amount(&refund("completed", 100, Some(60))), money(60));
+        assert_eq!(committed_amount(&refund("rejected", 100, None)), Money::ZERO);
+    }
 }
@@ -0,0 +1,278 @@
+use std::collections::{BTreeSet, HashMap};
+
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use serde_json::json;
+
+use crate::dto::roles::{
+    GrantRoleReq, RoleResp, UpdateRolePermissionsReq, UserRoleItem, UserRolesResp,
+};
+use crate::errors::{DomainError, ServiceResult};
+use crate::repositories::{audit_repo, roles_repo, users_repo};
+use crate::state::AppState;
+
+pub const DEFAULT_ROLE: &str = "user";
+
+pub const PERMISSIONS: &[&str] = &[
+    "audits.read",
+    "audits.write",
+    "metrics.read",
+    "reports.export",
+    "users.read",
+    "users.ban",
+    "users.reset_password",
+    "orders.read",
+    "orders.freeze",
+    "refunds.review",
+    "ledger.read",
+    "disputes.read",
+    "disputes.resolve",
+    "portfolios.review",
+    "merchants.review",
+    "photographers.review",
+    "roles.manage",
+    "configs.manage",
+];
+
+pub async fn resolve_roles<C: ConnectionTrait>(conn: &C, user_id: i64) -> ServiceResult<Vec<String>> {
+    let role_ids = roles_repo::list_user_role_ids(conn, user_id).await?;
+    let mut roles: Vec<String> = roles_repo::list_roles_by_ids(conn, role_ids)
+        .await?
+        .into_iter()
+        .map(|r| r.name)
+        .collect();
+    if roles.is_empty() {
+        roles.push(DEFAULT_ROLE.to_string());
+    }
+    Ok(roles)
+}
+
+pub async fn resolve_permissions<C: ConnectionTrait>(
+    conn: &C,
+    user_id: i64,
+) -> ServiceResult<Vec<String>> {
+    let role_ids = roles_repo::list_user_role_ids(conn, user_id).await?;
+    let permissions: BTreeSet<String> = roles_repo::list_permissions_by_role_ids(conn, role_ids)
+        .await?
+        .into_iter()
+        .map(|p| p.permission)
+        .collect();
+    Ok(permissions.into_iter().collect())
+}
+
+pub async fn ensure_permission<C: ConnectionTrait>(
+    conn: &C,
+    user_id: i64,
+    permission: &str,
+) -> ServiceResult<()> {
+    let permissions = resolve_permissions(conn, user_id).await?;
+    if permissions.iter().any(|p| p == permission) {
+        Ok(())
+    } else {
+        Err(DomainError::Forbidden.into())
+    }
+}
+
+pub async fn list_roles(state: &AppState, operator_id: i64) -> ServiceResult<Vec<RoleResp>> {
+    ensure_permission(&state.orm, operator_id, "roles.manage").await?;
+
+    let roles = roles_repo::list_roles(&state.orm).await?;
+    let role_ids: Vec<i64> = roles.iter().map(|r| r.id).collect();
+    let mut permission_map: HashMap<i64, Vec<String>> = HashMap::new();
+    for row in roles_repo::list_permissions_by_role_ids(&state.orm, role_ids).await? {
+        permission_map.entry(row.role_id).or_default().push(row.permission);
+    }
+
+    Ok(roles
+        .into_iter()
+        .map(|r| RoleResp {
+            id: r.id,
+            permissions: permission_map.remove(&r.id).unwrap_or_default(),
+            name: r.name,
+        })
+        .collect())
+}
+
+pub async fn update_role_permissions(
+    state: &AppState,
+    operator_id: i64,
+    role_name: String,
+    req: UpdateRolePermissionsReq,
+) -> ServiceResult<RoleResp> {
+    ensure_permission(&state.orm, operator_id, "roles.manage").await?;
+    let permissions = normalize_permissions(req.permissions)?;
+
+    let txn = state.orm.begin().await?;
+    let role = roles_repo::find_role_by_name(&txn, &role_name)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if role.name == "admin" && !permissions.iter().any(|p| p == "roles.manage") {
+        return Err(DomainError::BadRequest("admin_requires_roles_manage".to_string()).into());
+    }
+
+    roles_repo::replace_role_permissions(&txn, role.id, permissions.clone()).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        operator_id,
+        "role_permissions_update",
+        "role",
+        role.id,
+        json!({ "role": role.name, "permissions": permissions }),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(RoleResp {
+        id: role.id,
+        name: role.name,
+        permissions,
+    })
+}
+
+pub async fn list_user_roles(
+    state: &AppState,
+    operator_id: i64,
+    target_user_id: i64,
+) -> ServiceResult<UserRolesResp> {
+    ensure_permission(&state.orm, operator_id, "roles.manage").await?;
+    users_repo::find_user_by_id(&state.orm, target_user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    build_user_roles_resp(&state.orm, target_user_id).await
+}
+
+pub async fn grant_role(
+    state: &AppState,
+    operator_id: i64,
+    target_user_id: i64,
+    req: GrantRoleReq,
+) -> ServiceResult<UserRolesResp> {
+    ensure_permission(&state.orm, operator_id, "roles.manage").await?;
+
+    let txn = state.orm.begin().await?;
+    users_repo::find_user_by_id(&txn, target_user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let role = roles_repo::find_role_by_name(&txn, req.role.trim())
+        .await?
+        .ok_or(DomainError::InvalidRole)?;
+    if roles_repo::find_user_role(&txn, target_user_id, role.id)
+        .await?
+        .is_some()
+    {
+        return Err(DomainError::Conflict("role_exists".to_string()).into());
+    }
+
+    roles_repo::create_user_role(&txn, target_user_id, role.id, req.scope.clone()).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        operator_id,
+        "user_role_grant",
+        "user",
+        target_user_id,
+        json!({ "role": role.name, "scope": req.scope }),
+    )
+    .await?;
+    txn.commit().await?;
+    state.auth_cache.invalidate(target_user_id);
+
+    build_user_roles_resp(&state.orm, target_user_id).await
+}
+
+pub async fn revoke_role(
+    state: &AppState,
+    operator_id: i64,
+    target_user_id: i64,
+    role_name: String,
+) -> ServiceResult<UserRolesResp> {
+    ensure_permission(&state.orm, operator_id, "roles.manage").await?;
+    if operator_id == target_user_id && role_name == "admin" {
+        return Err(DomainError::BadRequest("cannot_revoke_own_admin".to_string()).into());
+    }
+
+    let txn = state.orm.begin().await?;
+    let role = roles_repo::find_role_by_name(&txn, &role_name)
+        .await?
+        .ok_or(DomainError::InvalidRole)?;
+    roles_repo::find_user_role(&txn, target_user_id, role.id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    roles_repo::delete_user_role(&txn, target_user_id, role.id).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        operator_id,
+        "user_role_revoke",
+        "user",
+        target_user_id,
+        json!({ "role": role.name }),
+    )
+    .await?;
+    txn.commit().await?;
+    state.auth_cache.invalidate(target_user_id);
+
+    build_user_roles_resp(&state.orm, target_user_id).await
+}
+
+async fn build_user_roles_resp<C: ConnectionTrait>(
+    conn: &C,
+    user_id: i64,
+) -> ServiceResult<UserRolesResp> {
+    let rows = roles_repo::list_user_roles(conn, user_id).await?;
+    let role_ids: Vec<i64> = rows.iter().map(|r| r.role_id).collect();
+    let role_map: HashMap<i64, String> = roles_repo::list_roles_by_ids(conn, role_ids)
+        .await?
+        .into_iter()
+        .map(|r| (r.id, r.name))
+        .collect();
+
+    let roles = rows
+        .into_iter()
+        .filter_map(|r| {
+            role_map.get(&r.role_id).map(|name| UserRoleItem {
+                role: name.clone(),
+                scope: r.scope,
+            })
+        })
+        .collect();
+    let permissions = resolve_permissions(conn, user_id).await?;
+
+    Ok(UserRolesResp {
+        user_id,
+        roles,
+        permissions,
+    })
+}
+
+fn normalize_permissions(input: Vec<String>) -> Result<Vec<String>, DomainError> {
+    let mut set = BTreeSet::new();
+    for permission in input {
+        let permission = permission.trim().to_string();
+        if !PERMISSIONS.contains(&permission.as_str()) {
+            return Err(DomainError::BadRequest("invalid_permission".to_string()));
+        }
+        set.insert(permission);
+    }
+    Ok(set.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_permissions_dedups_and_sorts() {
+        let perms = normalize_permissions(vec![
+            "orders.read".to_string(),
+            " metrics.read ".to_string(),
+            "orders.read".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(perms, vec!["metrics.read".to_string(), "orders.read".to_string()]);
+    }
+
+    #[test]
+    fn test_normalize_permissions_rejects_unknown() {
+        assert!(normalize_permissions(vec!["orders.delete".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use axum::http::HeaderMap;
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{ActiveValue::Set, ConnectionTrait, TransactionTrait};
+use uuid::Uuid;
+
+use crate::dto::sessions::{RefreshTokenReq, RevokeSessionResp, SessionItem, TokenResp};
+use crate::entity::sessions;
+use crate::errors::{DomainError, ServiceResult};
+use crate::middleware::auth::is_user_active;
+use crate::repositories::{sessions_repo, users_repo};
+use crate::state::AppState;
+
+pub const ACCESS_TOKEN_TTL_HOURS: i64 = 2;
+pub const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+#[derive(Default)]
+pub struct SessionMeta {
+    pub user_agent: Option<String>,
+    pub device_name: Option<String>,
+    pub ip: Option<String>,
+}
+
+impl SessionMeta {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name: &str, max: usize| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.chars().take(max).collect::<String>())
+        };
+        let ip = header("x-forwarded-for", 256)
+            .and_then(|v| v.split(',').next().map(|s| s.trim().to_string()))
+            .or_else(|| header("x-real-ip", 64))
+            .map(|v| v.chars().take(64).collect());
+
+        Self {
+            user_agent: header("user-agent", 512),
+            device_name: header("x-device-name", 128),
+            ip,
+        }
+    }
+}
+
+pub async fn issue_session<C: ConnectionTrait>(
+    conn: &C,
+    user_id: i64,
+    meta: SessionMeta,
+) -> ServiceResult<TokenResp> {
+    let now = Utc::now();
+    let (expired_at, refresh_expired_at) = token_expiry(now);
+    let session = sessions::ActiveModel {
+        user_id: Set(user_id),
+        token: Set(new_token()),
+        refresh_token: Set(Some(new_token())),
+        expired_at: Set(expired_at.into()),
+        refresh_expired_at: Set(Some(refresh_expired_at.into())),
+        user_agent: Set(meta.user_agent),
+        device_name: Set(meta.device_name),
+        ip: Set(meta.ip),
+        last_seen_at: Set(Some(now.into())),
+        ..Default::default()
+    };
+    let created = sessions_repo::create_session(conn, session).await?;
+
+    Ok(TokenResp {
+        token: created.token,
+        refresh_token: created.refresh_token.unwrap_or_default(),
+        expired_at: expired_at.timestamp(),
+        refresh_expired_at: refresh_expired_at.timestamp(),
+    })
+}
+
+pub async fn refresh_session(
+    state: &AppState,
+    req: RefreshTokenReq,
+    meta: SessionMeta,
+) -> ServiceResult<TokenResp> {
+    let now = Utc::now();
+    let presented = req.refresh_token.trim();
+    let txn = state.orm.begin().await?;
+    let Some(session) = sessions_repo::find_by_refresh_token(&txn, presented).await? else {
+        // 已轮换的刷新令牌被再次使用，说明令牌可能已泄露：吊销整个会话。
+        if let Some(session_id) =
+            sessions_repo::find_session_id_by_rotated_token(&txn, presented).await?
+        {
+            tracing::warn!(session_id, "refresh_token_reused");
+            sessions_repo::revoke_session(&txn, session_id, now).await?;
+            txn.commit().await?;
+        }
+        return Err(DomainError::Unauthorized.into());
+    };
+    let refresh_valid = session
+        .refresh_expired_at
+        .map(|t| t.with_timezone(&Utc) > now)
+        .unwrap_or(false);
+    if session.revoked_at.is_some() || !refresh_valid {
+        return Err(DomainError::Unauthorized.into());
+    }
+    let user = users_repo::find_user_by_id(&txn, session.user_id)
+        .await?
+        .ok_or(DomainError::Unauthorized)?;
+    if !is_user_active(&user) {
+        return Err(DomainError::AccountDisabled.into());
+    }
+
+    let (expired_at, refresh_expired_at) = token_expiry(now);
+    let tokens = sessions_repo::RotateTokens {
+        token: new_token(),
+        refresh_token: new_token(),
+        expired_at,
+        refresh_expired_at,
+        user_agent: meta.user_agent,
+        ip: meta.ip,
+    };
+    let resp = TokenResp {
+        token: tokens.token.clone(),
+        refresh_token: tokens.refresh_token.clone(),
+        expired_at: expired_at.timestamp(),
+        refresh_expired_at: refresh_expired_at.timestamp(),
+    };
+
+    let old_refresh = session.refresh_token.unwrap_or_default();
+    let rotated =
+        sessions_repo::rotate_session_tokens(&txn, session.id, &old_refresh, tokens, now).await?;
+    if rotated != 1 {
+        // 并发请求已先一步兑换同一刷新令牌，同样视为重复使用。
+        tracing::warn!(session_id = session.id, "refresh_token_reused");
+        sessions_repo::revoke_session(&txn, session.id, now).await?;
+        txn.commit().await?;
+        return Err(DomainError::Unauthorized.into());
+    }
+    sessions_repo::record_rotated_token(&txn, session.id, &old_refresh, now).await?;
+    txn.commit().await?;
+
+    Ok(resp)
+}
+
+pub async fn logout(
+    state: &AppState,
+    session_id: i64,
+) -> ServiceResult<RevokeSessionResp> {
+    sessions_repo::revoke_session(&state.orm, session_id, Utc::now()).await?;
+    Ok(RevokeSessionResp {
+        id: session_id,
+        revoked: true,
+    })
+}
+
+pub async fn list_sessions(
+    state: &AppState,
+    user_id: i64,
+    current_session_id: i64,
+) -> ServiceResult<Vec<SessionItem>> {
+    let rows = sessions_repo::list_active_sessions(&state.orm, user_id, Utc::now()).await?;
+    Ok(rows
+        .into_iter()
+        .map(|s| SessionItem {
+            id: s.id,
+            device_name: s.device_name,
+            user_agent: s.user_agent,
+            ip: s.ip,
+            created_at: s.created_at.to_rfc3339(),
+            last_seen_at: s.last_seen_at.map(|t| t.to_rfc3339()),
+            expired_at: s.refresh_expired_at.unwrap_or(s.expired_at).to_rfc3339(),
+            current: s.id == current_session_id,
+        })
+        .collect())
+}
+
+pub async fn revoke_session(
+    state: &AppState,
+    user_id: i64,
+    session_id: i64,
+) -> ServiceResult<RevokeSessionResp> {
+    let session = sessions_repo::find_session_by_id(&state.orm, session_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if session.user_id != user_id {
+        return Err(DomainError::NotFound.into());
+    }
+
+    sessions_repo::revoke_session(&state.orm, session_id, Utc::now()).await?;
+    Ok(RevokeSessionResp {
+        id: session_id,
+        revoked: true,
+    })
+}
+
+fn token_expiry(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        now + Duration::hours(ACCESS_TOKEN_TTL_HOURS),
+        now + Duration::days(REFRESH_TOKEN_TTL_DAYS),
+    )
+}
+
+fn new_token() -> String {
+    Uuid::new_v4().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_meta_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("user-agent", "LensDate/1.0 (iOS)".parse().unwrap());
+        headers.insert("x-device-name", "iPhone".parse().unwrap());
+        headers.insert("x-forwarded-for", "10.0.0.1, 172.16.0.1".parse().unwrap());
+        let meta = SessionMeta::from_headers(&headers);
+        assert_eq!(meta.user_agent.as_deref(), Some("LensDate/1.0 (iOS)"));
+        assert_eq!(meta.device_name.as_deref(), Some("iPhone"));
+        assert_eq!(meta.ip.as_deref(), Some("10.0.0.1"));
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
+use sea_orm::TransactionTrait;
+
 use crate::dto::teams::{AddTeamMemberReq, CreateTeamReq, TeamMemberResp, TeamResp, UpdateTeamReq};
 use crate::errors::{DomainError, ServiceResult};
 use crate::repositories::teams_repo;
+use crate::services::provider_access_service::{self, ProviderAction, TeamRole};
 use crate::state::AppState;
 
 pub async fn create_team(
@@ -12,7 +15,11 @@ pub async fn create_team(
 ) -> ServiceResult<TeamResp> {
     validate_team_name(&req.name)?;
 
-    let team = teams_repo::create_team(&state.orm, user_id, req.name, "active".to_string()).await?;
+    let txn = state.orm.begin().await?;
+    let team = teams_repo::create_team(&txn, user_id, req.name, "active".to_string()).await?;
+    teams_repo::create_team_member(&txn, team.id, user_id, TeamRole::Owner.as_str().to_string())
+        .await?;
+    txn.commit().await?;
 
     Ok(TeamResp {
         id: team.id,
@@ -41,7 +48,7 @@ pub async fn list_teams(state: &AppState, user_id: i64) -> ServiceResult<Vec<Tea
         .into_iter()
         .map(|row| {
             let role = if row.owner_user_id == user_id {
-                "owner".to_string()
+                TeamRole::Owner.as_str().to_string()
             } else {
                 role_map
                     .get(&row.id)
@@ -91,11 +98,22 @@ pub async fn add_member(
     team_id: i64,
     req: AddTeamMemberReq,
 ) -> ServiceResult<TeamMemberResp> {
-    ensure_team_admin(state, user_id, team_id).await?;
+    let operator_role = provider_access_service::ensure_team_action(
+        &state.orm,
+        team_id,
+        user_id,
+        ProviderAction::ManageMembers,
+    )
+    .await?;
 
     let role = req.role.unwrap_or_else(|| "member".to_string());
-    if !matches!(role.as_str(), "admin" | "member") {
-        return Err(DomainError::InvalidRole.into());
+    let target_role = match TeamRole::parse(&role) {
+        Some(r @ (TeamRole::Admin | TeamRole::Member)) => r,
+        _ => return Err(DomainError::InvalidRole.into()),
+    };
+    // 管理员可添加成员，添加管理员需团队所有者。
+    if target_role >= operator_role {
+        return Err(DomainError::Forbidden.into());
     }
 
     let existing = teams_repo::find_team_member(&state.orm, team_id, req.user_id).await?;
@@ -117,7 +135,13 @@ pub async fn list_members(
     user_id: i64,
     team_id: i64,
 ) -> ServiceResult<Vec<TeamMemberResp>> {
-    ensure_team_member(state, user_id, team_id).await?;
+    provider_access_service::ensure_team_action(
+        &state.orm,
+        team_id,
+        user_id,
+        ProviderAction::View,
+    )
+    .await?;
 
     let rows = teams_repo::list_team_members(&state.orm, team_id).await?;
 
@@ -139,11 +163,22 @@ pub async fn remove_member(
     team_id: i64,
     member_id: i64,
 ) -> ServiceResult<TeamMemberResp> {
-    ensure_team_admin(state, user_id, team_id).await?;
+    let operator_role = provider_access_service::ensure_team_action(
+        &state.orm,
+        team_id,
+        user_id,
+        ProviderAction::ManageMembers,
+    )
+    .await?;
 
     let member = teams_repo::find_team_member(&state.orm, team_id, member_id)
         .await?
         .ok_or(DomainError::NotFound)?;
+    // 所有者不可移除；管理员只能移除普通成员。
+    let member_role = TeamRole::parse(&member.role).unwrap_or(TeamRole::Member);
+    if member_role >= operator_role {
+        return Err(DomainError::Forbidden.into());
+    }
 
     teams_repo::delete_team_member(&state.orm, team_id, member_id).await?;
 
@@ -154,48 +189,6 @@ pub async fn remove_member(
     })
 }
 
-async fn ensure_team_member(
-    state: &AppState,
-    user_id: i64,
-    team_id: i64,
-) -> ServiceResult<()> {
-    let team = teams_repo::find_team_by_id(&state.orm, team_id)
-        .await?
-        .ok_or(DomainError::NotFound)?;
-
-    if team.owner_user_id == user_id {
-        return Ok(());
-    }
-
-    let member = teams_repo::find_team_member(&state.orm, team_id, user_id).await?;
-    if member.is_some() {
-        Ok(())
-    } else {
-        Err(DomainError::Forbidden.into())
-    }
-}
-
-async fn ensure_team_admin(
-    state: &AppState,
-    user_id: i64,
-    team_id: i64,
-) -> ServiceResult<()> {
-    let team = teams_repo::find_team_by_id(&state.orm, team_id)
-        .await?
-        .ok_or(DomainError::NotFound)?;
-
-    if team.owner_user_id == user_id {
-        return Ok(());
-    }
-
-    let member = teams_repo::find_team_member(&state.orm, team_id, user_id).await?;
-    if member.map(|m| m.role == "admin").unwrap_or(false) {
-        Ok(())
-    } else {
-        Err(DomainError::Forbidden.into())
-    }
-}
-
 fn validate_team_name(name: &str) -> Result<(), DomainError> {
     if name.len() < 2 || name.len() > 50 {
         Err(DomainError::InvalidName)
@@ -0,0 +1,346 @@
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use serde_json::json;
+
+use crate::dto::users::{
+    BanUserReq, ChangePasswordReq, DeleteAccountResp, ForcePasswordResetReq, PasswordResp,
+    UserExport, UserStatusResp,
+};
+use crate::entity::users;
+use crate::errors::{DomainError, ServiceResult};
+use crate::repositories::{audit_repo, sessions_repo, teams_repo, users_repo};
+use crate::services::roles_service::ensure_permission;
+use crate::state::AppState;
+
+pub async fn ban_user(
+    state: &AppState,
+    operator_id: i64,
+    target_user_id: i64,
+    req: BanUserReq,
+) -> ServiceResult<UserStatusResp> {
+    ensure_permission(&state.orm, operator_id, "users.ban").await?;
+    if operator_id == target_user_id {
+        return Err(DomainError::BadRequest("cannot_ban_self".to_string()).into());
+    }
+
+    let txn = state.orm.begin().await?;
+    let user = users_repo::find_user_by_id(&txn, target_user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if user.deleted_at.is_some() || user.status == "deleted" {
+        return Err(DomainError::BadRequest("user_deleted".to_string()).into());
+    }
+    if user.status == "frozen" {
+        return Err(DomainError::Conflict("already_banned".to_string()).into());
+    }
+
+    let updated = users_repo::update_user_status(&txn, user, "frozen").await?;
+    let revoked =
+        sessions_repo::revoke_user_sessions(&txn, target_user_id, chrono::Utc::now()).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        operator_id,
+        "user_ban",
+        "user",
+        target_user_id,
+        json!({ "reason": req.reason, "revoked_sessions": revoked }),
+    )
+    .await?;
+    txn.commit().await?;
+    state.auth_cache.invalidate(target_user_id);
+
+    Ok(UserStatusResp {
+        id: updated.id,
+        status: updated.status,
+        revoked_sessions: revoked,
+    })
+}
+
+pub async fn unban_user(
+    state: &AppState,
+    operator_id: i64,
+    target_user_id: i64,
+    req: BanUserReq,
+) -> ServiceResult<UserStatusResp> {
+    ensure_permission(&state.orm, operator_id, "users.ban").await?;
+
+    let txn = state.orm.begin().await?;
+    let user = users_repo::find_user_by_id(&txn, target_user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    if user.status != "frozen" {
+        return Err(DomainError::BadRequest("user_not_banned".to_string()).into());
+    }
+
+    let updated = users_repo::update_user_status(&txn, user, "active").await?;
+    audit_repo::create_audit_log(
+        &txn,
+        operator_id,
+        "user_unban",
+        "user",
+        target_user_id,
+        json!({ "reason": req.reason }),
+    )
+    .await?;
+    txn.commit().await?;
+    state.auth_cache.invalidate(target_user_id);
+
+    Ok(UserStatusResp {
+        id: updated.id,
+        status: updated.status,
+        revoked_sessions: 0,
+    })
+}
+
+pub async fn delete_account(state: &AppState, user_id: i64) -> ServiceResult<DeleteAccountResp> {
+    let txn = state.orm.begin().await?;
+    let user = users_repo::find_user_by_id(&txn, user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let photographer_ids = users_repo::list_photographer_ids(&txn, user_id).await?;
+    let owned_team_ids = teams_repo::list_owned_team_ids(&txn, user_id).await?;
+    if users_repo::count_open_orders(&txn, user_id, photographer_ids, owned_team_ids).await? > 0 {
+        return Err(DomainError::Conflict("open_orders_exist".to_string()).into());
+    }
+
+    let now = chrono::Utc::now();
+    let phone = user.phone.clone();
+    let updated = users_repo::anonymize_user(&txn, user, now).await?;
+    users_repo::delete_verification_codes(&txn, &phone).await?;
+    let revoked = sessions_repo::revoke_user_sessions(&txn, user_id, now).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        user_id,
+        "user_delete",
+        "user",
+        user_id,
+        json!({ "revoked_sessions": revoked }),
+    )
+    .await?;
+    txn.commit().await?;
+    state.auth_cache.invalidate(user_id);
+
+    Ok(DeleteAccountResp {
+        user_id,
+        status: updated.status,
+        deleted_at: now.to_rfc3339(),
+    })
+}
+
+pub async fn export_account(state: &AppState, user_id: i64) -> ServiceResult<UserExport> {
+    let user = users_repo::find_user_by_id(&state.orm, user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    let profile = users_repo::find_profile(&state.orm, user_id).await?;
+    let photographer_ids = users_repo::list_photographer_ids(&state.orm, user_id).await?;
+
+    Ok(UserExport {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        user: json!({
+            "id": user.id,
+            "username": user.username,
+            "phone": user.phone,
+            "email": user.email,
+            "status": user.status,
+            "credit_score": user.credit_score,
+            "created_at": user.created_at.to_rfc3339(),
+        }),
+        profile: profile.map(|p| {
+            json!({
+                "nickname": p.nickname,
+                "avatar_url": p.avatar_url,
+                "gender": p.gender,
+                "birthday": p.birthday.map(|d| d.to_string()),
+                "city_id": p.city_id,
+                "bio": p.bio,
+            })
+        }),
+        demands: users_repo::export_demands(&state.orm, user_id).await?,
+        quotes: users_repo::export_quotes(&state.orm, user_id, photographer_ids.clone()).await?,
+        orders: users_repo::export_orders(&state.orm, user_id, photographer_ids).await?,
+        payments: users_repo::export_payments(&state.orm, user_id).await?,
+        reviews: users_repo::export_reviews(&state.orm, user_id).await?,
+        messages: users_repo::export_messages(&state.orm, user_id).await?,
+        notifications: users_repo::export_notifications(&state.orm, user_id).await?,
+    })
+}
+
+const PASSWORD_MIN_LEN: usize = 8;
+const PASSWORD_MAX_LEN: usize = 64;
+
+pub async fn change_password(
+    state: &AppState,
+    user_id: i64,
+    session_id: i64,
+    req: ChangePasswordReq,
+) -> ServiceResult<PasswordResp> {
+    let txn = state.orm.begin().await?;
+    let user = users_repo::find_user_by_id(&txn, user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    let action = match user.password_hash.as_deref() {
+        Some(hash) => {
+            let old = req
+                .old_password
+                .as_deref()
+                .ok_or_else(|| DomainError::BadRequest("old_password_required".to_string()))?;
+            let ok = bcrypt::verify(old, hash).map_err(anyhow::Error::from)?;
+            if !ok {
+                return Err(DomainError::BadRequest("invalid_old_password".to_string()).into());
+            }
+            "password_change"
+        }
+        None => "password_set",
+    };
+
+    validate_password_strength(&req.new_password, &user)?;
+    let hash =
+        bcrypt::hash(&req.new_password, bcrypt::DEFAULT_COST).map_err(anyhow::Error::from)?;
+    users_repo::update_password_hash(&txn, user, Some(hash)).await?;
+    let revoked = sessions_repo::revoke_user_sessions_except(
+        &txn,
+        user_id,
+        session_id,
+        chrono::Utc::now(),
+    )
+    .await?;
+    audit_repo::create_audit_log(
+        &txn,
+        user_id,
+        action,
+        "user",
+        user_id,
+        json!({ "revoked_sessions": revoked }),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(PasswordResp {
+        user_id,
+        revoked_sessions: revoked,
+    })
+}
+
+/// Sets a new password after the caller has verified an SMS code for the user,
+/// signing out every existing session.
+pub async fn reset_password<C: ConnectionTrait>(
+    conn: &C,
+    user: users::Model,
+    new_password: &str,
+) -> ServiceResult<PasswordResp> {
+    validate_password_strength(new_password, &user)?;
+    let user_id = user.id;
+    let hash = bcrypt::hash(new_password, bcrypt::DEFAULT_COST).map_err(anyhow::Error::from)?;
+    users_repo::update_password_hash(conn, user, Some(hash)).await?;
+    let revoked = sessions_repo::revoke_user_sessions(conn, user_id, chrono::Utc::now()).await?;
+    audit_repo::create_audit_log(
+        conn,
+        user_id,
+        "password_reset",
+        "user",
+        user_id,
+        json!({ "method": "sms_code", "revoked_sessions": revoked }),
+    )
+    .await?;
+
+    Ok(PasswordResp {
+        user_id,
+        revoked_sessions: revoked,
+    })
+}
+
+pub async fn force_password_reset(
+    state: &AppState,
+    operator_id: i64,
+    target_user_id: i64,
+    req: ForcePasswordResetReq,
+) -> ServiceResult<PasswordResp> {
+    ensure_permission(&state.orm, operator_id, "users.reset_password").await?;
+
+    let txn = state.orm.begin().await?;
+    let user = users_repo::find_user_by_id(&txn, target_user_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    users_repo::update_password_hash(&txn, user, None).await?;
+    let revoked =
+        sessions_repo::revoke_user_sessions(&txn, target_user_id, chrono::Utc::now()).await?;
+    audit_repo::create_audit_log(
+        &txn,
+        operator_id,
+        "password_force_reset",
+        "user",
+        target_user_id,
+        json!({ "reason": req.reason, "revoked_sessions": revoked }),
+    )
+    .await?;
+    txn.commit().await?;
+
+    Ok(PasswordResp {
+        user_id: target_user_id,
+        revoked_sessions: revoked,
+    })
+}
+
+pub fn validate_password_strength(
+    password: &str,
+    user: &users::Model,
+) -> Result<(), DomainError> {
+    let len = password.chars().count();
+    if !(PASSWORD_MIN_LEN..=PASSWORD_MAX_LEN).contains(&len) {
+        return Err(DomainError::BadRequest("password_length_invalid".to_string()));
+    }
+    if password.chars().any(char::is_whitespace) {
+        return Err(DomainError::BadRequest("password_whitespace".to_string()));
+    }
+    let has_letter = password.chars().any(|c| c.is_ascii_alphabetic());
+    let has_digit = password.chars().any(|c| c.is_ascii_digit());
+    if !has_letter || !has_digit {
+        return Err(DomainError::BadRequest("password_too_weak".to_string()));
+    }
+    let lower = password.to_lowercase();
+    let mut identifiers = vec![user.phone.to_lowercase()];
+    if let Some(username) = user.username.as_deref() {
+        identifiers.push(username.to_lowercase());
+    }
+    if identifiers
+        .iter()
+        .any(|id| id.len() >= 4 && lower.contains(id.as_str()))
+    {
+        return Err(DomainError::BadRequest("password_contains_identity".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> users::Model {
+        let now = chrono::Utc::now().into();
+        users::Model {
+            id: 1,
+            username: Some("lens_user".to_string()),
+            phone: "13800138000".to_string(),
+            email: None,
+            password_hash: None,
+            status: "active".to_string(),
+            credit_score: 100,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_password_strength_rules() {
+        let u = user();
+        assert!(validate_password_strength("Sunset2026", &u).is_ok());
+        assert!(validate_password_strength("short1", &u).is_err());
+        assert!(validate_password_strength("onlyletters", &u).is_err());
+        assert!(validate_password_strength("1234567890", &u).is_err());
+        assert!(validate_password_strength("has space1a", &u).is_err());
+        assert!(validate_password_strength("a13800138000", &u).is_err());
+        assert!(validate_password_strength("Lens_User99", &u).is_err());
+    }
+}
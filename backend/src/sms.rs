@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::TokioExecutor;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+
+const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SmsMessage {
+    pub phone: String,
+    pub template: String,
+    pub params: BTreeMap<String, String>,
+}
+
+impl SmsMessage {
+    pub fn verification_code(phone: &str, code: &str) -> Self {
+        let mut params = BTreeMap::new();
+        params.insert("code".to_string(), code.to_string());
+        Self {
+            phone: phone.to_string(),
+            template: "verification_code".to_string(),
+            params,
+        }
+    }
+
+    /// Human readable text for sinks that cannot render provider-side templates.
+    pub fn content(&self) -> String {
+        let text = match self.template.as_str() {
+            "verification_code" => "您的验证码为 {code}，5 分钟内有效，请勿泄露。",
+            _ => "{content}",
+        };
+        render(text, &self.params, false)
+    }
+}
+
+#[async_trait]
+pub trait SmsSender: Send + Sync {
+    async fn send(&self, message: &SmsMessage) -> anyhow::Result<()>;
+}
+
+/// Builds the sender selected by `SMS_PROVIDER`, which must be set explicitly:
+/// `http` (real gateway) or `outbox` (local file, meant for development).
+pub fn sms_sender_from_env() -> anyhow::Result<Arc<dyn SmsSender>> {
+    let provider = env::var("SMS_PROVIDER").context("SMS_PROVIDER is required")?;
+    match provider.trim() {
+        "outbox" => {
+            if env::var("APP_ENV").unwrap_or_default().trim() != "dev" {
+                tracing::warn!("SMS_PROVIDER=outbox outside APP_ENV=dev: codes are not delivered");
+            }
+            let path = env::var("SMS_OUTBOX_PATH").unwrap_or_else(|_| "sms_outbox.log".to_string());
+            Ok(Arc::new(OutboxSmsSender::new(path)))
+        }
+        "http" => Ok(Arc::new(HttpSmsSender::from_env()?)),
+        other => bail!("unsupported SMS_PROVIDER: {other}"),
+    }
+}
+
+/// Development sink: appends every message as one JSON line to a local file.
+pub struct OutboxSmsSender {
+    path: PathBuf,
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl OutboxSmsSender {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl SmsSender for OutboxSmsSender {
+    async fn send(&self, message: &SmsMessage) -> anyhow::Result<()> {
+        let line = serde_json::json!({
+            "phone": message.phone,
+            "template": message.template,
+            "params": message.params,
+            "content": message.content(),
+            "sent_at": chrono::Utc::now().to_rfc3339(),
+        });
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("open sms outbox {}", self.path.display()))?;
+        file.write_all(format!("{line}\n").as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// Generic HTTP provider. The request body is rendered from `SMS_HTTP_BODY_TEMPLATE`
+/// with `{phone}`, `{template}`, `{content}` and `{param.<name>}` placeholders.
+/// Both `https://` (verified against the webpki roots) and plain `http://`
+/// endpoints are supported; every send is bounded by `SMS_HTTP_TIMEOUT_SECS`.
+pub struct HttpSmsSender {
+    url: hyper::Uri,
+    body_template: String,
+    content_type: String,
+    authorization: Option<String>,
+    timeout: Duration,
+    client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
+}
+
+impl HttpSmsSender {
+    pub fn from_env() -> anyhow::Result<Self> {
+        let url: hyper::Uri = env::var("SMS_HTTP_URL")
+            .context("SMS_HTTP_URL is required when SMS_PROVIDER=http")?
+            .parse()
+            .context("invalid SMS_HTTP_URL")?;
+        if !matches!(url.scheme_str(), Some("https" | "http")) {
+            bail!("SMS_HTTP_URL must use https:// or http://");
+        }
+        if url.host().is_none() {
+            bail!("SMS_HTTP_URL has no host");
+        }
+        let body_template = env::var("SMS_HTTP_BODY_TEMPLATE").unwrap_or_else(|_| {
+            r#"{"phone":"{phone}","template":"{template}","content":"{content}"}"#.to_string()
+        });
+        let content_type = env::var("SMS_HTTP_CONTENT_TYPE")
+            .unwrap_or_else(|_| "application/json".to_string());
+        let authorization = env::var("SMS_HTTP_AUTHORIZATION").ok();
+        let timeout = match env::var("SMS_HTTP_TIMEOUT_SECS") {
+            Ok(raw) => raw
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .with_context(|| format!("invalid SMS_HTTP_TIMEOUT_SECS: {raw}"))?,
+            Err(_) => DEFAULT_HTTP_TIMEOUT_SECS,
+        };
+
+        Ok(Self::new(
+            url,
+            body_template,
+            content_type,
+            authorization,
+            Duration::from_secs(timeout),
+        ))
+    }
+
+    pub fn new(
+        url: hyper::Uri,
+        body_template: String,
+        content_type: String,
+        authorization: Option<String>,
+        timeout: Duration,
+    ) -> Self {
+        let connector = HttpsConnectorBuilder::new()
+            .with_webpki_roots()
+            .https_or_http()
+            .enable_http1()
+            .build();
+        Self {
+            url,
+            body_template,
+            content_type,
+            authorization,
+            timeout,
+            client: Client::builder(TokioExecutor::new()).build(connector),
+        }
+    }
+
+    fn render_body(&self, message: &SmsMessage) -> String {
+        let json = self.content_type.contains("json");
+        let mut values = BTreeMap::new();
+        values.insert("phone".to_string(), message.phone.clone());
+        values.insert("template".to_string(), message.template.clone());
+        values.insert("content".to_string(), message.content());
+        for (key, value) in &message.params {
+            values.insert(format!("param.{key}"), value.clone());
+        }
+        render(&self.body_template, &values, json)
+    }
+
+    async fn post(&self, message: &SmsMessage) -> anyhow::Result<()> {
+        let mut req = hyper::Request::post(self.url.clone())
+            .header(hyper::header::CONTENT_TYPE, &self.content_type);
+        if let Some(auth) = &self.authorization {
+            req = req.header(hyper::header::AUTHORIZATION, auth);
+        }
+        let req = req.body(Full::new(Bytes::from(self.render_body(message))))?;
+
+        let resp = self.client.request(req).await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.into_body().collect().await?.to_bytes();
+            bail!(
+                "sms provider returned {status}: {}",
+                String::from_utf8_lossy(&body)
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SmsSender for HttpSmsSender {
+    async fn send(&self, message: &SmsMessage) -> anyhow::Result<()> {
+        // Covers connect, TLS handshake and the response, so a stalled provider
+        // cannot hold the request that triggered the SMS.
+        tokio::time::timeout(self.timeout, self.post(message))
+            .await
+            .map_err(|_| anyhow!("sms provider timed out after {:?}", self.timeout))?
+    }
+}
+
+fn render(template: &str, values: &BTreeMap<String, String>, json_escape: bool) -> String {
+    let mut out = template.to_string();
+    for (key, value) in values {
+        let value = if json_escape {
+            let quoted = serde_json::to_string(value).unwrap_or_default();
+            quoted[1..quoted.len() - 1].to_string()
+        } else {
+            value.clone()
+        };
+        out = out.replace(&format!("{{{key}}}"), &value);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_code_content() {
+        let msg = SmsMessage::verification_code("13800138000", "123456");
+        assert!(msg.content().contains("123456"));
+    }
+
+    #[test]
+    fn test_render_http_body_escapes_json() {
+        let sender = HttpSmsSender::new(
+            "https://localhost/sms".parse().unwrap(),
+            r#"{"to":"{phone}","code":"{param.code}","text":"{content}"}"#.to_string(),
+            "application/json".to_string(),
+            None,
+            Duration::from_secs(1),
+        );
+        let msg = SmsMessage::verification_code("13800138000", "12\"34");
+        let body = sender.render_body(&msg);
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["to"], "13800138000");
+        assert_eq!(parsed["code"], "12\"34");
+    }
+
+    #[tokio::test]
+    async fn test_http_send_times_out() {
+        // Accepts the connection but never answers.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let sender = HttpSmsSender::new(
+            format!("http://{addr}/sms").parse().unwrap(),
+            "{phone}".to_string(),
+            "text/plain".to_string(),
+            None,
+            Duration::from_millis(200),
+        );
+        let msg = SmsMessage::verification_code("13800138000", "123456");
+        let err = sender.send(&msg).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        server.abort();
+    }
+}
@@ -1,6 +1,15 @@
+use std::sync::Arc;
+
 use sea_orm::DatabaseConnection;
 
+use crate::middleware::auth::AuthCache;
+use crate::payment_provider::PaymentGateway;
+use crate::sms::SmsSender;
+
 #[derive(Clone)]
 pub struct AppState {
     pub orm: DatabaseConnection,
+    pub sms: Arc<dyn SmsSender>,
+    pub auth_cache: AuthCache,
+    pub payments: PaymentGateway,
 }